@@ -1,85 +1,174 @@
-use clap::{CommandFactory, Parser};
+use clap::{CommandFactory, Parser, ValueEnum};
 use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
 
 use crate::branch::{
     branch_matches, collect_branches, normalize_branch, normalize_state, print_branch_list,
 };
-use crate::cli::{Cli, Commands, GroupBy, ImportFormat, SavedCommands};
+use crate::calendar::{
+    collect_occurrences, parse_month, print_agenda, print_calendar, print_projections,
+};
+use crate::cli::{
+    Cli, Commands, CsvColumn, CsvQuote, ExportFormat, IdsCommands, ImportFormat, MarkdownHeadings,
+    MarkdownNested, SavedCommands,
+};
 use crate::display::{
-    print_task_list, print_task_list_due_split, print_task_list_grouped, print_task_view,
+    display_width, effective_columns, pad_right, print_task_list, print_task_list_due_split,
+    print_task_list_grouped, print_task_list_templated, print_task_view,
 };
-use crate::edit::edit_interactive;
-use crate::export::export_tasks;
+use crate::edit::{edit_in_editor, edit_interactive};
+use crate::error::TodoError;
+use crate::export::{CsvOptions, export_tasks, split_tags};
+use crate::ics;
+use crate::ids::{backfill_uids, renumber_ids, resolve_task_ref, short_uid};
+use crate::json::{self, Action, BranchStats, OccurrenceEntry, RankedTask, TaskList};
+use crate::markdown;
 use crate::model::{
-    AppConfig, DEFAULT_BRANCH, IdScope, Priority, Repeat, SortKey, Task, default_branch,
+    AppConfig, AppState, ColumnSettings, DEFAULT_BRANCH, IdScope, ListColumn, ListViewStyle,
+    Priority, Repeat, SortKey, SortSpec, Task, ThemeConfig, ThemeElement, ThemePreset,
+    UrgencyCoefficients, default_branch,
 };
+use crate::output::{self, Glyph, OutputMode};
+use crate::quick::{QuickAdd, parse_quick_add};
+use crate::shell::run_shell;
 use crate::sort::sort_tasks;
 use crate::storage::{load_state, load_tasks, save_state, save_tasks, state_path, storage_path};
-use crate::util::{advance_due, normalize_tag, normalize_tags, parse_bool_flag, parse_due};
-use chrono::{Datelike, Duration, Local, Timelike};
+use crate::taskwarrior;
+use crate::template::Template;
+use crate::theme::{install as install_theme, paint, print_theme, value_name};
+use crate::todotxt;
+use crate::tui::run_tui;
+use crate::urgency::{describe_coefficients, set_coefficient, urgency};
+use crate::util::{
+    advance_due, normalize_tag, normalize_tags, normalize_tags_ordered, parse_bool_flag, parse_due,
+    parse_horizon, validate_date_format,
+};
+use chrono::{Datelike, Duration, Local, NaiveDate, Timelike};
 use clap_complete::generate;
-use owo_colors::OwoColorize;
 use serde::Deserialize;
 use uuid::Uuid;
 
 pub fn run() {
     let raw_args: Vec<String> = std::env::args().collect();
+    // Errors before the arguments are parsed (storage, usage) still honor --json.
+    output::install(OutputMode {
+        json: raw_args
+            .iter()
+            .skip(1)
+            .take_while(|a| *a != "--")
+            .any(|a| a == "--json"),
+        ..OutputMode::default()
+    });
 
     let path = storage_path();
     let state_path = state_path();
-    let mut state = load_state(&state_path);
+    let mut state = load_state(&state_path).unwrap_or_else(|e| e.exit());
     normalize_state(&mut state);
 
     let expanded_args = expand_saved_command_args(&raw_args, &state);
-    let cli = Cli::parse_from(expanded_args);
+    let cli = Cli::try_parse_from(expanded_args).unwrap_or_else(|e| {
+        if output::mode().json && e.use_stderr() {
+            usage_error(&e).exit();
+        }
+        e.exit()
+    });
+    output::install(OutputMode {
+        ascii: cli.ascii,
+        accessible: cli.accessible,
+        json: cli.json,
+    });
 
     if let Commands::Saved { command } = &cli.command {
-        handle_saved_commands(command, &mut state, &state_path);
+        handle_saved_commands(command, &mut state, &state_path).unwrap_or_else(|e| e.exit());
         return;
     }
 
-    let mut tasks = load_tasks(&path);
+    let mut tasks = load_tasks(&path).unwrap_or_else(|e| e.exit());
     let color = resolve_color(&cli, &state.config);
-    maybe_print_daily_greeting(&mut state, &tasks, color, &state_path, &cli.command);
+    install_theme(&state.config.theme);
+    maybe_print_daily_greeting(&mut state, &tasks, color, &state_path, &cli.command)
+        .unwrap_or_else(|e| e.exit());
+
+    if let Err(e) = execute(
+        cli.command,
+        &mut state,
+        &mut tasks,
+        color,
+        &path,
+        &state_path,
+    ) {
+        e.exit();
+    }
+}
+
+/// Run a single parsed command against the loaded store.
+///
+/// Mutating commands save `tasks`/`state` themselves, so callers that keep the
+/// store in memory (the shell) stay in sync with disk after every command.
+pub(crate) fn execute(
+    command: Commands,
+    state: &mut AppState,
+    tasks: &mut Vec<Task>,
+    color: bool,
+    path: &PathBuf,
+    state_path: &PathBuf,
+) -> Result<(), TodoError> {
+    install_theme(&state.config.theme);
+    let json = output::mode().json;
+    match command {
+        Commands::Shell => {
+            run_shell(state, tasks, color, path, state_path)?;
+        }
+
+        Commands::Tui => {
+            run_tui(state, tasks, path, color)?;
+        }
 
-    match cli.command {
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
             let name = cmd.get_name().to_string();
             generate(shell, &mut cmd, name, &mut std::io::stdout());
         }
 
-        Commands::Saved { .. } => {
-            // Handled before loading tasks.
+        Commands::Saved { command } => {
+            handle_saved_commands(&command, state, state_path)?;
         }
 
         Commands::Branch { name, list } => {
             if list {
-                print_branch_list(&state, &tasks);
-                return;
+                print_branch_list(state, tasks);
+                return Ok(());
             }
 
             if let Some(branch) = normalize_branch(name) {
                 state.current_branch = branch;
-                save_state(&state_path, &state);
-                println!("Switched to {}", state.current_branch);
-                return;
+                save_state(state_path, state)?;
+                if json {
+                    print_branch_list(state, tasks);
+                } else {
+                    println!("Switched to {}", state.current_branch);
+                }
+                return Ok(());
             }
 
-            println!("Current branch: {}", state.current_branch);
+            if json {
+                print_branch_list(state, tasks);
+            } else {
+                println!("Current branch: {}", state.current_branch);
+            }
         }
 
         Commands::BranchDelete { name, force } => {
             let Some(branch) = normalize_branch(Some(name)) else {
-                eprintln!("Invalid branch name");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid branch name".to_string()));
             };
 
             if branch.eq_ignore_ascii_case(DEFAULT_BRANCH) {
-                eprintln!("Cannot delete the default branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage(
+                    "cannot delete the default branch".to_string(),
+                ));
             }
 
             let count = tasks
@@ -87,79 +176,97 @@ pub fn run() {
                 .filter(|t| t.branch.eq_ignore_ascii_case(&branch))
                 .count();
             if count > 0 && !force {
-                eprintln!("Branch '{branch}' has {count} tasks. Use --force to delete.");
-                std::process::exit(2);
+                return Err(TodoError::Usage(format!(
+                    "branch '{branch}' has {count} tasks; use --force to delete it"
+                )));
             }
 
-            let before = tasks.len();
+            let removed: Vec<Task> = tasks
+                .iter()
+                .filter(|t| t.branch.eq_ignore_ascii_case(&branch))
+                .cloned()
+                .collect();
             tasks.retain(|t| !t.branch.eq_ignore_ascii_case(&branch));
-            let deleted = before - tasks.len();
 
             if state.current_branch.eq_ignore_ascii_case(&branch) {
                 state.current_branch = default_branch();
-                save_state(&state_path, &state);
+                save_state(state_path, state)?;
             }
 
-            save_tasks(&path, &tasks);
-            println!("Deleted branch '{branch}' ({deleted} tasks removed)");
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action {
+                    from: Some(branch),
+                    ..Action::new("branch-deleted", removed)
+                });
+            } else {
+                println!(
+                    "Deleted branch '{branch}' ({} tasks removed)",
+                    removed.len()
+                );
+            }
         }
 
         Commands::BranchMove { from, to } => {
             let Some(from) = normalize_branch(Some(from)) else {
-                eprintln!("Invalid source branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid source branch".to_string()));
             };
             let Some(to) = normalize_branch(Some(to)) else {
-                eprintln!("Invalid target branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid target branch".to_string()));
             };
             if from.eq_ignore_ascii_case(&to) {
-                eprintln!("Source and target branch are the same");
-                std::process::exit(2);
+                return Err(TodoError::Usage(
+                    "source and target branch are the same".to_string(),
+                ));
             }
 
-            let mut moved = 0usize;
-            let mut next_id = next_task_id(&tasks, state.config.id_scope, Some(&to));
-            for task in tasks.iter_mut() {
+            let mut moved: Vec<usize> = Vec::new();
+            let mut next_id = next_task_id(tasks, state.config.id_scope, Some(&to));
+            for (idx, task) in tasks.iter_mut().enumerate() {
                 if task.branch.eq_ignore_ascii_case(&from) {
                     task.branch = to.clone();
                     if state.config.id_scope == IdScope::Branch {
                         task.id = next_id;
                         next_id += 1;
                     }
-                    moved += 1;
+                    task.touch();
+                    moved.push(idx);
                 }
             }
 
-            if moved == 0 {
-                eprintln!("No tasks found in '{from}'");
-                std::process::exit(1);
+            if moved.is_empty() {
+                return Err(TodoError::NotFound(format!("no tasks in branch '{from}'")));
             }
 
             if state.current_branch.eq_ignore_ascii_case(&from) {
                 state.current_branch = to.clone();
-                save_state(&state_path, &state);
+                save_state(state_path, state)?;
             }
 
-            save_tasks(&path, &tasks);
-            println!("Moved {moved} tasks from '{from}' to '{to}'");
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(
+                    &Action::new("branch-moved", tasks_at(tasks, &moved)).between(&from, &to),
+                );
+            } else {
+                println!("Moved {} tasks from '{from}' to '{to}'", moved.len());
+            }
         }
 
         Commands::BranchDuplicate { from, to } => {
             let Some(from) = normalize_branch(Some(from)) else {
-                eprintln!("Invalid source branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid source branch".to_string()));
             };
             let Some(to) = normalize_branch(Some(to)) else {
-                eprintln!("Invalid target branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid target branch".to_string()));
             };
             if from.eq_ignore_ascii_case(&to) {
-                eprintln!("Source and target branch are the same");
-                std::process::exit(2);
+                return Err(TodoError::Usage(
+                    "source and target branch are the same".to_string(),
+                ));
             }
 
-            let mut next_id = next_task_id(&tasks, state.config.id_scope, Some(&to));
+            let mut next_id = next_task_id(tasks, state.config.id_scope, Some(&to));
             let now = chrono::Local::now().to_rfc3339();
             let mut added = 0usize;
             let mut copies: Vec<Task> = Vec::new();
@@ -170,7 +277,8 @@ pub fn run() {
                     copy.id = next_id;
                     copy.branch = to.clone();
                     copy.created_at = now.clone();
-                    if state.config.use_uuid {
+                    copy.updated_at = None;
+                    if state.config.use_uuid || copy.uid.is_some() {
                         copy.uid = Some(Uuid::new_v4().to_string());
                     }
                     next_id += 1;
@@ -180,54 +288,64 @@ pub fn run() {
             }
 
             if added == 0 {
-                eprintln!("No tasks found in '{from}'");
-                std::process::exit(1);
+                return Err(TodoError::NotFound(format!("no tasks in branch '{from}'")));
             }
 
+            let start = tasks.len();
             tasks.extend(copies);
-            save_tasks(&path, &tasks);
-            println!("Duplicated {added} tasks from '{from}' to '{to}'");
+            save_tasks(path, tasks)?;
+            if json {
+                let created = tasks[start..].to_vec();
+                json::emit(&Action::new("branch-duplicated", created).between(&from, &to));
+            } else {
+                println!("Duplicated {added} tasks from '{from}' to '{to}'");
+            }
         }
 
         Commands::BranchMerge { from, to } => {
             let Some(from) = normalize_branch(Some(from)) else {
-                eprintln!("Invalid source branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid source branch".to_string()));
             };
             let Some(to) = normalize_branch(Some(to)) else {
-                eprintln!("Invalid target branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid target branch".to_string()));
             };
             if from.eq_ignore_ascii_case(&to) {
-                eprintln!("Source and target branch are the same");
-                std::process::exit(2);
+                return Err(TodoError::Usage(
+                    "source and target branch are the same".to_string(),
+                ));
             }
 
-            let mut moved = 0usize;
-            let mut next_id = next_task_id(&tasks, state.config.id_scope, Some(&to));
-            for task in tasks.iter_mut() {
+            let mut moved: Vec<usize> = Vec::new();
+            let mut next_id = next_task_id(tasks, state.config.id_scope, Some(&to));
+            for (idx, task) in tasks.iter_mut().enumerate() {
                 if task.branch.eq_ignore_ascii_case(&from) {
                     task.branch = to.clone();
                     if state.config.id_scope == IdScope::Branch {
                         task.id = next_id;
                         next_id += 1;
                     }
-                    moved += 1;
+                    task.touch();
+                    moved.push(idx);
                 }
             }
 
-            if moved == 0 {
-                eprintln!("No tasks found in '{from}'");
-                std::process::exit(1);
+            if moved.is_empty() {
+                return Err(TodoError::NotFound(format!("no tasks in branch '{from}'")));
             }
 
             if state.current_branch.eq_ignore_ascii_case(&from) {
                 state.current_branch = to.clone();
-                save_state(&state_path, &state);
+                save_state(state_path, state)?;
             }
 
-            save_tasks(&path, &tasks);
-            println!("Merged '{from}' into '{to}' ({moved} tasks moved)");
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(
+                    &Action::new("branch-merged", tasks_at(tasks, &moved)).between(&from, &to),
+                );
+            } else {
+                println!("Merged '{from}' into '{to}' ({} tasks moved)", moved.len());
+            }
         }
 
         Commands::Create {
@@ -243,41 +361,55 @@ pub fn run() {
                 .as_deref()
                 .map(parse_due)
                 .transpose()
-                .unwrap_or_else(|e| {
-                    eprintln!("Invalid due date: {e}");
-                    std::process::exit(2);
-                });
-
-            let created_at = chrono::Local::now().to_rfc3339();
-            let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let next_id = next_task_id(&tasks, state.config.id_scope, Some(&branch));
-            let uid = if state.config.use_uuid {
-                Some(Uuid::new_v4().to_string())
-            } else {
-                None
-            };
+                .map_err(|e| TodoError::Usage(format!("invalid due date: {e}")))?;
 
-            tasks.push(Task {
-                id: next_id,
-                uid,
+            let mut entry = QuickAdd {
                 title,
-                content,
-                tags: normalize_tags(&tags),
                 due: due_date,
                 priority,
+                tags: normalize_tags(&tags),
+                branch: normalize_branch(branch),
                 repeat,
-                branch,
-                archived: false,
-                done: false,
-                created_at,
-            });
+            };
+            if state.config.quick_add {
+                let parsed = parse_quick_add(&entry.title, Local::now().date_naive())
+                    .map_err(|e| TodoError::Usage(format!("invalid quick-add title: {e}")))?;
+                if parsed.has_metadata() && !json {
+                    println!("Parsed: {}", parsed.describe());
+                }
+                let mut tags = entry.tags;
+                tags.extend(parsed.tags);
+                entry = QuickAdd {
+                    title: parsed.title,
+                    due: entry.due.or(parsed.due),
+                    priority: entry.priority.or(parsed.priority),
+                    tags: normalize_tags(&tags),
+                    branch: entry.branch.or(parsed.branch),
+                    repeat: entry.repeat.or(parsed.repeat),
+                };
+            }
+
+            let pos = create_task(tasks, state, entry, content);
+            save_tasks(path, tasks)?;
+            print_created(&tasks[pos]);
+        }
+
+        Commands::Quick { text, content } => {
+            let entry = parse_quick_add(&text.join(" "), Local::now().date_naive())
+                .map_err(|e| TodoError::Usage(format!("invalid quick-add text: {e}")))?;
+            if !json {
+                println!("Parsed: {}", entry.describe());
+            }
 
-            save_tasks(&path, &tasks);
-            println!("Created task #{next_id}");
+            let pos = create_task(tasks, state, entry, content);
+            save_tasks(path, tasks)?;
+            print_created(&tasks[pos]);
         }
 
         Commands::Edit {
             id,
+            editor,
+            query,
             title,
             content,
             tags,
@@ -292,11 +424,6 @@ pub fn run() {
             clear_repeat,
             branch,
         } => {
-            let Some(task) = tasks.iter_mut().find(|t| t.id == id) else {
-                eprintln!("No task with id {id}");
-                std::process::exit(1);
-            };
-
             let is_interactive = title.is_none()
                 && content.is_none()
                 && tags.is_empty()
@@ -311,6 +438,65 @@ pub fn run() {
                 && !clear_repeat
                 && branch.is_none();
 
+            if editor {
+                if !is_interactive {
+                    return Err(TodoError::Usage(
+                        "--editor cannot be combined with field options".to_string(),
+                    ));
+                }
+                let indices = match (id.as_deref(), query.as_deref()) {
+                    (Some(id), _) => vec![find_task_index(tasks, id, &state.current_branch)?],
+                    (None, Some(query)) => {
+                        bulk_candidate_indices(tasks, query, &state.current_branch)
+                    }
+                    (None, None) => Vec::new(),
+                };
+                if indices.is_empty() {
+                    return Err(TodoError::NothingMatched("no matching tasks".to_string()));
+                }
+                let before: Vec<Option<String>> = indices
+                    .iter()
+                    .map(|idx| tasks[*idx].updated_at.clone())
+                    .collect();
+                let start = tasks.len();
+                let count =
+                    edit_in_editor(tasks, &indices, &state.config).map_err(TodoError::Failed)?;
+                if count > 0 {
+                    save_tasks(path, tasks)?;
+                }
+                if json {
+                    let changed: Vec<usize> = indices
+                        .iter()
+                        .zip(before)
+                        .filter(|(idx, updated)| tasks[**idx].updated_at != *updated)
+                        .map(|(idx, _)| *idx)
+                        .collect();
+                    json::emit(&Action {
+                        created: tasks[start..].to_vec(),
+                        ..Action::new("updated", tasks_at(tasks, &changed))
+                    });
+                    return Ok(());
+                }
+                if count == 0 {
+                    println!("No changes");
+                    return Ok(());
+                }
+                if let [pos] = indices.as_slice() {
+                    println!("Updated #{}", tasks[*pos].id);
+                } else {
+                    println!("Updated {count} tasks");
+                }
+                return Ok(());
+            }
+
+            let Some(id) = id else {
+                return Err(TodoError::Usage(
+                    "a task id is required without --editor".to_string(),
+                ));
+            };
+            let pos = find_task_index(tasks, &id, &state.current_branch)?;
+            let task = &mut tasks[pos];
+
             if is_interactive {
                 edit_interactive(task);
             } else {
@@ -330,10 +516,8 @@ pub fn run() {
                 if clear_due {
                     task.due = None;
                 } else if let Some(due) = due {
-                    let due_date = parse_due(&due).unwrap_or_else(|e| {
-                        eprintln!("Invalid due date: {e}");
-                        std::process::exit(2);
-                    });
+                    let due_date = parse_due(&due)
+                        .map_err(|e| TodoError::Usage(format!("invalid due date: {e}")))?;
                     task.due = Some(due_date);
                 }
                 if clear_priority {
@@ -350,9 +534,15 @@ pub fn run() {
                     task.branch = branch;
                 }
             }
+            task.touch();
 
-            save_tasks(&path, &tasks);
-            println!("Updated #{id}");
+            let id = task.id;
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("updated", vec![tasks[pos].clone()]));
+            } else {
+                println!("Updated #{id}");
+            }
         }
 
         Commands::List {
@@ -364,9 +554,13 @@ pub fn run() {
             archived,
             tags,
             group_by,
+            view: view_style,
+            board_by,
+            board_tags,
+            format,
         } => {
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let sort = sort.unwrap_or(state.config.default_sort);
+            let sort = sort.unwrap_or_else(|| state.config.default_sort.clone());
             let desc = resolve_desc(desc, asc, state.config.default_desc);
             let tags = normalize_tags(&tags);
             let mut view: Vec<&Task> = tasks
@@ -376,9 +570,46 @@ pub fn run() {
                 .filter(|t| branch_matches(t, Some(&branch)))
                 .filter(|t| tags_match(t, &tags))
                 .collect();
-            sort_tasks(&mut view, sort, desc);
-            let group_by_day = matches!(group_by, Some(GroupBy::DueDay));
-            print_task_list(&view, &state, color, group_by_day);
+            sort_tasks(&mut view, &sort, desc, &state.config.urgency);
+            if json {
+                json::emit(&TaskList::new(&view));
+                return Ok(());
+            }
+
+            // An explicit --view wins over the saved template.
+            let format = format.or_else(|| {
+                view_style
+                    .is_none()
+                    .then(|| state.profile.list_format.clone())
+                    .flatten()
+            });
+            if let Some(source) = format {
+                let template = parse_template_arg(&source)?;
+                print_task_list_templated(&view, &template, state, group_by);
+                return Ok(());
+            }
+
+            // One-off overrides; the profile is restored so the shell never persists them.
+            let saved = (
+                state.profile.list_view,
+                state.profile.board_group,
+                state.profile.board_tags.clone(),
+            );
+            if let Some(style) = view_style {
+                state.profile.list_view = style;
+            }
+            if let Some(group) = board_by {
+                state.profile.board_group = group;
+            }
+            if !board_tags.is_empty() {
+                state.profile.board_tags = normalize_tags_ordered(&board_tags);
+            }
+            print_task_list(&view, state, color, group_by);
+            (
+                state.profile.list_view,
+                state.profile.board_group,
+                state.profile.board_tags,
+            ) = saved;
         }
 
         Commands::SplitDue {
@@ -391,7 +622,7 @@ pub fn run() {
             asc,
         } => {
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let sort = sort.unwrap_or(state.config.default_sort);
+            let sort = sort.unwrap_or_else(|| state.config.default_sort.clone());
             let desc = resolve_desc(desc, asc, state.config.default_desc);
             let tags = normalize_tags(&tags);
 
@@ -413,10 +644,17 @@ pub fn run() {
                 .filter(|t| tags_match(t, &tags))
                 .collect();
 
-            sort_tasks(&mut due_view, sort, desc);
-            sort_tasks(&mut no_due_view, sort, desc);
+            sort_tasks(&mut due_view, &sort, desc, &state.config.urgency);
+            sort_tasks(&mut no_due_view, &sort, desc, &state.config.urgency);
+            if json {
+                json::emit(&json::DueSplit {
+                    due: due_view,
+                    no_due: no_due_view,
+                });
+                return Ok(());
+            }
 
-            print_task_list_due_split(&due_view, &no_due_view, &state, color);
+            print_task_list_due_split(&due_view, &no_due_view, state, color);
         }
 
         Commands::ListAll {
@@ -428,7 +666,7 @@ pub fn run() {
             tags,
             group_by,
         } => {
-            let sort = sort.unwrap_or(state.config.default_sort);
+            let sort = sort.unwrap_or_else(|| state.config.default_sort.clone());
             let desc = resolve_desc(desc, asc, state.config.default_desc);
             let tags = normalize_tags(&tags);
             let filtered: Vec<Task> = tasks
@@ -437,8 +675,13 @@ pub fn run() {
                 .filter(|t| tags_match(t, &tags))
                 .cloned()
                 .collect();
-            let group_by_day = matches!(group_by, Some(GroupBy::DueDay));
-            print_task_list_grouped(&filtered, &state, all, sort, desc, color, group_by_day);
+            if json {
+                let mut view: Vec<&Task> = filtered.iter().filter(|t| all || !t.done).collect();
+                sort_tasks(&mut view, &sort, desc, &state.config.urgency);
+                json::emit(&TaskList::new(&view));
+                return Ok(());
+            }
+            print_task_list_grouped(&filtered, state, all, &sort, desc, color, group_by);
         }
 
         Commands::ListRepeat {
@@ -450,9 +693,15 @@ pub fn run() {
             archived,
             tags,
             group_by,
+            project,
         } => {
+            let today = Local::now().date_naive();
+            let until = project
+                .map(|span| parse_horizon(&span, today))
+                .transpose()
+                .map_err(|e| TodoError::Usage(format!("invalid --project: {e}")))?;
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let sort = sort.unwrap_or(state.config.default_sort);
+            let sort = sort.unwrap_or_else(|| state.config.default_sort.clone());
             let desc = resolve_desc(desc, asc, state.config.default_desc);
             let tags = normalize_tags(&tags);
             let mut view: Vec<&Task> = tasks
@@ -463,9 +712,26 @@ pub fn run() {
                 .filter(|t| branch_matches(t, Some(&branch)))
                 .filter(|t| tags_match(t, &tags))
                 .collect();
-            sort_tasks(&mut view, sort, desc);
-            let group_by_day = matches!(group_by, Some(GroupBy::DueDay));
-            print_task_list(&view, &state, color, group_by_day);
+            sort_tasks(&mut view, &sort, desc, &state.config.urgency);
+            if json {
+                let projections = until.map(|until| {
+                    collect_occurrences(&view, today, until)
+                        .iter()
+                        .filter(|o| o.projected)
+                        .map(OccurrenceEntry::from)
+                        .collect()
+                });
+                json::emit(&json::RepeatList {
+                    tasks: view,
+                    projections,
+                });
+                return Ok(());
+            }
+            print_task_list(&view, state, color, group_by);
+            if let Some(until) = until {
+                println!();
+                print_projections(&view, state, until, today, color);
+            }
         }
 
         Commands::ListDone {
@@ -478,7 +744,7 @@ pub fn run() {
             group_by,
         } => {
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let sort = sort.unwrap_or(state.config.default_sort);
+            let sort = sort.unwrap_or_else(|| state.config.default_sort.clone());
             let desc = resolve_desc(desc, asc, state.config.default_desc);
             let tags = normalize_tags(&tags);
             let mut view: Vec<&Task> = tasks
@@ -488,9 +754,12 @@ pub fn run() {
                 .filter(|t| branch_matches(t, Some(&branch)))
                 .filter(|t| tags_match(t, &tags))
                 .collect();
-            sort_tasks(&mut view, sort, desc);
-            let group_by_day = matches!(group_by, Some(GroupBy::DueDay));
-            print_task_list(&view, &state, color, group_by_day);
+            sort_tasks(&mut view, &sort, desc, &state.config.urgency);
+            if json {
+                json::emit(&TaskList::new(&view));
+                return Ok(());
+            }
+            print_task_list(&view, state, color, group_by);
         }
 
         Commands::Search {
@@ -506,7 +775,7 @@ pub fn run() {
         } => {
             let q = query.to_lowercase();
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let sort = sort.unwrap_or(state.config.default_sort);
+            let sort = sort.unwrap_or_else(|| state.config.default_sort.clone());
             let desc = resolve_desc(desc, asc, state.config.default_desc);
             let tags = normalize_tags(&tags);
             let mut view: Vec<&Task> = tasks
@@ -518,17 +787,20 @@ pub fn run() {
                 .filter(|t| tags_match(t, &tags))
                 .collect();
 
-            sort_tasks(&mut view, sort, desc);
-            let group_by_day = matches!(group_by, Some(GroupBy::DueDay));
-            print_task_list(&view, &state, color, group_by_day);
+            sort_tasks(&mut view, &sort, desc, &state.config.urgency);
+            if json {
+                json::emit(&TaskList::new(&view));
+                return Ok(());
+            }
+            print_task_list(&view, state, color, group_by);
         }
 
         Commands::Reminders { branch, tags } => {
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
             let tags = normalize_tags(&tags);
             print_reminders(
-                &tasks,
-                &state,
+                tasks,
+                state,
                 &branch,
                 &tags,
                 state.config.reminder_days,
@@ -536,44 +808,190 @@ pub fn run() {
             );
         }
 
+        Commands::Calendar {
+            month,
+            all,
+            branch,
+            tags,
+        } => {
+            let today = Local::now().date_naive();
+            let month_start = match parse_month(month.as_deref(), today) {
+                Ok(date) => date,
+                Err(e) => {
+                    return Err(TodoError::Usage(format!("invalid month: {e}")));
+                }
+            };
+            let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
+            let tags = normalize_tags(&tags);
+            let view: Vec<&Task> = tasks
+                .iter()
+                .filter(|t| all || !t.done)
+                .filter(|t| !t.archived)
+                .filter(|t| branch_matches(t, Some(&branch)))
+                .filter(|t| tags_match(t, &tags))
+                .collect();
+            print_calendar(&view, state, month_start, today, color);
+        }
+
+        Commands::Agenda { days, branch, tags } => {
+            let today = Local::now().date_naive();
+            let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
+            let tags = normalize_tags(&tags);
+            let view: Vec<&Task> = tasks
+                .iter()
+                .filter(|t| !t.done && !t.archived)
+                .filter(|t| branch_matches(t, Some(&branch)))
+                .filter(|t| tags_match(t, &tags))
+                .collect();
+            print_agenda(&view, state, days, today, color);
+        }
+
+        Commands::Next {
+            limit,
+            branch,
+            tags,
+        } => {
+            if limit == 0 {
+                return Err(TodoError::Usage("--limit must be at least 1".to_string()));
+            }
+            let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
+            let tags = normalize_tags(&tags);
+            let mut view: Vec<&Task> = tasks
+                .iter()
+                .filter(|t| !t.done && !t.archived)
+                .filter(|t| branch_matches(t, Some(&branch)))
+                .filter(|t| tags_match(t, &tags))
+                .collect();
+            sort_tasks(
+                &mut view,
+                &SortKey::Urgency.into(),
+                false,
+                &state.config.urgency,
+            );
+            view.truncate(limit);
+            if json {
+                let today = Local::now().date_naive();
+                let tasks = view
+                    .into_iter()
+                    .map(|task| RankedTask {
+                        task,
+                        urgency: urgency(task, &state.config.urgency, today),
+                    })
+                    .collect();
+                json::emit(&json::RankedList { tasks });
+                return Ok(());
+            }
+
+            // Always show the score; the board layout makes no sense for a ranking.
+            let saved = (state.profile.list_view, state.profile.list_columns.clone());
+            let mut cols = effective_columns(state);
+            if !cols.contains(&ListColumn::Urgency) {
+                cols.push(ListColumn::Urgency);
+            }
+            state.profile.list_columns = cols;
+            if matches!(state.profile.list_view, ListViewStyle::Board) {
+                state.profile.list_view = ListViewStyle::Table;
+            }
+            print_task_list(&view, state, color, None);
+            (state.profile.list_view, state.profile.list_columns) = saved;
+        }
+
+        Commands::Theme => {
+            if json {
+                json::emit(&json::Theme::new(&state.config.theme));
+                return Ok(());
+            }
+            print_theme(&state.config.theme, color);
+        }
+
         Commands::Stats => {
-            print_stats(&tasks, &state.current_branch);
+            print_stats(tasks, &state.current_branch);
         }
 
-        Commands::BulkDone { query, branch } => {
+        Commands::BulkDone {
+            query,
+            branch,
+            dry_run,
+            yes,
+        } => {
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let count = bulk_set_done(
-                &mut tasks,
-                &query,
+            let indices: Vec<usize> = bulk_candidate_indices(tasks, &query, &branch)
+                .into_iter()
+                .filter(|&idx| !tasks[idx].done)
+                .collect();
+            if !confirm_bulk(
+                &tasks_ref(tasks, &indices),
+                "done",
+                "marked as done",
+                dry_run,
+                yes,
+                state,
+                color,
+            )? {
+                return Ok(());
+            }
+            let start = tasks.len();
+            bulk_set_done(
+                tasks,
+                &indices,
                 &branch,
                 true,
                 state.config.id_scope,
                 state.config.use_uuid,
             );
-            if count == 0 {
-                eprintln!("No matching tasks");
-                std::process::exit(1);
+            let changed = indices;
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action {
+                    created: tasks[start..].to_vec(),
+                    ..Action::new("done", tasks_at(tasks, &changed))
+                });
+            } else {
+                println!("Marked {} tasks as done", changed.len());
             }
-            save_tasks(&path, &tasks);
-            println!("Marked {count} tasks as done");
         }
 
-        Commands::BulkUndone { query, branch } => {
+        Commands::BulkUndone {
+            query,
+            branch,
+            dry_run,
+            yes,
+        } => {
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let count = bulk_set_done(
-                &mut tasks,
-                &query,
+            let indices: Vec<usize> = bulk_candidate_indices(tasks, &query, &branch)
+                .into_iter()
+                .filter(|&idx| tasks[idx].done)
+                .collect();
+            if !confirm_bulk(
+                &tasks_ref(tasks, &indices),
+                "undone",
+                "marked as not done",
+                dry_run,
+                yes,
+                state,
+                color,
+            )? {
+                return Ok(());
+            }
+            let start = tasks.len();
+            bulk_set_done(
+                tasks,
+                &indices,
                 &branch,
                 false,
                 state.config.id_scope,
                 state.config.use_uuid,
             );
-            if count == 0 {
-                eprintln!("No matching tasks");
-                std::process::exit(1);
+            let changed = indices;
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action {
+                    created: tasks[start..].to_vec(),
+                    ..Action::new("undone", tasks_at(tasks, &changed))
+                });
+            } else {
+                println!("Marked {} tasks as not done", changed.len());
             }
-            save_tasks(&path, &tasks);
-            println!("Marked {count} tasks as not done");
         }
 
         Commands::BulkEdit {
@@ -588,8 +1006,13 @@ pub fn run() {
             tags,
             remove_tags,
             clear_tags,
+            dry_run,
+            yes,
         } => {
-            let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
+            let due = due
+                .map(|value| parse_due(&value))
+                .transpose()
+                .map_err(|e| TodoError::Usage(format!("invalid due date: {e}")))?;
             let opts = BulkEditOpts {
                 due,
                 priority,
@@ -601,43 +1024,106 @@ pub fn run() {
                 remove_tags: &remove_tags,
                 clear_tags,
             };
-            let count = bulk_edit(&mut tasks, &query, &branch, &opts);
-            if count == 0 {
-                eprintln!("No matching tasks");
-                std::process::exit(1);
+            if opts.is_empty() {
+                return Err(TodoError::Usage(
+                    "nothing to change; give a field such as --due, --priority or --tag"
+                        .to_string(),
+                ));
+            }
+            let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
+            let indices = bulk_candidate_indices(tasks, &query, &branch);
+            if !confirm_bulk(
+                &tasks_ref(tasks, &indices),
+                "updated",
+                "updated",
+                dry_run,
+                yes,
+                state,
+                color,
+            )? {
+                return Ok(());
+            }
+            let changed = bulk_edit(tasks, &indices, &opts);
+            if changed.is_empty() {
+                return Err(TodoError::NothingMatched(
+                    "no matching task needed a change".to_string(),
+                ));
+            }
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("updated", tasks_at(tasks, &changed)));
+            } else {
+                println!("Updated {} tasks", changed.len());
             }
-            save_tasks(&path, &tasks);
-            println!("Updated {count} tasks");
         }
 
-        Commands::BulkDelete { query, branch } => {
+        Commands::BulkDelete {
+            query,
+            branch,
+            dry_run,
+            yes,
+        } => {
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let count = bulk_delete(&mut tasks, &query, &branch);
-            if count == 0 {
-                eprintln!("No matching tasks");
-                std::process::exit(1);
+            let indices = bulk_candidate_indices(tasks, &query, &branch);
+            if !confirm_bulk(
+                &tasks_ref(tasks, &indices),
+                "deleted",
+                "deleted",
+                dry_run,
+                yes,
+                state,
+                color,
+            )? {
+                return Ok(());
+            }
+            let removed = bulk_delete(tasks, &indices);
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("deleted", removed));
+            } else {
+                println!("Deleted {} tasks", removed.len());
             }
-            save_tasks(&path, &tasks);
-            println!("Deleted {count} tasks");
         }
 
-        Commands::BulkMove { query, to, branch } => {
+        Commands::BulkMove {
+            query,
+            to,
+            branch,
+            dry_run,
+            yes,
+        } => {
             let Some(target) = normalize_branch(Some(to)) else {
-                eprintln!("Invalid target branch");
-                std::process::exit(2);
+                return Err(TodoError::Usage("invalid target branch".to_string()));
             };
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
             if target.eq_ignore_ascii_case(&branch) {
-                eprintln!("Source and target branch are the same");
-                std::process::exit(2);
-            }
-            let count = bulk_move(&mut tasks, &query, &branch, &target, state.config.id_scope);
-            if count == 0 {
-                eprintln!("No matching tasks");
-                std::process::exit(1);
+                return Err(TodoError::Usage(
+                    "source and target branch are the same".to_string(),
+                ));
+            }
+            let indices = bulk_candidate_indices(tasks, &query, &branch);
+            let action = format!("moved to '{target}'");
+            if !confirm_bulk(
+                &tasks_ref(tasks, &indices),
+                "moved",
+                &action,
+                dry_run,
+                yes,
+                state,
+                color,
+            )? {
+                return Ok(());
+            }
+            bulk_move(tasks, &indices, &target, state.config.id_scope);
+            let moved = indices;
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(
+                    &Action::new("moved", tasks_at(tasks, &moved)).between(&branch, &target),
+                );
+            } else {
+                println!("Moved {} tasks to '{target}'", moved.len());
             }
-            save_tasks(&path, &tasks);
-            println!("Moved {count} tasks to '{target}'");
         }
 
         Commands::Archive {
@@ -645,105 +1131,142 @@ pub fn run() {
             all_branches,
             branch,
         } => {
-            let count = if let Some(id) = id {
-                archive_by_id(&mut tasks, id)
+            let archived = if let Some(id) = id {
+                let pos = find_task_index(tasks, &id, &state.current_branch)?;
+                archive_at(tasks, pos)
             } else if all_branches {
-                archive_done_all(&mut tasks)
+                archive_done_all(tasks)
             } else {
                 let branch =
                     normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-                archive_done_branch(&mut tasks, &branch)
+                archive_done_branch(tasks, &branch)
             };
 
-            if count == 0 {
-                eprintln!("No tasks archived");
-                std::process::exit(1);
+            if archived.is_empty() {
+                return Err(TodoError::NothingMatched("no tasks to archive".to_string()));
+            }
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("archived", tasks_at(tasks, &archived)));
+            } else {
+                println!("Archived {} tasks", archived.len());
             }
-            save_tasks(&path, &tasks);
-            println!("Archived {count} tasks");
         }
 
         Commands::Unarchive { id } => {
-            let Some(task) = tasks.iter_mut().find(|t| t.id == id) else {
-                eprintln!("No task with id {id}");
-                std::process::exit(1);
-            };
-            task.archived = false;
-            save_tasks(&path, &tasks);
-            println!("Unarchived #{id}");
+            let pos = find_task_index(tasks, &id, &state.current_branch)?;
+            let id = tasks[pos].id;
+            tasks[pos].archived = false;
+            tasks[pos].touch();
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("unarchived", vec![tasks[pos].clone()]));
+            } else {
+                println!("Unarchived #{id}");
+            }
         }
 
         Commands::Done { id } => {
-            let Some(pos) = tasks.iter().position(|t| t.id == id) else {
-                eprintln!("No task with id {id}");
-                std::process::exit(1);
-            };
+            let pos = find_task_index(tasks, &id, &state.current_branch)?;
+            let id = tasks[pos].id;
             let branch = tasks[pos].branch.clone();
-            let mut next_id = next_task_id(&tasks, state.config.id_scope, Some(&branch));
+            let mut next_id = next_task_id(tasks, state.config.id_scope, Some(&branch));
             let task = &mut tasks[pos];
-            if let Some(next_task) =
+            let created: Vec<Task> =
                 mark_done_with_repeat(task, &mut next_id, state.config.use_uuid)
-            {
-                tasks.push(next_task);
+                    .into_iter()
+                    .collect();
+            tasks.extend(created.iter().cloned());
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action {
+                    created,
+                    ..Action::new("done", vec![tasks[pos].clone()])
+                });
+            } else {
+                println!("Done #{id}");
             }
-            save_tasks(&path, &tasks);
-            println!("Done #{id}");
         }
 
         Commands::Undone { id } => {
-            let Some(task) = tasks.iter_mut().find(|t| t.id == id) else {
-                eprintln!("No task with id {id}");
-                std::process::exit(1);
-            };
-            task.done = false;
-            save_tasks(&path, &tasks);
-            println!("Undone #{id}");
+            let pos = find_task_index(tasks, &id, &state.current_branch)?;
+            let id = tasks[pos].id;
+            tasks[pos].set_done(false);
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("undone", vec![tasks[pos].clone()]));
+            } else {
+                println!("Undone #{id}");
+            }
         }
 
         Commands::Toggle { id } => {
-            let Some(pos) = tasks.iter().position(|t| t.id == id) else {
-                eprintln!("No task with id {id}");
-                std::process::exit(1);
-            };
+            let pos = find_task_index(tasks, &id, &state.current_branch)?;
+            let id = tasks[pos].id;
             let branch = tasks[pos].branch.clone();
-            let mut next_id = next_task_id(&tasks, state.config.id_scope, Some(&branch));
+            let mut next_id = next_task_id(tasks, state.config.id_scope, Some(&branch));
             let task = &mut tasks[pos];
+            let mut created: Vec<Task> = Vec::new();
             if task.done {
-                task.done = false;
+                task.set_done(false);
             } else if let Some(next_task) =
                 mark_done_with_repeat(task, &mut next_id, state.config.use_uuid)
             {
-                tasks.push(next_task);
+                created.push(next_task);
+            }
+            tasks.extend(created.iter().cloned());
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action {
+                    created,
+                    ..Action::new("toggled", vec![tasks[pos].clone()])
+                });
+            } else {
+                println!("Toggled #{id}");
             }
-            save_tasks(&path, &tasks);
-            println!("Toggled #{id}");
         }
 
         Commands::Delete { id } => {
-            let len_before = tasks.len();
-            tasks.retain(|t| t.id != id);
-            if tasks.len() == len_before {
-                eprintln!("No task with id {id}");
-                std::process::exit(1);
+            let pos = find_task_index(tasks, &id, &state.current_branch)?;
+            let removed = tasks.remove(pos);
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("deleted", vec![removed]));
+            } else {
+                println!("Deleted #{}", removed.id);
             }
-            save_tasks(&path, &tasks);
-            println!("Deleted #{id}");
         }
 
         Commands::Clear => {
-            let len_before = tasks.len();
-            tasks.retain(|t| !t.done);
-            let cleared = len_before - tasks.len();
-            save_tasks(&path, &tasks);
-            println!("Cleared {cleared} completed tasks");
+            let (cleared, kept): (Vec<Task>, Vec<Task>) =
+                std::mem::take(tasks).into_iter().partition(|t| t.done);
+            *tasks = kept;
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action::new("cleared", cleared));
+            } else {
+                println!("Cleared {} completed tasks", cleared.len());
+            }
         }
 
-        Commands::View { id } => {
-            let Some(task) = tasks.iter().find(|t| t.id == id) else {
-                eprintln!("No task with id {id}");
-                std::process::exit(1);
-            };
-            print_task_view(task, color);
+        Commands::View { id, format } => {
+            let pos = find_task_index(tasks, &id, &state.current_branch)?;
+            if json {
+                let task = &tasks[pos];
+                let today = Local::now().date_naive();
+                json::emit(&json::TaskView {
+                    task,
+                    urgency: urgency(task, &state.config.urgency, today),
+                });
+                return Ok(());
+            }
+            match format {
+                Some(source) => {
+                    let template = parse_template_arg(&source)?;
+                    print_task_list_templated(&[&tasks[pos]], &template, state, None);
+                }
+                None => print_task_view(&tasks[pos], state, color),
+            }
         }
 
         Commands::Export {
@@ -752,7 +1275,28 @@ pub fn run() {
             archived,
             branch,
             tags,
+            columns,
+            delimiter,
+            no_header,
+            quote,
         } => {
+            if format != ExportFormat::Csv {
+                let given = [
+                    (!columns.is_empty(), "--columns"),
+                    (delimiter.is_some(), "--delimiter"),
+                    (no_header, "--no-header"),
+                    (quote.is_some(), "--quote"),
+                ];
+                if let Some((_, flag)) = given.iter().find(|(given, _)| *given) {
+                    return Err(TodoError::Usage(format!(
+                        "{flag} only applies to `export csv`"
+                    )));
+                }
+            }
+            // `import csv` needs a title column to read the file back.
+            if !columns.is_empty() && !columns.contains(&CsvColumn::Title) {
+                return Err(TodoError::Usage("--columns must include title".to_string()));
+            }
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
             let tags = normalize_tags(&tags);
             let view: Vec<&Task> = tasks
@@ -762,21 +1306,98 @@ pub fn run() {
                 .filter(|t| branch_matches(t, Some(&branch)))
                 .filter(|t| tags_match(t, &tags))
                 .collect();
-            export_tasks(&view, format);
+            let csv = CsvOptions {
+                columns,
+                delimiter: delimiter.unwrap_or(b','),
+                header: !no_header,
+                quote: quote.unwrap_or(CsvQuote::Necessary),
+            };
+            export_tasks(&view, format, &csv)?;
         }
 
         Commands::Import {
             format,
             file,
             branch,
+            delimiter,
+            no_header,
+            columns,
+            events,
+            headings,
+            nested,
+            dry_run,
         } => {
+            if format != ImportFormat::Csv {
+                let given = [
+                    (no_header, "--no-header"),
+                    (!columns.is_empty(), "--columns"),
+                ];
+                if let Some((_, flag)) = given.iter().find(|(given, _)| *given) {
+                    return Err(TodoError::Usage(format!(
+                        "{flag} only applies to `import csv`"
+                    )));
+                }
+            }
+            if !columns.is_empty() && !no_header {
+                return Err(TodoError::Usage(
+                    "--columns only applies with --no-header; the header row names the columns"
+                        .to_string(),
+                ));
+            }
+            if !columns.is_empty() && !columns.contains(&CsvColumn::Title) {
+                return Err(TodoError::Usage("--columns must include title".to_string()));
+            }
             let branch = normalize_branch(branch).unwrap_or_else(|| state.current_branch.clone());
-            let result = import_tasks(&mut tasks, format, &file, &branch, &state.config);
+            let start = tasks.len();
+            let options = ImportOptions {
+                delimiter,
+                csv_columns: no_header.then(|| {
+                    if columns.is_empty() {
+                        CsvColumn::value_variants().to_vec()
+                    } else {
+                        columns
+                    }
+                }),
+                events,
+                headings,
+                nested,
+            };
+            let result = import_tasks(tasks, format, &file, &branch, &options, &state.config)?;
             if result.imported == 0 {
-                eprintln!("No tasks imported");
-                std::process::exit(1);
+                let mut message = "no tasks imported".to_string();
+                if result.skipped_uid > 0 {
+                    message.push_str(&format!(
+                        "; {} rows have a uid already in use",
+                        result.skipped_uid
+                    ));
+                }
+                return Err(TodoError::NothingMatched(message));
+            }
+            if dry_run {
+                let imported: Vec<&Task> = tasks[start..].iter().collect();
+                if json {
+                    json::emit(&json::DryRun {
+                        action: "imported",
+                        dry_run: true,
+                        count: imported.len(),
+                        tasks: imported,
+                    });
+                } else {
+                    let noun = if imported.len() == 1 { "task" } else { "tasks" };
+                    println!("Dry run: {} {noun} would be imported", imported.len());
+                    print_task_list(&imported, state, color, None);
+                }
+                tasks.truncate(start);
+                return Ok(());
+            }
+            save_tasks(path, tasks)?;
+            if json {
+                json::emit(&Action {
+                    skipped: Some(result.skipped),
+                    ..Action::new("imported", tasks[start..].to_vec())
+                });
+                return Ok(());
             }
-            save_tasks(&path, &tasks);
             println!("Imported {} tasks", result.imported);
             if result.skipped > 0 {
                 eprintln!("Skipped {} rows", result.skipped);
@@ -786,6 +1407,9 @@ pub fn run() {
                 if result.skipped_parse > 0 {
                     eprintln!("- Parse errors: {}", result.skipped_parse);
                 }
+                if result.skipped_uid > 0 {
+                    eprintln!("- Uid already in use: {}", result.skipped_uid);
+                }
             }
         }
 
@@ -805,7 +1429,22 @@ pub fn run() {
             list_view,
             columns,
             columns_default,
+            column_widths,
+            column_aligns,
+            column_flex,
+            column_fixed,
+            column_layout_default,
+            wrap_rows,
             auto_pager,
+            board_by,
+            board_tags,
+            board_tags_all,
+            list_format,
+            clear_list_format,
+            date_style,
+            date_styles,
+            date_format,
+            date_format_default,
             reset_greeting,
         } => {
             let mut changed = false;
@@ -848,8 +1487,9 @@ pub fn run() {
                     state.profile.day_start_hour = hour;
                     changed = true;
                 } else {
-                    eprintln!("day-start-hour must be between 0 and 23");
-                    std::process::exit(2);
+                    return Err(TodoError::Usage(
+                        "--day-start-hour must be between 0 and 23".to_string(),
+                    ));
                 }
             }
 
@@ -906,22 +1546,122 @@ pub fn run() {
                 changed = true;
             }
 
+            if column_layout_default {
+                state.profile.column_settings.clear();
+                changed = true;
+            }
+            let column_changes = !column_widths.is_empty()
+                || !column_aligns.is_empty()
+                || !column_flex.is_empty()
+                || !column_fixed.is_empty();
+            let settings = &mut state.profile.column_settings;
+            for (column, width) in column_widths {
+                settings.entry(column).or_default().width = width;
+            }
+            for (column, align) in column_aligns {
+                settings.entry(column).or_default().align = align;
+            }
+            for column in column_flex {
+                settings.entry(column).or_default().flex = true;
+            }
+            for column in column_fixed {
+                settings.entry(column).or_default().flex = false;
+            }
+            settings.retain(|_, s| *s != ColumnSettings::default());
+            changed |= column_changes;
+            if let Some(enabled) = wrap_rows {
+                state.profile.wrap_rows = enabled;
+                changed = true;
+            }
+
             if let Some(enabled) = auto_pager {
                 state.profile.auto_pager = enabled;
                 changed = true;
             }
+            if let Some(group) = board_by {
+                state.profile.board_group = group;
+                changed = true;
+            }
+            if board_tags_all {
+                state.profile.board_tags.clear();
+                changed = true;
+            } else if !board_tags.is_empty() {
+                state.profile.board_tags = normalize_tags_ordered(&board_tags);
+                changed = true;
+            }
+            if clear_list_format {
+                state.profile.list_format = None;
+                changed = true;
+            }
+            if let Some(source) = list_format {
+                parse_template_arg(&source)?;
+                state.profile.list_format = Some(source).filter(|s| !s.trim().is_empty());
+                changed = true;
+            }
+            if let Some(style) = date_style {
+                state.profile.date_style = style;
+                changed = true;
+            }
+            for (view, style) in date_styles {
+                match style {
+                    Some(style) => state.profile.date_styles.insert(view, style),
+                    None => state.profile.date_styles.remove(&view),
+                };
+                changed = true;
+            }
+            if date_format_default {
+                state.profile.date_format = None;
+                changed = true;
+            }
+            if let Some(format) = date_format {
+                if let Err(e) = validate_date_format(&format) {
+                    return Err(TodoError::Usage(format!("invalid date format: {e}")));
+                }
+                state.profile.date_format = Some(format);
+                changed = true;
+            }
             if reset_greeting {
                 state.profile.last_greeted = None;
                 changed = true;
             }
 
             if changed {
-                save_state(&state_path, &state);
+                save_state(state_path, state)?;
+            }
+            if json {
+                json::emit(&json::Settings {
+                    updated: changed,
+                    settings: &state.profile,
+                });
+                return Ok(());
+            }
+            if changed {
                 println!("Updated settings");
             }
-            print_settings(&state, color);
+            print_settings(state, color);
         }
 
+        Commands::Ids { command } => match command {
+            IdsCommands::Renumber => {
+                let before: Vec<u64> = tasks.iter().map(|t| t.id).collect();
+                let changed = renumber_ids(tasks, state.config.id_scope);
+                if changed > 0 {
+                    save_tasks(path, tasks)?;
+                }
+                if json {
+                    let renumbered: Vec<Task> = tasks
+                        .iter()
+                        .zip(before)
+                        .filter(|(t, id)| t.id != *id)
+                        .map(|(t, _)| t.clone())
+                        .collect();
+                    json::emit(&Action::new("renumbered", renumbered));
+                    return Ok(());
+                }
+                println!("Renumbered {changed} tasks");
+            }
+        },
+
         Commands::Config {
             default_sort,
             default_desc,
@@ -929,27 +1669,69 @@ pub fn run() {
             reminder_days,
             id_scope,
             use_uuid,
+            quick_add,
+            bulk_confirm_threshold,
+            urgency,
+            urgency_reset,
+            theme,
+            theme_styles,
+            tag_colors,
+            branch_colors,
+            theme_reset,
         } => {
-            let updated = update_config(
-                &mut state.config,
+            if use_uuid == Some(true) {
+                let added = backfill_uids(tasks);
+                if added > 0 {
+                    save_tasks(path, tasks)?;
+                    if !json {
+                        println!("Assigned uids to {added} existing tasks");
+                    }
+                }
+            }
+            let update = ConfigUpdate {
                 default_sort,
                 default_desc,
-                color_pref,
+                color: color_pref,
                 reminder_days,
                 id_scope,
                 use_uuid,
-            );
+                quick_add,
+                bulk_confirm_threshold,
+                urgency,
+                urgency_reset,
+                theme,
+                theme_styles,
+                tag_colors,
+                branch_colors,
+                theme_reset,
+            };
+            let updated = update_config(&mut state.config, update);
             if updated {
-                save_state(&state_path, &state);
+                save_state(state_path, state)?;
+            }
+            if json {
+                json::emit(&json::Config {
+                    updated,
+                    config: &state.config,
+                });
+            } else if updated {
                 println!("Updated config");
             } else {
                 print_config(&state.config);
             }
         }
     }
+    Ok(())
 }
 
-fn reserved_top_level_command_names() -> HashSet<String> {
+/// A clap error as a `TodoError`: the first line of its message, without the `error:` prefix.
+pub(crate) fn usage_error(e: &clap::Error) -> TodoError {
+    let rendered = e.render().to_string();
+    let message = rendered.lines().next().unwrap_or_default();
+    TodoError::Usage(message.trim_start_matches("error: ").to_string())
+}
+
+pub(crate) fn reserved_top_level_command_names() -> HashSet<String> {
     let mut reserved: HashSet<String> = HashSet::new();
     let cmd = Cli::command();
     for sub in cmd.get_subcommands() {
@@ -966,7 +1748,10 @@ fn reserved_top_level_command_names() -> HashSet<String> {
     reserved
 }
 
-fn expand_saved_command_args(raw_args: &[String], state: &crate::model::AppState) -> Vec<String> {
+pub(crate) fn expand_saved_command_args(
+    raw_args: &[String],
+    state: &crate::model::AppState,
+) -> Vec<String> {
     if raw_args.len() < 2 {
         return raw_args.to_vec();
     }
@@ -1009,12 +1794,17 @@ fn handle_saved_commands(
     command: &SavedCommands,
     state: &mut crate::model::AppState,
     state_path: &std::path::PathBuf,
-) {
+) -> Result<(), TodoError> {
+    let json = output::mode().json;
     match command {
         SavedCommands::List => {
+            if json {
+                print_saved_json(state, None);
+                return Ok(());
+            }
             if state.profile.saved_commands.is_empty() {
                 println!("No saved commands.");
-                return;
+                return Ok(());
             }
 
             for (name, args) in state.profile.saved_commands.iter() {
@@ -1030,8 +1820,9 @@ fn handle_saved_commands(
                 .find(|k| k.eq_ignore_ascii_case(name))
                 .cloned();
             let Some(key) = key else {
-                eprintln!("No saved command named '{name}'");
-                std::process::exit(1);
+                return Err(TodoError::NotFound(format!(
+                    "no saved command named '{name}'"
+                )));
             };
             let args = state
                 .profile
@@ -1039,6 +1830,10 @@ fn handle_saved_commands(
                 .get(&key)
                 .cloned()
                 .unwrap_or_default();
+            if json {
+                print_saved_json(state, Some(&key));
+                return Ok(());
+            }
             println!("{key}: todo {}", args.join(" "));
         }
 
@@ -1050,36 +1845,44 @@ fn handle_saved_commands(
                 .find(|k| k.eq_ignore_ascii_case(name))
                 .cloned();
             let Some(key) = key else {
-                eprintln!("No saved command named '{name}'");
-                std::process::exit(1);
+                return Err(TodoError::NotFound(format!(
+                    "no saved command named '{name}'"
+                )));
             };
             state.profile.saved_commands.remove(&key);
-            save_state(state_path, state);
+            save_state(state_path, state)?;
+            if json {
+                print_saved_json(state, None);
+                return Ok(());
+            }
             println!("Removed saved command '{key}'");
         }
 
         SavedCommands::Save { name, args } => {
             let trimmed = name.trim();
             if trimmed.is_empty() {
-                eprintln!("Saved command name cannot be empty");
-                std::process::exit(2);
+                return Err(TodoError::Usage(
+                    "saved command name cannot be empty".to_string(),
+                ));
             }
             if trimmed.starts_with('-') {
-                eprintln!("Saved command name cannot start with '-'");
-                std::process::exit(2);
+                return Err(TodoError::Usage(
+                    "saved command name cannot start with '-'".to_string(),
+                ));
             }
 
             let reserved = reserved_top_level_command_names();
             if reserved.contains(&trimmed.to_lowercase()) {
-                eprintln!("'{trimmed}' is a built-in command name/alias and cannot be overwritten");
-                std::process::exit(2);
+                return Err(TodoError::Usage(format!(
+                    "'{trimmed}' is a built-in command or alias and cannot be overwritten"
+                )));
             }
 
             if args.is_empty() {
-                eprintln!(
-                    "No command provided. Example: todo saved save today -- list --group-by due-day"
-                );
-                std::process::exit(2);
+                return Err(TodoError::Usage(
+                    "no command given, e.g. todo saved save today -- list --group-by due-day"
+                        .to_string(),
+                ));
             }
 
             let existing_key = state
@@ -1089,9 +1892,14 @@ fn handle_saved_commands(
                 .find(|k| k.eq_ignore_ascii_case(trimmed))
                 .cloned();
             if let Some(existing_key) = existing_key {
-                if !confirm_overwrite(&format!("Overwrite saved command '{existing_key}'?")) {
+                if !confirm(&format!("Overwrite saved command '{existing_key}'?")) {
+                    if json {
+                        return Err(TodoError::Failed(format!(
+                            "saved command '{existing_key}' not overwritten"
+                        )));
+                    }
                     println!("Not overwritten.");
-                    return;
+                    return Ok(());
                 }
                 state.profile.saved_commands.remove(&existing_key);
             }
@@ -1100,13 +1908,30 @@ fn handle_saved_commands(
                 .profile
                 .saved_commands
                 .insert(trimmed.to_string(), args.clone());
-            save_state(state_path, state);
+            save_state(state_path, state)?;
+            if json {
+                print_saved_json(state, None);
+                return Ok(());
+            }
             println!("Saved command '{trimmed}'");
         }
     }
+    Ok(())
 }
 
-fn confirm_overwrite(prompt: &str) -> bool {
+/// Every saved command, or only `only`, as a `--json` document.
+fn print_saved_json(state: &crate::model::AppState, only: Option<&str>) {
+    let saved_commands = state
+        .profile
+        .saved_commands
+        .iter()
+        .filter(|(name, _)| only.is_none_or(|only| only == name.as_str()))
+        .map(|(name, args)| (name.as_str(), args.as_slice()))
+        .collect();
+    json::emit(&json::SavedCommands { saved_commands });
+}
+
+fn confirm(prompt: &str) -> bool {
     if !std::io::stdin().is_terminal() {
         return false;
     }
@@ -1123,7 +1948,137 @@ fn confirm_overwrite(prompt: &str) -> bool {
     matches!(input.trim(), "y" | "Y" | "yes" | "YES" | "Yes")
 }
 
-fn task_matches(task: &Task, query: &str) -> bool {
+/// Append a new task built from `entry` and return its index.
+pub(crate) fn create_task(
+    tasks: &mut Vec<Task>,
+    state: &crate::model::AppState,
+    entry: QuickAdd,
+    content: Option<String>,
+) -> usize {
+    let created_at = chrono::Local::now().to_rfc3339();
+    let branch = entry.branch.unwrap_or_else(|| state.current_branch.clone());
+    let next_id = next_task_id(tasks, state.config.id_scope, Some(&branch));
+    let uid = if state.config.use_uuid {
+        Some(Uuid::new_v4().to_string())
+    } else {
+        None
+    };
+
+    tasks.push(Task {
+        id: next_id,
+        uid,
+        title: entry.title,
+        content,
+        tags: entry.tags,
+        due: entry.due,
+        priority: entry.priority,
+        repeat: entry.repeat,
+        branch,
+        archived: false,
+        done: false,
+        created_at,
+        updated_at: None,
+        completed_at: None,
+    });
+    tasks.len() - 1
+}
+
+fn print_created(task: &Task) {
+    if output::mode().json {
+        json::emit(&Action::new("created", vec![task.clone()]));
+        return;
+    }
+    match task.uid.as_deref() {
+        Some(uid) => println!("Created task #{} ({})", task.id, short_uid(uid)),
+        None => println!("Created task #{}", task.id),
+    }
+}
+
+/// Copies of the tasks at `indices`, for `--json` results.
+fn tasks_at(tasks: &[Task], indices: &[usize]) -> Vec<Task> {
+    indices.iter().map(|idx| tasks[*idx].clone()).collect()
+}
+
+fn find_task_index(tasks: &[Task], key: &str, current_branch: &str) -> Result<usize, TodoError> {
+    resolve_task_ref(tasks, key, current_branch)
+}
+
+/// Indices of tasks a bulk command would consider: same branch, not archived, matching the query.
+fn bulk_candidate_indices(tasks: &[Task], query: &str, branch: &str) -> Vec<usize> {
+    let q = query.to_lowercase();
+    tasks
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.branch.eq_ignore_ascii_case(branch))
+        .filter(|(_, t)| !t.archived)
+        .filter(|(_, t)| task_matches(t, &q))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+fn tasks_ref<'a>(tasks: &'a [Task], indices: &[usize]) -> Vec<&'a Task> {
+    indices.iter().map(|idx| &tasks[*idx]).collect()
+}
+
+/// Preview or confirm a bulk change. Returns true when the change should be applied.
+/// `kind` names the change in `--json` output; `action` describes it in text.
+#[allow(clippy::too_many_arguments)]
+fn confirm_bulk(
+    targets: &[&Task],
+    kind: &'static str,
+    action: &str,
+    dry_run: bool,
+    yes: bool,
+    state: &crate::model::AppState,
+    color: bool,
+) -> Result<bool, TodoError> {
+    if targets.is_empty() {
+        return Err(TodoError::NothingMatched("no matching tasks".to_string()));
+    }
+
+    let count = targets.len();
+    let noun = if count == 1 { "task" } else { "tasks" };
+    let json = output::mode().json;
+    if dry_run && json {
+        json::emit(&json::DryRun {
+            action: kind,
+            dry_run: true,
+            count,
+            tasks: targets.to_vec(),
+        });
+        return Ok(false);
+    }
+    if dry_run {
+        println!("Dry run: {count} {noun} would be {action}");
+        print_task_list(targets, state, color, None);
+        return Ok(false);
+    }
+
+    if yes || count <= state.config.bulk_confirm_threshold {
+        return Ok(true);
+    }
+    if json {
+        return Err(TodoError::Usage(format!(
+            "{count} {noun} would be {action}; use --yes to confirm with --json"
+        )));
+    }
+
+    // Without a terminal there is nobody to ask; scripts must opt in with --yes.
+    if !std::io::stdin().is_terminal() {
+        return Err(TodoError::Usage(format!(
+            "{count} {noun} would be {action}; use --yes to confirm without a terminal"
+        )));
+    }
+    print_task_list(targets, state, color, None);
+    if confirm(&format!("{count} {noun} will be {action}. Continue?")) {
+        Ok(true)
+    } else {
+        println!("Aborted. Use --yes to skip the confirmation.");
+        Ok(false)
+    }
+}
+
+pub(crate) fn task_matches(task: &Task, query: &str) -> bool {
     if task.title.to_lowercase().contains(query) {
         return true;
     }
@@ -1161,7 +2116,7 @@ fn apply_tag_changes(tags: &mut Vec<String>, add: &[String], remove: &[String])
     tags.dedup();
 }
 
-fn next_task_id(tasks: &[Task], scope: IdScope, branch: Option<&str>) -> u64 {
+pub(crate) fn next_task_id(tasks: &[Task], scope: IdScope, branch: Option<&str>) -> u64 {
     match scope {
         IdScope::Global => tasks.iter().map(|t| t.id).max().unwrap_or(0) + 1,
         IdScope::Branch => {
@@ -1177,12 +2132,16 @@ fn next_task_id(tasks: &[Task], scope: IdScope, branch: Option<&str>) -> u64 {
     }
 }
 
-fn mark_done_with_repeat(task: &mut Task, next_id: &mut u64, use_uuid: bool) -> Option<Task> {
+pub(crate) fn mark_done_with_repeat(
+    task: &mut Task,
+    next_id: &mut u64,
+    use_uuid: bool,
+) -> Option<Task> {
     if task.done {
         return None;
     }
 
-    task.done = true;
+    task.set_done(true);
     let repeat = task.repeat?;
 
     let base_due = task.due.unwrap_or_else(|| Local::now().date_naive());
@@ -1193,75 +2152,57 @@ fn mark_done_with_repeat(task: &mut Task, next_id: &mut u64, use_uuid: bool) ->
     copy.done = false;
     copy.due = Some(next_due);
     copy.created_at = Local::now().to_rfc3339();
-    if use_uuid {
+    copy.updated_at = None;
+    copy.completed_at = None;
+    // The copy needs its own uid: a shared one would make the uid ambiguous.
+    if use_uuid || copy.uid.is_some() {
         copy.uid = Some(Uuid::new_v4().to_string());
     }
     *next_id += 1;
     Some(copy)
 }
 
+/// Mark the tasks at `indices` done (adding the next occurrence of repeating ones) or not done.
 fn bulk_set_done(
     tasks: &mut Vec<Task>,
-    query: &str,
+    indices: &[usize],
     branch: &str,
     done: bool,
     id_scope: IdScope,
     use_uuid: bool,
-) -> usize {
-    let q = query.to_lowercase();
-    let mut count = 0usize;
+) {
     let mut next_id = next_task_id(tasks, id_scope, Some(branch));
     let mut new_tasks: Vec<Task> = Vec::new();
-
-    for task in tasks.iter_mut() {
-        if !task.branch.eq_ignore_ascii_case(branch) {
-            continue;
-        }
-        if task.archived {
-            continue;
-        }
-        if !task_matches(task, &q) {
-            continue;
-        }
-
+    for &idx in indices {
         if done {
-            if task.done {
-                continue;
-            }
-            if let Some(next_task) = mark_done_with_repeat(task, &mut next_id, use_uuid) {
+            if let Some(next_task) = mark_done_with_repeat(&mut tasks[idx], &mut next_id, use_uuid)
+            {
                 new_tasks.push(next_task);
             }
-            count += 1;
-        } else if task.done {
-            task.done = false;
-            count += 1;
+        } else {
+            tasks[idx].set_done(false);
         }
     }
-
-    if !new_tasks.is_empty() {
-        tasks.extend(new_tasks);
-    }
-
-    count
+    tasks.extend(new_tasks);
 }
 
-fn bulk_delete(tasks: &mut Vec<Task>, query: &str, branch: &str) -> usize {
-    let q = query.to_lowercase();
-    let before = tasks.len();
+/// Remove the tasks at `indices` and return them.
+fn bulk_delete(tasks: &mut Vec<Task>, indices: &[usize]) -> Vec<Task> {
+    let mut removed = Vec::with_capacity(indices.len());
+    let mut idx = 0;
     tasks.retain(|task| {
-        if !task.branch.eq_ignore_ascii_case(branch) {
-            return true;
+        let remove = indices.contains(&idx);
+        if remove {
+            removed.push(task.clone());
         }
-        if task.archived {
-            return true;
-        }
-        !task_matches(task, &q)
+        idx += 1;
+        !remove
     });
-    before - tasks.len()
+    removed
 }
 
 struct BulkEditOpts<'a> {
-    due: Option<String>,
+    due: Option<NaiveDate>,
     priority: Option<Priority>,
     repeat: Option<Repeat>,
     clear_due: bool,
@@ -1272,50 +2213,31 @@ struct BulkEditOpts<'a> {
     clear_tags: bool,
 }
 
-fn bulk_edit(tasks: &mut [Task], query: &str, branch: &str, opts: &BulkEditOpts) -> usize {
-    if opts.due.is_none()
-        && opts.priority.is_none()
-        && opts.repeat.is_none()
-        && opts.tags.is_empty()
-        && opts.remove_tags.is_empty()
-        && !opts.clear_due
-        && !opts.clear_priority
-        && !opts.clear_repeat
-        && !opts.clear_tags
-    {
-        return 0;
+impl BulkEditOpts<'_> {
+    /// No field to set or clear was given.
+    fn is_empty(&self) -> bool {
+        self.due.is_none()
+            && self.priority.is_none()
+            && self.repeat.is_none()
+            && self.tags.is_empty()
+            && self.remove_tags.is_empty()
+            && !self.clear_due
+            && !self.clear_priority
+            && !self.clear_repeat
+            && !self.clear_tags
     }
+}
 
-    let parsed_due = if let Some(value) = opts.due.as_deref() {
-        match parse_due(value) {
-            Ok(due) => Some(due),
-            Err(e) => {
-                eprintln!("Invalid due date: {e}");
-                std::process::exit(2);
-            }
-        }
-    } else {
-        None
-    };
-
-    let q = query.to_lowercase();
-    let mut count = 0usize;
-    for task in tasks.iter_mut() {
-        if !task.branch.eq_ignore_ascii_case(branch) {
-            continue;
-        }
-        if task.archived {
-            continue;
-        }
-        if !task_matches(task, &q) {
-            continue;
-        }
-
+/// Apply `opts` to the tasks at `indices`; returns those that changed.
+fn bulk_edit(tasks: &mut [Task], indices: &[usize], opts: &BulkEditOpts) -> Vec<usize> {
+    let mut updated: Vec<usize> = Vec::new();
+    for &idx in indices {
+        let task = &mut tasks[idx];
         let mut changed = false;
         if opts.clear_due {
             task.due = None;
             changed = true;
-        } else if let Some(due) = parsed_due {
+        } else if let Some(due) = opts.due {
             task.due = Some(due);
             changed = true;
         }
@@ -1350,35 +2272,25 @@ fn bulk_edit(tasks: &mut [Task], query: &str, branch: &str, opts: &BulkEditOpts)
         }
 
         if changed {
-            count += 1;
+            task.touch();
+            updated.push(idx);
         }
     }
 
-    count
+    updated
 }
 
-fn bulk_move(tasks: &mut [Task], query: &str, branch: &str, target: &str, scope: IdScope) -> usize {
-    let q = query.to_lowercase();
-    let mut count = 0usize;
+fn bulk_move(tasks: &mut [Task], indices: &[usize], target: &str, scope: IdScope) {
     let mut next_id = next_task_id(tasks, scope, Some(target));
-    for task in tasks.iter_mut() {
-        if !task.branch.eq_ignore_ascii_case(branch) {
-            continue;
-        }
-        if task.archived {
-            continue;
-        }
-        if !task_matches(task, &q) {
-            continue;
-        }
+    for &idx in indices {
+        let task = &mut tasks[idx];
         task.branch = target.to_string();
         if scope == IdScope::Branch {
             task.id = next_id;
             next_id += 1;
         }
-        count += 1;
+        task.touch();
     }
-    count
 }
 
 fn print_reminders(
@@ -1423,6 +2335,15 @@ fn print_reminders(
         }
     }
 
+    if output::mode().json {
+        json::emit(&json::Reminders {
+            overdue,
+            today: today_list,
+            upcoming,
+        });
+        return;
+    }
+
     if overdue.is_empty() && today_list.is_empty() && upcoming.is_empty() {
         println!("No reminders.");
         return;
@@ -1430,7 +2351,7 @@ fn print_reminders(
 
     if !overdue.is_empty() {
         println!("Overdue ({})", overdue.len());
-        print_task_list(&overdue, state, color, false);
+        print_task_list(&overdue, state, color, None);
     }
 
     if !today_list.is_empty() {
@@ -1438,7 +2359,7 @@ fn print_reminders(
             println!();
         }
         println!("Due today ({})", today_list.len());
-        print_task_list(&today_list, state, color, false);
+        print_task_list(&today_list, state, color, None);
     }
 
     if !upcoming.is_empty() {
@@ -1446,7 +2367,7 @@ fn print_reminders(
             println!();
         }
         println!("Upcoming ({})", upcoming.len());
-        print_task_list(&upcoming, state, color, false);
+        print_task_list(&upcoming, state, color, None);
     }
 }
 
@@ -1483,6 +2404,47 @@ fn print_stats(tasks: &[Task], current_branch: &str) {
         }
     }
 
+    let mut branches = collect_branches(tasks);
+    branches.sort_by_key(|branch| branch.to_lowercase());
+    let branches: Vec<BranchStats> = branches
+        .into_iter()
+        .map(|branch| {
+            let mut stats = BranchStats {
+                current: branch.eq_ignore_ascii_case(current_branch),
+                name: branch,
+                total: 0,
+                done: 0,
+                archived: 0,
+            };
+            for task in tasks.iter() {
+                if task.branch.eq_ignore_ascii_case(&stats.name) {
+                    stats.total += 1;
+                    if task.done {
+                        stats.done += 1;
+                    }
+                    if task.archived {
+                        stats.archived += 1;
+                    }
+                }
+            }
+            stats
+        })
+        .collect();
+
+    if output::mode().json {
+        json::emit(&json::Stats {
+            total,
+            open: total - done,
+            done,
+            overdue,
+            due_today,
+            archived,
+            repeating,
+            branches,
+        });
+        return;
+    }
+
     println!("Total:    {total}");
     println!("Open:     {}", total - done);
     println!("Done:     {done}");
@@ -1491,36 +2453,16 @@ fn print_stats(tasks: &[Task], current_branch: &str) {
     println!("Archived: {archived}");
     println!("Repeating:{:>3}", repeating);
 
-    let mut branches = collect_branches(tasks);
-    branches.sort_by_key(|branch| branch.to_lowercase());
     if branches.is_empty() {
         return;
     }
 
     println!("\nBy branch:");
     for branch in branches {
-        let mut b_total = 0usize;
-        let mut b_done = 0usize;
-        let mut b_archived = 0usize;
-        for task in tasks.iter() {
-            if task.branch.eq_ignore_ascii_case(&branch) {
-                b_total += 1;
-                if task.done {
-                    b_done += 1;
-                }
-                if task.archived {
-                    b_archived += 1;
-                }
-            }
-        }
-        let mark = if branch.eq_ignore_ascii_case(current_branch) {
-            "*"
-        } else {
-            " "
-        };
+        let mark = if branch.current { "*" } else { " " };
         println!(
-            "{mark} {branch}: {}/{} done, {b_archived} archived",
-            b_done, b_total
+            "{mark} {}: {}/{} done, {} archived",
+            branch.name, branch.done, branch.total, branch.archived
         );
     }
 }
@@ -1538,6 +2480,8 @@ struct ImportTaskJson {
     done: Option<bool>,
     archived: Option<bool>,
     created_at: Option<String>,
+    updated_at: Option<String>,
+    completed_at: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -1553,6 +2497,8 @@ struct ImportTaskCsv {
     done: Option<String>,
     archived: Option<String>,
     created_at: Option<String>,
+    updated_at: Option<String>,
+    completed_at: Option<String>,
 }
 
 struct ImportResult {
@@ -1560,6 +2506,17 @@ struct ImportResult {
     skipped: usize,
     skipped_title: usize,
     skipped_parse: usize,
+    skipped_uid: usize,
+}
+
+/// Format-specific `import` flags.
+struct ImportOptions {
+    delimiter: u8,
+    /// Columns of a CSV file without a header row.
+    csv_columns: Option<Vec<CsvColumn>>,
+    events: bool,
+    headings: MarkdownHeadings,
+    nested: MarkdownNested,
 }
 
 fn import_tasks(
@@ -1567,72 +2524,129 @@ fn import_tasks(
     format: ImportFormat,
     file: &str,
     branch: &str,
+    options: &ImportOptions,
     config: &AppConfig,
-) -> ImportResult {
+) -> Result<ImportResult, TodoError> {
     match format {
         ImportFormat::Json => import_json(tasks, file, branch, config),
-        ImportFormat::Csv => import_csv(tasks, file, branch, config),
+        ImportFormat::Csv => import_csv(tasks, file, branch, options, config),
+        ImportFormat::Ics => import_ics(tasks, file, branch, options.events, config),
+        ImportFormat::Todotxt => {
+            let bytes = read_import_file(file)?;
+            let rows = todotxt::parse_tasks(&String::from_utf8_lossy(&bytes));
+            Ok(import_parts(tasks, rows, branch, config))
+        }
+        ImportFormat::Taskwarrior => {
+            let bytes = read_import_file(file)?;
+            let rows = taskwarrior::parse_tasks(&bytes).map_err(|e| {
+                TodoError::Usage(format!("invalid Taskwarrior JSON in {file}: {e}"))
+            })?;
+            Ok(import_parts(tasks, rows, branch, config))
+        }
+        ImportFormat::Markdown => {
+            let bytes = read_import_file(file)?;
+            let text = String::from_utf8_lossy(&bytes);
+            let rows = markdown::parse_checklists(&text, options.headings, options.nested);
+            Ok(import_parts(tasks, rows, branch, config))
+        }
     }
 }
 
+/// The contents of a file given to `import`.
+fn read_import_file(file: &str) -> Result<Vec<u8>, TodoError> {
+    std::fs::read(file).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => TodoError::NotFound(format!("no such file: {file}")),
+        _ => TodoError::Failed(format!("cannot read {file}: {e}")),
+    })
+}
+
 fn import_json(
     tasks: &mut Vec<Task>,
     file: &str,
     branch: &str,
     config: &AppConfig,
-) -> ImportResult {
-    let Ok(bytes) = std::fs::read(file) else {
-        eprintln!("Failed to read file: {file}");
-        std::process::exit(2);
-    };
-    let Ok(rows) = serde_json::from_slice::<Vec<ImportTaskJson>>(&bytes) else {
-        eprintln!("Invalid JSON format");
-        std::process::exit(2);
-    };
+) -> Result<ImportResult, TodoError> {
+    let bytes = read_import_file(file)?;
+    let rows: Vec<ImportTaskJson> = serde_json::from_slice(&bytes)
+        .map_err(|e| TodoError::Usage(format!("invalid JSON in {file}: {e}")))?;
+    let rows = rows
+        .into_iter()
+        .map(|row| TaskParts {
+            uid: row.uid,
+            title: row.title.unwrap_or_default(),
+            content: row.content,
+            tags: row.tags.unwrap_or_default(),
+            due: row.due,
+            priority: row.priority,
+            repeat: row.repeat,
+            branch: row.branch,
+            done: row.done,
+            archived: row.archived,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            completed_at: row.completed_at,
+        })
+        .collect();
+    Ok(import_parts(tasks, rows, branch, config))
+}
+
+fn import_ics(
+    tasks: &mut Vec<Task>,
+    file: &str,
+    branch: &str,
+    events: bool,
+    config: &AppConfig,
+) -> Result<ImportResult, TodoError> {
+    let bytes = read_import_file(file)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let rows = ics::parse_calendar(&text, events)
+        .map_err(|e| TodoError::Usage(format!("invalid iCalendar in {file}: {e}")))?;
+    Ok(import_parts(tasks, rows, branch, config))
+}
 
+/// Add parsed tasks, numbering them per target branch when ids are branch-scoped.
+fn import_parts(
+    tasks: &mut Vec<Task>,
+    rows: Vec<TaskParts>,
+    branch: &str,
+    config: &AppConfig,
+) -> ImportResult {
     let mut next_id = next_task_id(tasks, config.id_scope, Some(branch));
     let mut branch_ids: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
     let mut added = 0usize;
     let mut skipped = 0usize;
     let mut skipped_title = 0usize;
+    let mut skipped_uid = 0usize;
 
-    for row in rows {
-        let Some(title) = row.title else {
+    for mut row in rows {
+        if row.title.trim().is_empty() {
             skipped += 1;
             skipped_title += 1;
             continue;
-        };
+        }
+        // Uids are task handles, so a row whose uid is already taken is left out.
+        if let Some(uid) = row.uid.as_deref()
+            && tasks.iter().any(|t| {
+                t.uid
+                    .as_deref()
+                    .is_some_and(|u| u.eq_ignore_ascii_case(uid))
+            })
+        {
+            skipped += 1;
+            skipped_uid += 1;
+            continue;
+        }
         let target_branch =
-            normalize_branch(row.branch.clone()).unwrap_or_else(|| branch.to_string());
+            normalize_branch(row.branch.take()).unwrap_or_else(|| branch.to_string());
         let next_id_ref = if config.id_scope == IdScope::Branch {
-            let entry = branch_ids
+            branch_ids
                 .entry(target_branch.clone())
-                .or_insert_with(|| next_task_id(tasks, config.id_scope, Some(&target_branch)));
-            entry
+                .or_insert_with(|| next_task_id(tasks, config.id_scope, Some(&target_branch)))
         } else {
             &mut next_id
         };
-
-        let tags = row.tags.unwrap_or_default();
-        let task = build_task_from_parts(
-            TaskParts {
-                uid: row.uid,
-                title,
-                content: row.content,
-                tags,
-                due: row.due,
-                priority: row.priority,
-                repeat: row.repeat,
-                branch: Some(target_branch.clone()),
-                done: row.done,
-                archived: row.archived,
-                created_at: row.created_at,
-            },
-            branch,
-            next_id_ref,
-            config,
-        );
-        if let Some(task) = task {
+        row.branch = Some(target_branch);
+        if let Some(task) = build_task_from_parts(row, branch, next_id_ref, config) {
             tasks.push(task);
             added += 1;
         } else {
@@ -1645,110 +2659,94 @@ fn import_json(
         skipped,
         skipped_title,
         skipped_parse: 0,
+        skipped_uid,
     }
 }
 
-fn import_csv(tasks: &mut Vec<Task>, file: &str, branch: &str, config: &AppConfig) -> ImportResult {
-    let mut rdr = match csv::Reader::from_path(file) {
-        Ok(reader) => reader,
-        Err(_) => {
-            eprintln!("Failed to read file: {file}");
-            std::process::exit(2);
-        }
+fn import_csv(
+    tasks: &mut Vec<Task>,
+    file: &str,
+    branch: &str,
+    options: &ImportOptions,
+    config: &AppConfig,
+) -> Result<ImportResult, TodoError> {
+    let bytes = read_import_file(file)?;
+    let (rows, skipped_parse) =
+        parse_csv(&bytes, options.delimiter, options.csv_columns.as_deref())?;
+    let mut result = import_parts(tasks, rows, branch, config);
+    result.skipped += skipped_parse;
+    result.skipped_parse = skipped_parse;
+    Ok(result)
+}
+
+/// Rows of a CSV file and the number that could not be read. `columns` names the
+/// columns of a file without a header row; otherwise the header row does.
+pub(crate) fn parse_csv(
+    bytes: &[u8],
+    delimiter: u8,
+    columns: Option<&[CsvColumn]>,
+) -> Result<(Vec<TaskParts>, usize), TodoError> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(columns.is_none())
+        .from_reader(bytes);
+    let headers = match columns {
+        Some(columns) => columns.iter().map(|c| value_name(*c)).collect(),
+        None => rdr
+            .headers()
+            .map_err(|e| TodoError::Failed(format!("cannot read the CSV header: {e}")))?
+            .clone(),
     };
 
-    let mut next_id = next_task_id(tasks, config.id_scope, Some(branch));
-    let mut branch_ids: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
-    let mut added = 0usize;
-    let mut skipped = 0usize;
+    let mut rows = Vec::new();
     let mut skipped_parse = 0usize;
-
-    for result in rdr.deserialize::<ImportTaskCsv>() {
-        let row = match result {
-            Ok(row) => row,
-            Err(_) => {
-                skipped += 1;
-                skipped_parse += 1;
-                continue;
-            }
-        };
-
-        let priority = row
-            .priority
-            .as_deref()
-            .and_then(|p| p.parse::<crate::model::Priority>().ok());
-        let repeat = row.repeat.as_deref().and_then(|r| r.parse::<Repeat>().ok());
-        let done = row.done.as_deref().and_then(parse_bool_flag);
-        let archived = row.archived.as_deref().and_then(parse_bool_flag);
-
-        let target_branch =
-            normalize_branch(row.branch.clone()).unwrap_or_else(|| branch.to_string());
-        let next_id_ref = if config.id_scope == IdScope::Branch {
-            let entry = branch_ids
-                .entry(target_branch.clone())
-                .or_insert_with(|| next_task_id(tasks, config.id_scope, Some(&target_branch)));
-            entry
-        } else {
-            &mut next_id
+    for result in rdr.records() {
+        let Some(row) = result
+            .ok()
+            .and_then(|record| record.deserialize::<ImportTaskCsv>(Some(&headers)).ok())
+        else {
+            skipped_parse += 1;
+            continue;
         };
-
-        let tags = row
-            .tags
-            .as_deref()
-            .map(|raw| {
-                raw.split(',')
-                    .map(|t| t.trim().to_string())
-                    .filter(|t| !t.is_empty())
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_default();
-
-        let task = build_task_from_parts(
-            TaskParts {
-                uid: row.uid,
-                title: row.title,
-                content: row.content,
-                tags,
-                due: row.due,
-                priority,
-                repeat,
-                branch: Some(target_branch.clone()),
-                done,
-                archived,
-                created_at: row.created_at,
-            },
-            branch,
-            next_id_ref,
-            config,
-        );
-        if let Some(task) = task {
-            tasks.push(task);
-            added += 1;
-        } else {
-            skipped += 1;
-        }
-    }
-
-    ImportResult {
-        imported: added,
-        skipped,
-        skipped_title: 0,
-        skipped_parse,
+        let tags = row.tags.as_deref().map(split_tags).unwrap_or_default();
+        rows.push(TaskParts {
+            uid: row.uid,
+            title: row.title,
+            content: row.content,
+            tags,
+            due: row.due,
+            priority: row
+                .priority
+                .as_deref()
+                .and_then(|p| p.parse::<crate::model::Priority>().ok()),
+            repeat: row.repeat.as_deref().and_then(|r| r.parse::<Repeat>().ok()),
+            branch: row.branch,
+            done: row.done.as_deref().and_then(parse_bool_flag),
+            archived: row.archived.as_deref().and_then(parse_bool_flag),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            completed_at: row.completed_at,
+        });
     }
+    Ok((rows, skipped_parse))
 }
 
-struct TaskParts {
-    uid: Option<String>,
-    title: String,
-    content: Option<String>,
-    tags: Vec<String>,
-    due: Option<String>,
-    priority: Option<Priority>,
-    repeat: Option<Repeat>,
-    branch: Option<String>,
-    done: Option<bool>,
-    archived: Option<bool>,
-    created_at: Option<String>,
+/// A task read by an importer, before ids, defaults and the target branch are applied.
+#[derive(Default)]
+pub(crate) struct TaskParts {
+    pub(crate) uid: Option<String>,
+    pub(crate) title: String,
+    pub(crate) content: Option<String>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) due: Option<String>,
+    pub(crate) priority: Option<Priority>,
+    pub(crate) repeat: Option<Repeat>,
+    pub(crate) branch: Option<String>,
+    pub(crate) done: Option<bool>,
+    pub(crate) archived: Option<bool>,
+    pub(crate) created_at: Option<String>,
+    pub(crate) updated_at: Option<String>,
+    pub(crate) completed_at: Option<String>,
 }
 
 fn build_task_from_parts(
@@ -1761,13 +2759,7 @@ fn build_task_from_parts(
         return None;
     }
 
-    let due = match parts.due.as_deref() {
-        Some(value) => match parse_due(value) {
-            Ok(due) => Some(due),
-            Err(_) => None,
-        },
-        None => None,
-    };
+    let due = parts.due.as_deref().and_then(|value| parse_due(value).ok());
 
     let branch = normalize_branch(parts.branch).unwrap_or_else(|| default_branch.to_string());
     let created_at = parts
@@ -1798,6 +2790,8 @@ fn build_task_from_parts(
         archived,
         done,
         created_at,
+        updated_at: parts.updated_at,
+        completed_at: parts.completed_at,
     };
 
     *next_id += 1;
@@ -1814,11 +2808,19 @@ fn resolve_desc(desc: bool, asc: bool, default_desc: bool) -> bool {
     default_desc
 }
 
-fn resolve_color(cli: &Cli, config: &AppConfig) -> bool {
-    if cli.no_color {
+fn parse_template_arg(source: &str) -> Result<Template, TodoError> {
+    Template::parse(source).map_err(|e| TodoError::Usage(format!("invalid format template: {e}")))
+}
+
+/// `--no-color` and `NO_COLOR` always win, then the `color` config; `CLICOLOR_FORCE`
+/// keeps color when stdout is not a terminal.
+pub(crate) fn resolve_color(cli: &Cli, config: &AppConfig) -> bool {
+    let env_set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+    if cli.no_color || cli.accessible || env_set("NO_COLOR") || !config.color {
         return false;
     }
-    config.color && std::io::stdout().is_terminal()
+    let forced = std::env::var_os("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0");
+    forced || std::io::stdout().is_terminal()
 }
 
 fn filter_archived(task: &Task, include_archived: bool) -> bool {
@@ -1829,80 +2831,163 @@ fn filter_archived(task: &Task, include_archived: bool) -> bool {
     }
 }
 
-fn archive_by_id(tasks: &mut [Task], id: u64) -> usize {
-    let Some(task) = tasks.iter_mut().find(|t| t.id == id) else {
-        return 0;
-    };
-    task.archived = true;
-    1
+fn archive_at(tasks: &mut [Task], pos: usize) -> Vec<usize> {
+    tasks[pos].archived = true;
+    tasks[pos].touch();
+    vec![pos]
 }
 
-fn archive_done_branch(tasks: &mut [Task], branch: &str) -> usize {
-    let mut count = 0usize;
-    for task in tasks.iter_mut() {
+fn archive_done_branch(tasks: &mut [Task], branch: &str) -> Vec<usize> {
+    let mut archived: Vec<usize> = Vec::new();
+    for (idx, task) in tasks.iter_mut().enumerate() {
         if task.branch.eq_ignore_ascii_case(branch) && task.done && !task.archived {
             task.archived = true;
-            count += 1;
+            task.touch();
+            archived.push(idx);
         }
     }
-    count
+    archived
 }
 
-fn archive_done_all(tasks: &mut [Task]) -> usize {
-    let mut count = 0usize;
-    for task in tasks.iter_mut() {
+fn archive_done_all(tasks: &mut [Task]) -> Vec<usize> {
+    let mut archived: Vec<usize> = Vec::new();
+    for (idx, task) in tasks.iter_mut().enumerate() {
         if task.done && !task.archived {
             task.archived = true;
-            count += 1;
+            task.touch();
+            archived.push(idx);
         }
     }
-    count
+    archived
 }
 
-fn update_config(
-    config: &mut AppConfig,
-    default_sort: Option<SortKey>,
+struct ConfigUpdate {
+    default_sort: Option<SortSpec>,
     default_desc: Option<bool>,
     color: Option<bool>,
     reminder_days: Option<u32>,
     id_scope: Option<IdScope>,
     use_uuid: Option<bool>,
-) -> bool {
+    quick_add: Option<bool>,
+    bulk_confirm_threshold: Option<usize>,
+    urgency: Vec<(String, f64)>,
+    urgency_reset: bool,
+    theme: Option<ThemePreset>,
+    theme_styles: Vec<(ThemeElement, String)>,
+    tag_colors: Vec<(String, String)>,
+    branch_colors: Vec<(String, String)>,
+    theme_reset: bool,
+}
+
+fn update_config(config: &mut AppConfig, update: ConfigUpdate) -> bool {
     let mut changed = false;
-    if let Some(default_sort) = default_sort {
+    if let Some(default_sort) = update.default_sort {
         config.default_sort = default_sort;
         changed = true;
     }
-    if let Some(default_desc) = default_desc {
+    if let Some(default_desc) = update.default_desc {
         config.default_desc = default_desc;
         changed = true;
     }
-    if let Some(color) = color {
+    if let Some(color) = update.color {
         config.color = color;
         changed = true;
     }
-    if let Some(reminder_days) = reminder_days {
+    if let Some(reminder_days) = update.reminder_days {
         config.reminder_days = reminder_days;
         changed = true;
     }
-    if let Some(id_scope) = id_scope {
+    if let Some(id_scope) = update.id_scope {
         config.id_scope = id_scope;
         changed = true;
     }
-    if let Some(use_uuid) = use_uuid {
+    if let Some(use_uuid) = update.use_uuid {
         config.use_uuid = use_uuid;
         changed = true;
     }
+    if let Some(quick_add) = update.quick_add {
+        config.quick_add = quick_add;
+        changed = true;
+    }
+    if let Some(threshold) = update.bulk_confirm_threshold {
+        config.bulk_confirm_threshold = threshold;
+        changed = true;
+    }
+    if update.urgency_reset {
+        config.urgency = UrgencyCoefficients::default();
+        changed = true;
+    }
+    for (name, weight) in update.urgency {
+        set_coefficient(&mut config.urgency, &name, weight);
+        changed = true;
+    }
+    if update.theme_reset {
+        config.theme = ThemeConfig::default();
+        changed = true;
+    }
+    if let Some(preset) = update.theme {
+        config.theme.preset = preset;
+        changed = true;
+    }
+    // An empty spec (`default`) removes the override.
+    for (element, spec) in update.theme_styles {
+        set_or_remove(&mut config.theme.styles, element, spec);
+        changed = true;
+    }
+    for (tag, spec) in update.tag_colors {
+        set_or_remove(&mut config.theme.tag_colors, tag, spec);
+        changed = true;
+    }
+    for (branch, spec) in update.branch_colors {
+        set_or_remove(&mut config.theme.branch_colors, branch, spec);
+        changed = true;
+    }
     changed
 }
 
+fn set_or_remove<K: Ord>(map: &mut std::collections::BTreeMap<K, String>, key: K, spec: String) {
+    if spec.is_empty() {
+        map.remove(&key);
+    } else {
+        map.insert(key, spec);
+    }
+}
+
 fn print_config(config: &AppConfig) {
-    println!("default_sort: {:?}", config.default_sort);
+    println!("default_sort: {}", config.default_sort);
     println!("default_desc: {}", config.default_desc);
     println!("color:        {}", config.color);
     println!("reminder_days:{:>3}", config.reminder_days);
     println!("id_scope:     {:?}", config.id_scope);
     println!("use_uuid:     {}", config.use_uuid);
+    println!("quick_add:    {}", config.quick_add);
+    println!("bulk_confirm_threshold: {}", config.bulk_confirm_threshold);
+    println!(
+        "urgency:      {}",
+        describe_coefficients(&config.urgency).join(" ")
+    );
+    println!("theme:        {}", value_name(config.theme.preset));
+    let named = |map: &std::collections::BTreeMap<String, String>| {
+        map.iter()
+            .map(|(name, spec)| format!("{name}={spec}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    if !config.theme.styles.is_empty() {
+        let styles: Vec<String> = config
+            .theme
+            .styles
+            .iter()
+            .map(|(element, spec)| format!("{}={spec}", value_name(*element)))
+            .collect();
+        println!("theme_styles: {}", styles.join(" "));
+    }
+    if !config.theme.tag_colors.is_empty() {
+        println!("tag_colors:   {}", named(&config.theme.tag_colors));
+    }
+    if !config.theme.branch_colors.is_empty() {
+        println!("branch_colors: {}", named(&config.theme.branch_colors));
+    }
 }
 
 fn maybe_print_daily_greeting(
@@ -1911,21 +2996,21 @@ fn maybe_print_daily_greeting(
     color: bool,
     state_path: &std::path::PathBuf,
     command: &Commands,
-) {
-    if matches!(command, Commands::Completions { .. }) {
-        return;
+) -> Result<(), TodoError> {
+    if matches!(command, Commands::Completions { .. }) || output::mode().json {
+        return Ok(());
     }
     if !state.profile.daily_greeting {
-        return;
+        return Ok(());
     }
     if !std::io::stdout().is_terminal() {
-        return;
+        return Ok(());
     }
 
     let now = Local::now();
     let day_key = (now - Duration::hours(state.profile.day_start_hour as i64)).date_naive();
     if state.profile.last_greeted == Some(day_key) {
-        return;
+        return Ok(());
     }
 
     let hour = now.hour();
@@ -1945,6 +3030,7 @@ fn maybe_print_daily_greeting(
         .filter(|s| !s.is_empty())
         .unwrap_or("friend");
 
+    let accessible = output::mode().accessible;
     let greeting_text = format!("{salutation}, {name}!");
     let message_text = greeting_message(state, day_key);
 
@@ -1957,8 +3043,13 @@ fn maybe_print_daily_greeting(
         };
 
         if open > 0 {
+            let sep = if accessible {
+                ",".to_string()
+            } else {
+                format!(" {}", Glyph::Separator.as_str())
+            };
             Some(format!(
-                "Today: {open} open task{} · {overdue} overdue · {due_today} due today",
+                "Today: {open} open task{}{sep} {overdue} overdue{sep} {due_today} due today",
                 if open == 1 { "" } else { "s" },
             ))
         } else {
@@ -1969,19 +3060,38 @@ fn maybe_print_daily_greeting(
     };
 
     match state.profile.greeting_style {
+        // No banner or centering for screen readers.
+        _ if accessible => {
+            println!("{greeting_text}");
+            if !message_text.is_empty() {
+                println!("{message_text}");
+            }
+            if let Some(summary) = &summary_text {
+                println!("{summary}");
+            }
+        }
         crate::model::GreetingStyle::Banner => {
             let term_width = terminal_width().unwrap_or(80).clamp(40, 200);
-            let banner_width =
-                std::cmp::min(std::cmp::max(44, std::cmp::min(78, term_width)), term_width);
+            let banner_width = std::cmp::min(term_width.clamp(44, 78), term_width);
             let inner_width = banner_width.saturating_sub(2);
 
-            let top = format!("┏{}┓", "━".repeat(inner_width));
-            let bottom = format!("┗{}┛", "━".repeat(inner_width));
-            let greet_line = format!("┃{}┃", center_in_width(&greeting_text, inner_width));
-            let msg_line = format!("┃{}┃", center_in_width(&message_text, inner_width));
+            let rule = Glyph::HeavyRule.repeat(inner_width);
+            let bar = Glyph::HeavyVertical.as_str();
+            let top = format!(
+                "{}{rule}{}",
+                Glyph::HeavyTopLeft.as_str(),
+                Glyph::HeavyTopRight.as_str()
+            );
+            let bottom = format!(
+                "{}{rule}{}",
+                Glyph::HeavyBottomLeft.as_str(),
+                Glyph::HeavyBottomRight.as_str()
+            );
+            let greet_line = format!("{bar}{}{bar}", center_in_width(&greeting_text, inner_width));
+            let msg_line = format!("{bar}{}{bar}", center_in_width(&message_text, inner_width));
             let summary_line = summary_text
                 .as_ref()
-                .map(|s| format!("┃{}┃", center_in_width(s, inner_width)));
+                .map(|s| format!("{bar}{}{bar}", center_in_width(s, inner_width)));
 
             println!();
             print_banner_line(&top, term_width, color, BannerStyle::Border);
@@ -2000,11 +3110,14 @@ fn maybe_print_daily_greeting(
             let line = if message_text.is_empty() {
                 greeting_text.clone()
             } else {
-                format!("{greeting_text} — {message_text}")
+                format!("{greeting_text} {} {message_text}", Glyph::Dash.as_str())
             };
 
             if color {
-                println!("{}", center_line(&line, term_width).bright_yellow().bold());
+                println!(
+                    "{}",
+                    paint(ThemeElement::Greeting, &center_line(&line, term_width))
+                );
             } else {
                 println!("{}", center_line(&line, term_width));
             }
@@ -2013,7 +3126,7 @@ fn maybe_print_daily_greeting(
                 if color {
                     println!(
                         "{}",
-                        center_line(&summary, term_width).bright_cyan().dimmed()
+                        paint(ThemeElement::Muted, &center_line(&summary, term_width))
                     );
                 } else {
                     println!("{}", center_line(&summary, term_width));
@@ -2032,9 +3145,13 @@ fn maybe_print_daily_greeting(
     {
         let tip = "Tip: set your name with: todo settings --name \"Your Name\"";
         let term_width = terminal_width().unwrap_or(80).clamp(40, 200);
-        let tip_line = center_line(tip, term_width);
+        let tip_line = if accessible {
+            tip.to_string()
+        } else {
+            center_line(tip, term_width)
+        };
         if color {
-            println!("{}", tip_line.dimmed());
+            println!("{}", paint(ThemeElement::Muted, &tip_line));
         } else {
             println!("{tip_line}");
         }
@@ -2043,14 +3160,14 @@ fn maybe_print_daily_greeting(
     println!();
 
     state.profile.last_greeted = Some(day_key);
-    save_state(state_path, state);
+    save_state(state_path, state)
 }
 
 fn greeting_message(state: &crate::model::AppState, day_key: chrono::NaiveDate) -> String {
-    if let Some(msg) = state.profile.daily_message.as_deref().map(str::trim) {
-        if !msg.is_empty() {
-            return msg.to_string();
-        }
+    if let Some(msg) = state.profile.daily_message.as_deref().map(str::trim)
+        && !msg.is_empty()
+    {
+        return msg.to_string();
     }
 
     match state.profile.encouragement_mode {
@@ -2062,7 +3179,7 @@ fn greeting_message(state: &crate::model::AppState, day_key: chrono::NaiveDate)
 
 fn built_in_encouragement(day_key: chrono::NaiveDate) -> &'static str {
     const LINES: &[&str] = &[
-        "You’ve got this.",
+        "You've got this.",
         "One small step is still progress.",
         "Be kind to yourself today.",
         "Start with the easiest win.",
@@ -2090,12 +3207,13 @@ fn print_banner_line(line: &str, term_width: usize, color: bool, style: BannerSt
         return;
     }
 
-    match style {
-        BannerStyle::Border => println!("{}", centered.bright_blue().bold()),
-        BannerStyle::Greeting => println!("{}", centered.bright_yellow().bold()),
-        BannerStyle::Message => println!("{}", centered.bright_magenta().bold()),
-        BannerStyle::Summary => println!("{}", centered.bright_cyan().dimmed()),
-    }
+    let element = match style {
+        BannerStyle::Border => ThemeElement::Header,
+        BannerStyle::Greeting => ThemeElement::Greeting,
+        BannerStyle::Message => ThemeElement::Message,
+        BannerStyle::Summary => ThemeElement::Muted,
+    };
+    println!("{}", paint(element, &centered));
 }
 
 fn terminal_width() -> Option<usize> {
@@ -2106,7 +3224,7 @@ fn terminal_width() -> Option<usize> {
 }
 
 fn center_line(text: &str, width: usize) -> String {
-    let len = text.chars().count();
+    let len = display_width(text);
     if width <= len {
         return text.to_string();
     }
@@ -2115,9 +3233,9 @@ fn center_line(text: &str, width: usize) -> String {
 }
 
 fn center_in_width(text: &str, width: usize) -> String {
-    let len = text.chars().count();
+    let len = display_width(text);
     if width <= len {
-        return text.chars().take(width).collect();
+        return pad_right(text, width);
     }
 
     let left = (width - len) / 2;
@@ -2216,24 +3334,59 @@ fn print_settings(state: &crate::model::AppState, color: bool) {
             .collect::<Vec<String>>()
             .join(",")
     };
+    let board_tags = if state.profile.board_tags.is_empty() {
+        "<all>".to_string()
+    } else {
+        state.profile.board_tags.join(",")
+    };
+    let list_format = state.profile.list_format.as_deref().unwrap_or("<none>");
+    let column_layout = if state.profile.column_settings.is_empty() {
+        "<default>".to_string()
+    } else {
+        state
+            .profile
+            .column_settings
+            .iter()
+            .map(|(column, s)| {
+                let mut parts: Vec<String> = Vec::new();
+                parts.extend(s.width.map(|w| w.to_string()));
+                parts.extend(s.align.map(value_name));
+                if s.flex {
+                    parts.push("flex".to_string());
+                }
+                format!("{}={}", value_name(*column), parts.join(":"))
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+    let wrap_rows = if state.profile.wrap_rows { "on" } else { "off" };
+    let mut date_style = value_name(state.profile.date_style);
+    for (view, style) in &state.profile.date_styles {
+        date_style.push_str(&format!(" {}={}", value_name(*view), value_name(*style)));
+    }
+    let date_format = state.profile.date_format();
 
     if color {
-        println!("{} {}", "name:".dimmed(), name);
-        println!("{} {}", "pronouns:".dimmed(), pronouns);
-        println!("{} {}", "daily_greeting:".dimmed(), greeting);
+        println!("{} {}", paint(ThemeElement::Muted, "name:"), name);
+        println!("{} {}", paint(ThemeElement::Muted, "pronouns:"), pronouns);
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "daily_greeting:"),
+            greeting
+        );
         println!(
             "{} {}",
-            "day_start_hour:".dimmed(),
+            paint(ThemeElement::Muted, "day_start_hour:"),
             state.profile.day_start_hour
         );
         println!(
             "{} {:?}",
-            "greeting_style:".dimmed(),
+            paint(ThemeElement::Muted, "greeting_style:"),
             state.profile.greeting_style
         );
         println!(
             "{} {}",
-            "greeting_summary:".dimmed(),
+            paint(ThemeElement::Muted, "greeting_summary:"),
             if state.profile.greeting_summary {
                 "on"
             } else {
@@ -2242,27 +3395,74 @@ fn print_settings(state: &crate::model::AppState, color: bool) {
         );
         println!(
             "{} {:?}",
-            "summary_scope:".dimmed(),
+            paint(ThemeElement::Muted, "summary_scope:"),
             state.profile.summary_scope
         );
         println!(
             "{} {:?}",
-            "encouragement:".dimmed(),
+            paint(ThemeElement::Muted, "encouragement:"),
             state.profile.encouragement_mode
         );
-        println!("{} {:?}", "list_view:".dimmed(), state.profile.list_view);
-        println!("{} {}", "list_columns:".dimmed(), columns);
+        println!(
+            "{} {:?}",
+            paint(ThemeElement::Muted, "list_view:"),
+            state.profile.list_view
+        );
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "list_columns:"),
+            columns
+        );
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "column_layout:"),
+            column_layout
+        );
+        println!("{} {}", paint(ThemeElement::Muted, "wrap_rows:"), wrap_rows);
+        println!(
+            "{} {:?}",
+            paint(ThemeElement::Muted, "board_by:"),
+            state.profile.board_group
+        );
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "board_tags:"),
+            board_tags
+        );
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "list_format:"),
+            list_format
+        );
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "date_style:"),
+            date_style
+        );
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "date_format:"),
+            date_format
+        );
         println!(
             "{} {}",
-            "auto_pager:".dimmed(),
+            paint(ThemeElement::Muted, "auto_pager:"),
             if state.profile.auto_pager {
                 "on"
             } else {
                 "off"
             }
         );
-        println!("{} {}", "daily_message:".dimmed(), message);
-        println!("{} {}", "last_greeted:".dimmed(), greeted);
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "daily_message:"),
+            message
+        );
+        println!(
+            "{} {}",
+            paint(ThemeElement::Muted, "last_greeted:"),
+            greeted
+        );
     } else {
         println!("name:          {name}");
         println!("pronouns:      {pronouns}");
@@ -2281,6 +3481,13 @@ fn print_settings(state: &crate::model::AppState, color: bool) {
         println!("encouragement: {:?}", state.profile.encouragement_mode);
         println!("list_view:     {:?}", state.profile.list_view);
         println!("list_columns:  {columns}");
+        println!("column_layout: {column_layout}");
+        println!("wrap_rows:     {wrap_rows}");
+        println!("board_by:      {:?}", state.profile.board_group);
+        println!("board_tags:    {board_tags}");
+        println!("list_format:   {list_format}");
+        println!("date_style:    {date_style}");
+        println!("date_format:   {date_format}");
         println!(
             "auto_pager:    {}",
             if state.profile.auto_pager {
@@ -1,4 +1,6 @@
+use crate::json;
 use crate::model::{AppState, DEFAULT_BRANCH, Task, default_branch};
+use crate::output;
 
 pub fn normalize_branch(branch: Option<String>) -> Option<String> {
     branch.and_then(|b| {
@@ -29,6 +31,14 @@ pub fn print_branch_list(state: &AppState, tasks: &[Task]) {
     let mut branches = collect_branches(tasks);
     order_branches(&mut branches, &state.current_branch);
 
+    if output::mode().json {
+        json::emit(&json::Branches {
+            current: state.current_branch.clone(),
+            branches,
+        });
+        return;
+    }
+
     if branches.is_empty() {
         println!("Current branch: {}", state.current_branch);
         println!("Known branches: (none)");
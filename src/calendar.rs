@@ -0,0 +1,499 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::display::{
+    CellContext, build_meta_line, display_width, effective_columns, output_text, pad_right,
+    terminal_width, truncate_to_width,
+};
+use crate::json::{self, OccurrenceEntry};
+use crate::model::{AppState, DateView, ListColumn, Task, ThemeElement};
+use crate::output::{self, Glyph, status_glyph};
+use crate::theme::paint;
+use crate::util::{advance_due, format_date};
+
+/// Title lines shown per day cell in the month grid.
+const CALENDAR_TITLE_LINES: usize = 3;
+
+/// A task landing on a date, either its stored due date or a recurrence projected from it.
+pub(crate) struct Occurrence<'a> {
+    pub task: &'a Task,
+    pub date: NaiveDate,
+    /// Not stored; the task would only be recreated for this date after completing earlier ones.
+    pub projected: bool,
+}
+
+/// Dates in `from..=until` on which `task` is due, following `repeat` forward from the stored due date.
+pub(crate) fn occurrences_between(
+    task: &Task,
+    from: NaiveDate,
+    until: NaiveDate,
+) -> Vec<(NaiveDate, bool)> {
+    let mut out: Vec<(NaiveDate, bool)> = Vec::new();
+    let Some(due) = task.due else {
+        return out;
+    };
+    if (from..=until).contains(&due) {
+        out.push((due, false));
+    }
+    let Some(repeat) = task.repeat else {
+        return out;
+    };
+    if task.done {
+        return out;
+    }
+
+    let mut next = advance_due(due, repeat);
+    while let Some(date) = next {
+        if date > until {
+            break;
+        }
+        if date >= from {
+            out.push((date, true));
+        }
+        next = advance_due(date, repeat);
+    }
+    out
+}
+
+/// Every occurrence of the tasks in `from..=until`, by date.
+pub(crate) fn collect_occurrences<'a>(
+    view: &[&'a Task],
+    from: NaiveDate,
+    until: NaiveDate,
+) -> Vec<Occurrence<'a>> {
+    let mut out: Vec<Occurrence<'a>> = Vec::new();
+    for task in view {
+        for (date, projected) in occurrences_between(task, from, until) {
+            out.push(Occurrence {
+                task,
+                date,
+                projected,
+            });
+        }
+    }
+    out.sort_by_key(|o| (o.date, o.projected));
+    out
+}
+
+/// Parse a month argument: `YYYY-MM`, a month number (1-12) or a month name.
+/// Numbers and names refer to the current year. Returns the first day of the month.
+pub fn parse_month(value: Option<&str>, today: NaiveDate) -> Result<NaiveDate, String> {
+    let first = |year: i32, month: u32| {
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .ok_or_else(|| "month must be between 1 and 12".to_string())
+    };
+    let Some(value) = value.map(|v| v.trim().to_lowercase()) else {
+        return first(today.year(), today.month());
+    };
+
+    match value.as_str() {
+        "" | "this" => return first(today.year(), today.month()),
+        "next" => return Ok(first(today.year(), today.month())? + Months::new(1)),
+        "last" | "prev" => return Ok(first(today.year(), today.month())? - Months::new(1)),
+        _ => {}
+    }
+
+    if let Some((year, month)) = value.split_once('-')
+        && let (Ok(year), Ok(month)) = (year.parse::<i32>(), month.parse::<u32>())
+    {
+        return first(year, month);
+    }
+    if let Ok(month) = value.parse::<u32>() {
+        return first(today.year(), month);
+    }
+
+    const NAMES: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    if value.len() >= 3
+        && let Some(idx) = NAMES.iter().position(|name| name.starts_with(&value))
+    {
+        return first(today.year(), idx as u32 + 1);
+    }
+
+    Err("expected YYYY-MM, a month number (1-12) or a month name".to_string())
+}
+
+pub fn print_calendar(
+    view: &[&Task],
+    state: &AppState,
+    month_start: NaiveDate,
+    today: NaiveDate,
+    color: bool,
+) {
+    if output::mode().json {
+        let until = month_start + Months::new(1) - Days::new(1);
+        emit_occurrences(view, month_start, until, None);
+        return;
+    }
+    let lines = render_calendar(view, month_start, today, color);
+    output_text(&lines.join("\n"), state.profile.auto_pager, color);
+}
+
+fn render_calendar(
+    view: &[&Task],
+    month_start: NaiveDate,
+    today: NaiveDate,
+    color: bool,
+) -> Vec<String> {
+    let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
+    // Seven cells plus eight borders.
+    let cell = (term_width.saturating_sub(8) / 7).max(8);
+    let grid_width = cell * 7 + 8;
+    // One space of inner padding on the left of every cell.
+    let fit = |text: &str| pad_right(&format!(" {}", truncate_to_width(text, cell - 1)), cell);
+
+    let month_end = month_start + Months::new(1) - Days::new(1);
+    let occurrences = collect_occurrences(view, month_start, month_end);
+
+    let mut lines: Vec<String> = Vec::new();
+    let title = month_start.format("%B %Y").to_string();
+    let title = format!(
+        "{}{title}",
+        " ".repeat(grid_width.saturating_sub(display_width(&title)) / 2)
+    );
+    lines.push(if color {
+        paint(ThemeElement::Header, &title)
+    } else {
+        title
+    });
+
+    let border = |left: Glyph, mid: Glyph, right: Glyph| {
+        let line = format!(
+            "{}{}{}",
+            left.as_str(),
+            vec![Glyph::Rule.repeat(cell); 7].join(mid.as_str()),
+            right.as_str()
+        );
+        if color {
+            paint(ThemeElement::Separator, &line)
+        } else {
+            line
+        }
+    };
+    let bar = if color {
+        paint(ThemeElement::Separator, Glyph::Vertical.as_str())
+    } else {
+        Glyph::Vertical.as_str().to_string()
+    };
+
+    lines.push(border(Glyph::TopLeft, Glyph::TopMiddle, Glyph::TopRight));
+    let weekdays: Vec<String> = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+        .iter()
+        .map(|d| {
+            let d = fit(d);
+            if color {
+                paint(ThemeElement::Title, &d)
+            } else {
+                d
+            }
+        })
+        .collect();
+    lines.push(format!("{bar}{}{bar}", weekdays.join(&bar)));
+
+    let lead = month_start.weekday().num_days_from_monday() as u64;
+    let mut week_start = month_start - Days::new(lead);
+    while week_start <= month_end {
+        lines.push(border(Glyph::MiddleLeft, Glyph::Cross, Glyph::MiddleRight));
+
+        let mut rows: Vec<Vec<String>> = vec![Vec::new(); CALENDAR_TITLE_LINES + 1];
+        for offset in 0..7 {
+            let date = week_start + Days::new(offset);
+            let in_month = date.month() == month_start.month();
+            let day: Vec<&Occurrence> = if in_month {
+                occurrences.iter().filter(|o| o.date == date).collect()
+            } else {
+                Vec::new()
+            };
+
+            let label = if !in_month {
+                String::new()
+            } else if day.is_empty() {
+                date.day().to_string()
+            } else {
+                format!("{} ({})", date.day(), day.len())
+            };
+            let label = fit(&label);
+            rows[0].push(if !color {
+                label
+            } else if date == today {
+                paint(ThemeElement::Today, &label)
+            } else if date < today {
+                paint(ThemeElement::Muted, &label)
+            } else {
+                paint(ThemeElement::Title, &label)
+            });
+
+            for (line, row) in rows.iter_mut().skip(1).enumerate() {
+                let more = day.len() > CALENDAR_TITLE_LINES && line == CALENDAR_TITLE_LINES - 1;
+                let text = if more {
+                    format!("+{} more", day.len() - line)
+                } else {
+                    day.get(line)
+                        .map(|o| format!("{}{}", projection_marker(o), o.task.title))
+                        .unwrap_or_default()
+                };
+                let text = fit(&text);
+                row.push(match day.get(line) {
+                    Some(o) if color && !more => style_occurrence(&text, o, today),
+                    _ if color && more => paint(ThemeElement::Muted, &text),
+                    _ => text,
+                });
+            }
+        }
+        for row in rows {
+            lines.push(format!("{bar}{}{bar}", row.join(&bar)));
+        }
+
+        week_start = week_start + Days::new(7);
+    }
+    lines.push(border(
+        Glyph::BottomLeft,
+        Glyph::BottomMiddle,
+        Glyph::BottomRight,
+    ));
+    lines
+}
+
+pub fn print_agenda(view: &[&Task], state: &AppState, days: u32, today: NaiveDate, color: bool) {
+    if output::mode().json {
+        let until = today + Days::new(days.saturating_sub(1) as u64);
+        let overdue = view
+            .iter()
+            .copied()
+            .filter(|t| !t.done && t.due.is_some_and(|d| d < today))
+            .collect();
+        emit_occurrences(view, today, until, Some(overdue));
+        return;
+    }
+    let lines = render_agenda(view, state, days, today, color);
+    output_text(&lines.join("\n"), state.profile.auto_pager, color);
+}
+
+fn emit_occurrences(
+    view: &[&Task],
+    from: NaiveDate,
+    until: NaiveDate,
+    overdue: Option<Vec<&Task>>,
+) {
+    let occurrences = collect_occurrences(view, from, until);
+    json::emit(&json::Occurrences {
+        from,
+        until,
+        overdue,
+        occurrences: occurrences.iter().map(OccurrenceEntry::from).collect(),
+    });
+}
+
+fn render_agenda(
+    view: &[&Task],
+    state: &AppState,
+    days: u32,
+    today: NaiveDate,
+    color: bool,
+) -> Vec<String> {
+    let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
+    let last = today + Days::new(days.saturating_sub(1) as u64);
+    let ctx = CellContext::new(state, DateView::Agenda);
+    // The day header already says when a task is due; overdue tasks have no such header.
+    let overdue_cols = effective_columns(state);
+    let cols: Vec<ListColumn> = overdue_cols
+        .iter()
+        .copied()
+        .filter(|c| *c != ListColumn::Due)
+        .collect();
+
+    let mut lines: Vec<String> = Vec::new();
+    let header = |text: String| {
+        if color {
+            paint(ThemeElement::Header, &text)
+        } else {
+            text
+        }
+    };
+
+    let overdue: Vec<&Task> = view
+        .iter()
+        .copied()
+        .filter(|t| !t.done && t.due.is_some_and(|d| d < today))
+        .collect();
+    if !overdue.is_empty() {
+        lines.push(header(format!("Overdue ({})", overdue.len())));
+        for task in overdue {
+            let occurrence = Occurrence {
+                task,
+                date: task.due.unwrap_or(today),
+                projected: false,
+            };
+            lines.push(agenda_line(
+                &occurrence,
+                &overdue_cols,
+                &ctx,
+                term_width,
+                color,
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    let occurrences = collect_occurrences(view, today, last);
+    let mut date = today;
+    while date <= last {
+        let day: Vec<&Occurrence> = occurrences.iter().filter(|o| o.date == date).collect();
+        let relative = match (date - today).num_days() {
+            0 => format!(" {} today", Glyph::Separator.as_str()),
+            1 => format!(" {} tomorrow", Glyph::Separator.as_str()),
+            _ => String::new(),
+        };
+        lines.push(header(format!(
+            "{}{relative}",
+            day_label(date, ctx.date_format)
+        )));
+        if day.is_empty() {
+            let empty = "  (nothing due)";
+            lines.push(if color {
+                paint(ThemeElement::Muted, empty)
+            } else {
+                empty.to_string()
+            });
+        }
+        for occurrence in day {
+            lines.push(agenda_line(occurrence, &cols, &ctx, term_width, color));
+        }
+        if date < last {
+            lines.push(String::new());
+        }
+        date = date + Days::new(1);
+    }
+    lines
+}
+
+fn agenda_line(
+    occurrence: &Occurrence,
+    cols: &[ListColumn],
+    ctx: &CellContext,
+    term_width: usize,
+    color: bool,
+) -> String {
+    let task = occurrence.task;
+    let status = if occurrence.projected {
+        Glyph::Projection.as_str()
+    } else {
+        status_glyph(task.done)
+    };
+    let status = if !color {
+        status.to_string()
+    } else if occurrence.projected {
+        paint(ThemeElement::Projection, status)
+    } else if task.done {
+        paint(ThemeElement::StatusDone, status)
+    } else {
+        paint(ThemeElement::StatusOpen, status)
+    };
+    let id = format!("#{:>3}", task.id);
+    let id = if color {
+        paint(ThemeElement::Muted, &id)
+    } else {
+        id
+    };
+
+    let meta = build_meta_line(task, cols, ctx);
+    let title_width = term_width.saturating_sub(10);
+    let title = truncate_to_width(&task.title, title_width);
+    let title = if color {
+        style_occurrence(&title, occurrence, ctx.today)
+    } else {
+        title
+    };
+
+    if meta.is_empty() {
+        return format!("  {status} {id}  {title}");
+    }
+    let meta = truncate_to_width(&meta, term_width.saturating_sub(10));
+    let meta = if color {
+        paint(ThemeElement::Muted, &meta)
+    } else {
+        meta
+    };
+    format!("  {status} {id}  {title}  {meta}")
+}
+
+/// Marks recurrences that don't exist as stored tasks.
+fn projection_marker(occurrence: &Occurrence) -> String {
+    if occurrence.projected {
+        format!("{} ", Glyph::Projection.as_str())
+    } else {
+        String::new()
+    }
+}
+
+fn style_occurrence(text: &str, occurrence: &Occurrence, today: NaiveDate) -> String {
+    let element = if occurrence.projected {
+        ThemeElement::Projection
+    } else if occurrence.task.done {
+        ThemeElement::Done
+    } else if occurrence.date < today {
+        ThemeElement::Overdue
+    } else {
+        ThemeElement::Title
+    };
+    paint(element, text)
+}
+
+pub fn print_projections(
+    view: &[&Task],
+    state: &AppState,
+    until: NaiveDate,
+    today: NaiveDate,
+    color: bool,
+) {
+    let ctx = CellContext::new(state, DateView::Agenda);
+    let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
+    let occurrences: Vec<Occurrence> = collect_occurrences(view, today, until)
+        .into_iter()
+        .filter(|o| o.projected)
+        .collect();
+
+    let header = format!(
+        "Projected until {} ({})",
+        format_date(until, ctx.date_format),
+        occurrences.len()
+    );
+    println!(
+        "{}",
+        if color {
+            paint(ThemeElement::Header, &header)
+        } else {
+            header
+        }
+    );
+    if occurrences.is_empty() {
+        println!("  (no occurrences)");
+        return;
+    }
+    for occurrence in &occurrences {
+        let date = day_label(occurrence.date, ctx.date_format);
+        let date = if color {
+            paint(ThemeElement::Muted, &date)
+        } else {
+            date
+        };
+        let line = agenda_line(occurrence, &[ListColumn::Repeat], &ctx, term_width, color);
+        println!("  {date}{line}");
+    }
+}
+
+/// Weekday and date, e.g. `Fri 2026-10-23`.
+fn day_label(date: NaiveDate, format: &str) -> String {
+    format!("{} {}", date.format("%a"), format_date(date, format))
+}
@@ -1,14 +1,31 @@
 use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::model::{
-    EncouragementMode, GreetingStyle, IdScope, ListColumn, ListViewStyle, Priority, Repeat,
-    SortKey, SummaryScope,
+    BoardGroup, ColumnAlign, DateStyle, DateView, EncouragementMode, GreetingStyle, IdScope,
+    ListColumn, ListViewStyle, Priority, Repeat, SortSpec, SummaryScope, ThemeElement, ThemePreset,
 };
+use crate::theme::{parse_element_style, parse_named_style};
+use crate::urgency::parse_urgency_setting;
+use crate::util::{parse_column_align, parse_column_width, parse_delimiter, parse_view_date_style};
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum GroupBy {
-    /// Group by due date (day) in table view
+    /// Group by due date (day)
     DueDay,
+    /// Group by the week (Monday to Sunday) of the due date
+    DueWeek,
+    /// Group by the month of the due date
+    DueMonth,
+    /// Overdue, today, this week, later and someday
+    Relative,
+    /// Group by priority
+    Priority,
+    /// Group by tag; tasks with several tags show under each
+    Tag,
+    /// Group by branch
+    Branch,
+    /// Open and completed tasks
+    Status,
 }
 
 #[derive(Parser)]
@@ -18,6 +35,18 @@ pub struct Cli {
     #[arg(long = "no-color")]
     pub no_color: bool,
 
+    /// Draw with plain ASCII instead of symbols and box-drawing characters
+    #[arg(long = "ascii")]
+    pub ascii: bool,
+
+    /// Screen-reader friendly output: one sentence per task, no color or decoration
+    #[arg(long = "accessible")]
+    pub accessible: bool,
+
+    /// Print one JSON document per command instead of text
+    #[arg(long = "json")]
+    pub json: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -36,7 +65,7 @@ pub enum Commands {
     },
 
     /// Delete a branch
-    #[command(aliases = ["branch-delete", "br-del"])]
+    #[command(alias = "br-del")]
     BranchDelete {
         /// Branch name to delete
         name: String,
@@ -47,7 +76,7 @@ pub enum Commands {
     },
 
     /// Move all tasks from one branch to another
-    #[command(aliases = ["branch-move", "br-mv"])]
+    #[command(alias = "br-mv")]
     BranchMove { from: String, to: String },
 
     /// Duplicate tasks from one branch to another
@@ -55,7 +84,7 @@ pub enum Commands {
     BranchDuplicate { from: String, to: String },
 
     /// Merge a branch into another
-    #[command(aliases = ["branch-merge", "br-merge"])]
+    #[command(alias = "br-merge")]
     BranchMerge { from: String, to: String },
 
     /// Create a new task
@@ -89,10 +118,40 @@ pub enum Commands {
         branch: Option<String>,
     },
 
+    /// Create a task, reading due date, priority, tags, branch and repeat from the text
+    ///
+    /// Example:
+    ///   todo quick Call dentist tomorrow #health !high +personal every month
+    #[command(alias = "q")]
+    Quick {
+        /// Task text with quick-add tokens (prefix a token with \ to keep it literal)
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+
+        /// Optional task content
+        #[arg(short = 'c', long = "content")]
+        content: Option<String>,
+    },
+
     /// Edit an existing task (interactive if no fields are provided)
     #[command(alias = "e")]
     Edit {
-        id: u64,
+        /// Task id or unique uid prefix
+        #[arg(required_unless_present = "query")]
+        id: Option<String>,
+
+        /// Open the task(s) in $VISUAL/$EDITOR as a frontmatter document
+        #[arg(long = "editor")]
+        editor: bool,
+
+        /// Edit all tasks in the current branch matching this text (requires --editor)
+        #[arg(
+            short = 'q',
+            long = "query",
+            requires = "editor",
+            conflicts_with = "id"
+        )]
+        query: Option<String>,
 
         /// New title
         #[arg(long = "title")]
@@ -166,9 +225,9 @@ pub enum Commands {
         #[arg(long = "tag")]
         tags: Vec<String>,
 
-        /// Sort by: due, priority, created, id
-        #[arg(short = 's', long = "sort", value_enum)]
-        sort: Option<SortKey>,
+        /// Sort spec, e.g. priority,-due,title (keys: due, priority, urgency, created, updated, completed, id, title, branch, status)
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<SortSpec>,
 
         /// Sort descending
         #[arg(long = "desc")]
@@ -178,9 +237,25 @@ pub enum Commands {
         #[arg(long = "asc")]
         asc: bool,
 
-        /// Group output (table view)
+        /// Group output
         #[arg(long = "group-by", value_enum)]
         group_by: Option<GroupBy>,
+
+        /// View style for this listing (overrides the list-view setting)
+        #[arg(long = "view", value_enum)]
+        view: Option<ListViewStyle>,
+
+        /// Board columns: status, priority, branch or tag (board view)
+        #[arg(long = "board-by", value_enum)]
+        board_by: Option<BoardGroup>,
+
+        /// Tag shown as a board column when grouping by tag (can repeat)
+        #[arg(long = "board-tag")]
+        board_tags: Vec<String>,
+
+        /// Print one line per task from a template, e.g. '{id} {title} ({due:relative})'
+        #[arg(long = "format", value_name = "TEMPLATE")]
+        format: Option<String>,
     },
 
     /// Show tasks split into “has due date” and “no due date” sections
@@ -202,9 +277,9 @@ pub enum Commands {
         #[arg(long = "tag")]
         tags: Vec<String>,
 
-        /// Sort by: due, priority, created, id
-        #[arg(short = 's', long = "sort", value_enum)]
-        sort: Option<SortKey>,
+        /// Sort spec, e.g. priority,-due,title (keys: due, priority, urgency, created, updated, completed, id, title, branch, status)
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<SortSpec>,
 
         /// Sort descending
         #[arg(long = "desc")]
@@ -216,7 +291,7 @@ pub enum Commands {
     },
 
     /// List tasks grouped by branch
-    #[command(aliases = ["la", "list-all"])]
+    #[command(alias = "la")]
     ListAll {
         /// Include completed tasks
         #[arg(short = 'a', long = "all")]
@@ -226,9 +301,9 @@ pub enum Commands {
         #[arg(long = "archived")]
         archived: bool,
 
-        /// Sort by: due, priority, created, id
-        #[arg(short = 's', long = "sort", value_enum)]
-        sort: Option<SortKey>,
+        /// Sort spec, e.g. priority,-due,title (keys: due, priority, urgency, created, updated, completed, id, title, branch, status)
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<SortSpec>,
 
         /// Sort descending
         #[arg(long = "desc")]
@@ -242,13 +317,13 @@ pub enum Commands {
         #[arg(long = "asc")]
         asc: bool,
 
-        /// Group output (table view)
+        /// Group output
         #[arg(long = "group-by", value_enum)]
         group_by: Option<GroupBy>,
     },
 
     /// List repeating tasks
-    #[command(aliases = ["lr", "list-repeat"])]
+    #[command(alias = "lr")]
     ListRepeat {
         /// Include completed tasks
         #[arg(short = 'a', long = "all")]
@@ -266,9 +341,9 @@ pub enum Commands {
         #[arg(long = "tag")]
         tags: Vec<String>,
 
-        /// Sort by: due, priority, created, id
-        #[arg(short = 's', long = "sort", value_enum)]
-        sort: Option<SortKey>,
+        /// Sort spec, e.g. priority,-due,title (keys: due, priority, urgency, created, updated, completed, id, title, branch, status)
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<SortSpec>,
 
         /// Sort descending
         #[arg(long = "desc")]
@@ -278,17 +353,21 @@ pub enum Commands {
         #[arg(long = "asc")]
         asc: bool,
 
-        /// Group output (table view)
+        /// Group output
         #[arg(long = "group-by", value_enum)]
         group_by: Option<GroupBy>,
+
+        /// Also show the occurrences coming up within a span, e.g. 30d, 6w or 3m
+        #[arg(long = "project", value_name = "SPAN")]
+        project: Option<String>,
     },
 
     /// List only completed tasks
     #[command(aliases = ["ld", "done-list"])]
     ListDone {
-        /// Sort by: due, priority, created, id
-        #[arg(short = 's', long = "sort", value_enum)]
-        sort: Option<SortKey>,
+        /// Sort spec, e.g. priority,-due,title (keys: due, priority, urgency, created, updated, completed, id, title, branch, status)
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<SortSpec>,
 
         /// Sort descending
         #[arg(long = "desc")]
@@ -310,7 +389,7 @@ pub enum Commands {
         #[arg(long = "tag")]
         tags: Vec<String>,
 
-        /// Group output (table view)
+        /// Group output
         #[arg(long = "group-by", value_enum)]
         group_by: Option<GroupBy>,
     },
@@ -328,9 +407,9 @@ pub enum Commands {
         #[arg(long = "archived")]
         archived: bool,
 
-        /// Sort by: due, priority, created, id
-        #[arg(short = 's', long = "sort", value_enum)]
-        sort: Option<SortKey>,
+        /// Sort spec, e.g. priority,-due,title (keys: due, priority, urgency, created, updated, completed, id, title, branch, status)
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<SortSpec>,
 
         /// Sort descending
         #[arg(long = "desc")]
@@ -348,7 +427,7 @@ pub enum Commands {
         #[arg(long = "tag")]
         tags: Vec<String>,
 
-        /// Group output (table view)
+        /// Group output
         #[arg(long = "group-by", value_enum)]
         group_by: Option<GroupBy>,
     },
@@ -365,32 +444,104 @@ pub enum Commands {
         tags: Vec<String>,
     },
 
+    /// Show a month calendar of due tasks
+    #[command(alias = "cal")]
+    Calendar {
+        /// Month to show: YYYY-MM, a month number or name, next or last (default: this month)
+        month: Option<String>,
+
+        /// Include completed tasks
+        #[arg(short = 'a', long = "all")]
+        all: bool,
+
+        /// Filter by branch
+        #[arg(short = 'b', long = "branch")]
+        branch: Option<String>,
+
+        /// Filter by tag (can repeat)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+
+    /// Show overdue tasks and the coming days one by one
+    #[command(alias = "ag")]
+    Agenda {
+        /// Number of days to show, starting today (1 to 366)
+        #[arg(
+            short = 'd',
+            long = "days",
+            default_value_t = 7,
+            value_parser = clap::value_parser!(u32).range(1..=366)
+        )]
+        days: u32,
+
+        /// Filter by branch
+        #[arg(short = 'b', long = "branch")]
+        branch: Option<String>,
+
+        /// Filter by tag (can repeat)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+
+    /// Show the most urgent open tasks
+    #[command(alias = "nx")]
+    Next {
+        /// Number of tasks to show
+        #[arg(short = 'n', long = "limit", default_value_t = 5)]
+        limit: usize,
+
+        /// Filter by branch
+        #[arg(short = 'b', long = "branch")]
+        branch: Option<String>,
+
+        /// Filter by tag (can repeat)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+
     /// Show stats across tasks
-    #[command(aliases = ["st", "stats"])]
+    #[command(alias = "st")]
     Stats,
 
     /// Mark matching tasks as done
-    #[command(aliases = ["bd", "bulk-done"])]
+    #[command(alias = "bd")]
     BulkDone {
         query: String,
 
         /// Filter by branch
         #[arg(short = 'b', long = "branch")]
         branch: Option<String>,
+
+        /// Show matching tasks without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
     },
 
     /// Mark matching tasks as not done
-    #[command(aliases = ["bu", "bulk-undone"])]
+    #[command(alias = "bu")]
     BulkUndone {
         query: String,
 
         /// Filter by branch
         #[arg(short = 'b', long = "branch")]
         branch: Option<String>,
+
+        /// Show matching tasks without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
     },
 
     /// Edit matching tasks in bulk
-    #[command(aliases = ["be", "bulk-edit"])]
+    #[command(alias = "be")]
     BulkEdit {
         query: String,
 
@@ -433,20 +584,36 @@ pub enum Commands {
         /// Clear all tags
         #[arg(long = "clear-tags")]
         clear_tags: bool,
+
+        /// Show matching tasks without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
     },
 
     /// Delete matching tasks
-    #[command(aliases = ["bx", "bulk-delete"])]
+    #[command(alias = "bx")]
     BulkDelete {
         query: String,
 
         /// Filter by branch
         #[arg(short = 'b', long = "branch")]
         branch: Option<String>,
+
+        /// Show matching tasks without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
     },
 
     /// Move matching tasks to another branch
-    #[command(aliases = ["bm", "bulk-move"])]
+    #[command(alias = "bm")]
     BulkMove {
         query: String,
 
@@ -456,13 +623,21 @@ pub enum Commands {
         /// Filter by branch
         #[arg(short = 'b', long = "branch")]
         branch: Option<String>,
+
+        /// Show matching tasks without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
     },
 
     /// Archive done tasks
     #[command(aliases = ["arc", "archive-done"])]
     Archive {
-        /// Archive a single task by id
-        id: Option<u64>,
+        /// Archive a single task by id or uid prefix
+        id: Option<String>,
 
         /// Archive done tasks in all branches
         #[arg(long = "all-branches")]
@@ -474,24 +649,39 @@ pub enum Commands {
     },
 
     /// Unarchive a task by id
-    #[command(aliases = ["unarc", "unarchive"])]
-    Unarchive { id: u64 },
+    #[command(alias = "unarc")]
+    Unarchive {
+        /// Task id or unique uid prefix
+        id: String,
+    },
 
     /// Mark a task as done by id
     #[command(alias = "d")]
-    Done { id: u64 },
+    Done {
+        /// Task id or unique uid prefix
+        id: String,
+    },
 
     /// Mark a task as not done by id
     #[command(alias = "u")]
-    Undone { id: u64 },
+    Undone {
+        /// Task id or unique uid prefix
+        id: String,
+    },
 
     /// Toggle a task's done status by id
     #[command(alias = "t")]
-    Toggle { id: u64 },
+    Toggle {
+        /// Task id or unique uid prefix
+        id: String,
+    },
 
     /// Delete specified task by id
     #[command(aliases = ["x", "rm", "del"])]
-    Delete { id: u64 },
+    Delete {
+        /// Task id or unique uid prefix
+        id: String,
+    },
 
     /// Clear all completed tasks
     #[command(alias = "clr")]
@@ -499,7 +689,14 @@ pub enum Commands {
 
     /// View a specific task by id
     #[command(alias = "v")]
-    View { id: u64 },
+    View {
+        /// Task id or unique uid prefix
+        id: String,
+
+        /// Print the task with a template instead, e.g. '{title}{?due} due {due:relative}{/}'
+        #[arg(long = "format", value_name = "TEMPLATE")]
+        format: Option<String>,
+    },
 
     /// Export tasks
     #[command(aliases = ["ex", "out"])]
@@ -523,6 +720,22 @@ pub enum Commands {
         /// Filter by tag (can repeat)
         #[arg(long = "tag")]
         tags: Vec<String>,
+
+        /// CSV columns in order, e.g. --columns title,due,tags (default: all; must include title)
+        #[arg(long = "columns", value_enum, value_delimiter = ',')]
+        columns: Vec<CsvColumn>,
+
+        /// CSV field delimiter: one character, or `tab` [default: ,]
+        #[arg(long = "delimiter", value_name = "CHAR", value_parser = parse_delimiter)]
+        delimiter: Option<u8>,
+
+        /// Leave out the CSV header row (read it back with `import csv --no-header` and the same --columns)
+        #[arg(long = "no-header")]
+        no_header: bool,
+
+        /// When to quote CSV fields [default: necessary]
+        #[arg(long = "quote", value_enum)]
+        quote: Option<CsvQuote>,
     },
 
     /// Import tasks from a file
@@ -539,6 +752,34 @@ pub enum Commands {
         /// Override branch for imported tasks
         #[arg(short = 'b', long = "branch")]
         branch: Option<String>,
+
+        /// CSV field delimiter: one character, or `tab`
+        #[arg(long = "delimiter", value_name = "CHAR", default_value = ",", value_parser = parse_delimiter)]
+        delimiter: u8,
+
+        /// The CSV file has no header row (as written by `export csv --no-header`)
+        #[arg(long = "no-header")]
+        no_header: bool,
+
+        /// Columns of a CSV file read with --no-header, in order (default: all, in export order)
+        #[arg(long = "columns", value_enum, value_delimiter = ',')]
+        columns: Vec<CsvColumn>,
+
+        /// Also import VEVENTs from an ics file; their start date becomes the due date
+        #[arg(long = "events")]
+        events: bool,
+
+        /// What markdown headings become
+        #[arg(long = "headings", value_enum, default_value = "tag")]
+        headings: MarkdownHeadings,
+
+        /// What markdown checklist items nested under another become
+        #[arg(long = "nested", value_enum, default_value = "content")]
+        nested: MarkdownNested,
+
+        /// Show the tasks that would be imported without saving them
+        #[arg(long = "dry-run")]
+        dry_run: bool,
     },
 
     /// Manage saved commands (custom aliases)
@@ -549,7 +790,7 @@ pub enum Commands {
     },
 
     /// Show or update personal settings
-    #[command(aliases = ["set", "settings", "profile", "me"])]
+    #[command(aliases = ["set", "profile", "me"])]
     Settings {
         /// Your name (used for greetings)
         #[arg(long = "name")]
@@ -611,21 +852,81 @@ pub enum Commands {
         #[arg(long = "columns-default")]
         columns_default: bool,
 
+        /// Table column width, e.g. tags=24; `<column>=default` restores it (repeatable)
+        #[arg(long = "column-width", value_name = "COLUMN=WIDTH", value_parser = parse_column_width)]
+        column_widths: Vec<(ListColumn, Option<usize>)>,
+
+        /// Table column alignment: left, right or center; `<column>=default` restores it (repeatable)
+        #[arg(long = "column-align", value_name = "COLUMN=ALIGN", value_parser = parse_column_align)]
+        column_aligns: Vec<(ListColumn, Option<ColumnAlign>)>,
+
+        /// Let a column grow with the title into leftover width (repeatable)
+        #[arg(long = "column-flex", value_enum, value_name = "COLUMN")]
+        column_flex: Vec<ListColumn>,
+
+        /// Give a flexible column its fixed width back (repeatable)
+        #[arg(long = "column-fixed", value_enum, value_name = "COLUMN")]
+        column_fixed: Vec<ListColumn>,
+
+        /// Drop all column width, alignment and flex settings
+        #[arg(long = "column-layout-default")]
+        column_layout_default: bool,
+
+        /// Wrap long table cells onto more lines instead of truncating them (true/false)
+        #[arg(long = "wrap-rows")]
+        wrap_rows: Option<bool>,
+
         /// Enable/disable auto pager for long list output (true/false)
         #[arg(long = "auto-pager")]
         auto_pager: Option<bool>,
 
+        /// What board view columns represent
+        #[arg(long = "board-by", value_enum)]
+        board_by: Option<BoardGroup>,
+
+        /// Tags used as board columns when grouping by tag (repeatable). If provided, replaces current selection.
+        #[arg(long = "board-tag")]
+        board_tags: Vec<String>,
+
+        /// Use every tag in view as a board column
+        #[arg(long = "board-tags-all")]
+        board_tags_all: bool,
+
+        /// Template `list` uses unless --view or --format is given
+        #[arg(long = "list-format", value_name = "TEMPLATE")]
+        list_format: Option<String>,
+
+        /// Go back to the list view style
+        #[arg(long = "clear-list-format")]
+        clear_list_format: bool,
+
+        /// How dates are written in every view without its own style
+        #[arg(long = "date-style", value_enum)]
+        date_style: Option<DateStyle>,
+
+        /// Date style for one view, e.g. cards=relative; `<view>=default` follows --date-style (repeatable)
+        #[arg(long = "date-style-for", value_name = "VIEW=STYLE", value_parser = parse_view_date_style)]
+        date_styles: Vec<(DateView, Option<DateStyle>)>,
+
+        /// strftime format for absolute dates, e.g. %d.%m.%Y
+        #[arg(long = "date-format", value_name = "FORMAT")]
+        date_format: Option<String>,
+
+        /// Go back to ISO dates (%Y-%m-%d)
+        #[arg(long = "date-format-default")]
+        date_format_default: bool,
+
         /// Forget that you've been greeted today (forces greeting next run)
         #[arg(long = "reset-greeting")]
         reset_greeting: bool,
     },
 
     /// Show or update config defaults
-    #[command(aliases = ["cfg", "config"])]
+    #[command(alias = "cfg")]
     Config {
         /// Default sort key
-        #[arg(long = "default-sort", value_enum)]
-        default_sort: Option<SortKey>,
+        #[arg(long = "default-sort", allow_hyphen_values = true)]
+        default_sort: Option<SortSpec>,
 
         /// Default descending sort
         #[arg(long = "default-desc")]
@@ -646,10 +947,69 @@ pub enum Commands {
         /// Generate UUIDs for tasks
         #[arg(long = "use-uuid")]
         use_uuid: Option<bool>,
+
+        /// Parse quick-add tokens (#tag, !high, +branch, tomorrow, every week) in `create` titles
+        #[arg(long = "quick-add")]
+        quick_add: Option<bool>,
+
+        /// Ask for confirmation when a bulk command would change more than this many tasks
+        #[arg(long = "bulk-confirm-threshold")]
+        bulk_confirm_threshold: Option<usize>,
+
+        /// Set an urgency weight (can repeat): due, overdue, priority-high, priority-medium,
+        /// priority-low, age, tags, blocked, repeat or tag.<name> (0 removes a tag weight)
+        #[arg(
+            long = "urgency",
+            value_name = "NAME=WEIGHT",
+            value_parser = parse_urgency_setting,
+            allow_hyphen_values = true
+        )]
+        urgency: Vec<(String, f64)>,
+
+        /// Restore the default urgency weights (applied before --urgency)
+        #[arg(long = "urgency-reset")]
+        urgency_reset: bool,
+
+        /// Color theme preset
+        #[arg(long = "theme", value_enum)]
+        theme: Option<ThemePreset>,
+
+        /// Style one element on top of the preset (can repeat), e.g. overdue="bold magenta";
+        /// `default` goes back to the preset
+        #[arg(long = "theme-style", value_name = "ELEMENT=STYLE", value_parser = parse_element_style)]
+        theme_styles: Vec<(ThemeElement, String)>,
+
+        /// Color a tag (can repeat), e.g. work=cyan; `default` removes it
+        #[arg(long = "tag-color", value_name = "TAG=STYLE", value_parser = parse_named_style)]
+        tag_colors: Vec<(String, String)>,
+
+        /// Color a branch (can repeat), e.g. work="bold blue"; `default` removes it
+        #[arg(long = "branch-color", value_name = "BRANCH=STYLE", value_parser = parse_named_style)]
+        branch_colors: Vec<(String, String)>,
+
+        /// Go back to the dark preset and drop all custom styles and colors (applied first)
+        #[arg(long = "theme-reset")]
+        theme_reset: bool,
+    },
+
+    /// Preview the active color theme
+    Theme,
+
+    /// Start an interactive shell with history and tab completion
+    #[command(alias = "sh")]
+    Shell,
+
+    /// Open the full-screen terminal UI
+    Tui,
+
+    /// Manage task ids
+    Ids {
+        #[command(subcommand)]
+        command: IdsCommands,
     },
 
     /// Generate shell completions
-    #[command(aliases = ["comp", "completion", "completions"])]
+    #[command(aliases = ["comp", "completion"])]
     Completions {
         #[arg(value_enum)]
         shell: clap_complete::Shell,
@@ -681,16 +1041,94 @@ pub enum SavedCommands {
     Remove { name: String },
 }
 
-#[derive(Copy, Clone, Debug, ValueEnum)]
+#[derive(Subcommand)]
+pub enum IdsCommands {
+    /// Compact ids (per branch when id-scope is branch); uids stay unchanged
+    Renumber,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum ExportFormat {
     #[value(alias = "raw")]
     Text,
     Json,
     Markdown,
+    Csv,
+    /// iCalendar VTODOs
+    Ics,
+    #[value(alias = "todo.txt")]
+    Todotxt,
+    /// `task export` JSON
+    #[value(alias = "tw")]
+    Taskwarrior,
+}
+
+/// A CSV export column; the names are the header `import csv` reads.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CsvColumn {
+    /// Not imported; new ids are assigned
+    Id,
+    Uid,
+    Title,
+    Content,
+    /// Comma-separated
+    Tags,
+    Due,
+    Priority,
+    Repeat,
+    Branch,
+    Done,
+    Archived,
+    #[value(name = "created_at", alias = "created-at")]
+    CreatedAt,
+    #[value(name = "updated_at", alias = "updated-at")]
+    UpdatedAt,
+    #[value(name = "completed_at", alias = "completed-at")]
+    CompletedAt,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum CsvQuote {
+    /// Only fields containing the delimiter, quotes or line breaks
+    Necessary,
+    Always,
+    /// Never quote; fields with delimiters or line breaks won't re-import
+    Never,
+    /// Every field that isn't a number
+    NonNumeric,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum ImportFormat {
     Json,
     Csv,
+    /// iCalendar VTODOs (and VEVENTs with --events)
+    Ics,
+    #[value(alias = "todo.txt")]
+    Todotxt,
+    /// `task export` JSON
+    #[value(alias = "tw")]
+    Taskwarrior,
+    /// GitHub-style checklists, including `export markdown` output
+    #[value(alias = "md")]
+    Markdown,
+}
+
+/// What `import markdown` makes of the headings above a checklist.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MarkdownHeadings {
+    /// The nearest heading is the branch
+    Branch,
+    /// Every enclosing heading is a tag
+    Tag,
+    Ignore,
+}
+
+/// What `import markdown` makes of checklist items nested under another.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MarkdownNested {
+    /// Lines in the parent task's content
+    Content,
+    /// Tasks of their own, noting the parent in their content
+    Tasks,
 }
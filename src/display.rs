@@ -2,18 +2,72 @@ use std::cmp::Ordering;
 use std::io::{IsTerminal, Write};
 use std::process::{Command, Stdio};
 
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
 use crate::branch::{collect_branches, is_default_branch, order_branches};
-use crate::model::{AppState, ListColumn, ListViewStyle, Priority, SortKey, Task};
+use crate::cli::GroupBy;
+use crate::group::group_tasks;
+use crate::model::{
+    AppState, BoardGroup, ColumnAlign, DateStyle, DateView, ISO_DATE_FORMAT, ListColumn,
+    ListViewStyle, Priority, SortSpec, Task, ThemeElement, UrgencyCoefficients,
+};
+use crate::output::{self, Glyph, status_glyph};
 use crate::sort::sort_tasks;
-use owo_colors::OwoColorize;
+use crate::template::{RenderContext, Template};
+use crate::theme::{paint, paint_branch, paint_tag_list};
+use crate::urgency::urgency;
+use crate::util::{
+    format_date, format_due_date, format_relative_date, format_short_span, parse_timestamp,
+};
+
+/// What rendering a cell needs besides the task: today, urgency weights and how to write dates.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CellContext<'a> {
+    pub today: chrono::NaiveDate,
+    pub urgency: &'a UrgencyCoefficients,
+    pub date_style: DateStyle,
+    pub date_format: &'a str,
+}
+
+impl<'a> CellContext<'a> {
+    /// Context for `view` with the user's date settings.
+    pub fn new(state: &'a AppState, view: DateView) -> Self {
+        Self {
+            today: chrono::Local::now().date_naive(),
+            urgency: &state.config.urgency,
+            date_style: state.profile.date_style_for(view),
+            date_format: state.profile.date_format(),
+        }
+    }
+
+    /// Context with ISO dates, for output that other tools read back.
+    pub fn iso(urgency: &'a UrgencyCoefficients) -> Self {
+        Self {
+            today: chrono::Local::now().date_naive(),
+            urgency,
+            date_style: DateStyle::Absolute,
+            date_format: ISO_DATE_FORMAT,
+        }
+    }
+
+    pub fn due_text(&self, due: chrono::NaiveDate, done: bool) -> String {
+        format_due_date(due, done, self.date_style, self.date_format, self.today)
+    }
 
-pub fn print_task_list(view: &[&Task], state: &AppState, color: bool, group_by_day: bool) {
+    /// A past date such as a creation date, never marked overdue.
+    pub fn date_text(&self, date: chrono::NaiveDate) -> String {
+        format_due_date(date, true, self.date_style, self.date_format, self.today)
+    }
+}
+
+pub fn print_task_list(view: &[&Task], state: &AppState, color: bool, group_by: Option<GroupBy>) {
     if view.is_empty() {
         println!("No tasks.");
         return;
     }
 
-    let text = render_task_list(view, state, color, group_by_day);
+    let text = render_task_list(view, state, color, group_by);
     output_text(&text, state.profile.auto_pager, color);
 }
 
@@ -31,10 +85,10 @@ pub fn print_task_list_grouped(
     tasks: &[Task],
     state: &AppState,
     all: bool,
-    sort: SortKey,
+    sort: &SortSpec,
     desc: bool,
     color: bool,
-    group_by_day: bool,
+    group_by: Option<GroupBy>,
 ) {
     let mut branches = collect_branches(tasks);
     if branches.is_empty() {
@@ -56,10 +110,14 @@ pub fn print_task_list_grouped(
             continue;
         }
 
-        sort_tasks(&mut view, sort, desc);
+        sort_tasks(&mut view, sort, desc, &state.config.urgency);
         lines.push("".to_string());
-        lines.push(format_header(&branch, color));
-        lines.extend(render_task_list_lines(&view, state, color, group_by_day));
+        lines.push(if accessible_view(state) {
+            format!("Branch {branch}:")
+        } else {
+            format_header(&branch, color)
+        });
+        lines.extend(render_task_list_lines(&view, state, color, group_by));
     }
 
     if lines.iter().all(|l| l.trim().is_empty()) {
@@ -71,31 +129,75 @@ pub fn print_task_list_grouped(
     output_text(&text, state.profile.auto_pager, color);
 }
 
+/// Print one line per task rendered with `template`, for scripts and status bars.
+/// Nothing is printed for an empty view; group labels get a line of their own.
+pub fn print_task_list_templated(
+    view: &[&Task],
+    template: &Template,
+    state: &AppState,
+    group_by: Option<GroupBy>,
+) {
+    let today = chrono::Local::now().date_naive();
+    let ctx = RenderContext {
+        today,
+        urgency: &state.config.urgency,
+    };
+    let groups = match group_by {
+        Some(group_by) => group_tasks(view, group_by, &state.current_branch, today),
+        None => vec![(String::new(), view.to_vec())],
+    };
+    let accessible = accessible_view(state);
+    for (label, tasks) in groups {
+        if group_by.is_some() {
+            if accessible {
+                println!("{}", group_sentence(&label, tasks.len()));
+            } else {
+                println!("{label} ({})", tasks.len());
+            }
+        }
+        for task in tasks {
+            println!("{}", template.render(task, &ctx));
+        }
+    }
+}
+
 pub fn task_lines_plain(task: &Task) -> Vec<String> {
-    task_lines(task, false)
+    let urgency = UrgencyCoefficients::default();
+    task_lines(task, &CellContext::iso(&urgency), false)
 }
 
-fn render_task_list(view: &[&Task], state: &AppState, color: bool, group_by_day: bool) -> String {
-    render_task_list_lines(view, state, color, group_by_day).join("\n")
+fn render_task_list(
+    view: &[&Task],
+    state: &AppState,
+    color: bool,
+    group_by: Option<GroupBy>,
+) -> String {
+    render_task_list_lines(view, state, color, group_by).join("\n")
 }
 
 fn render_task_list_lines(
     view: &[&Task],
     state: &AppState,
     color: bool,
-    group_by_day: bool,
+    group_by: Option<GroupBy>,
 ) -> Vec<String> {
+    match group_by {
+        Some(group_by) => render_grouped(view, state, color, group_by),
+        None => render_view_lines(view, state, color),
+    }
+}
+
+fn render_view_lines(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
+    if accessible_view(state) {
+        return render_accessible(view, state);
+    }
     match state.profile.list_view {
-        ListViewStyle::Table => {
-            if group_by_day {
-                render_table_grouped_by_due_day(view, state, color)
-            } else {
-                render_table(view, state, color)
-            }
-        }
+        ListViewStyle::Table => render_table(view, state, color),
         ListViewStyle::Compact => render_compact(view, state, color),
         ListViewStyle::Cards => render_cards(view, state, color),
-        ListViewStyle::Classic => render_classic(view, color),
+        ListViewStyle::Classic => render_classic(view, state, color),
+        ListViewStyle::Board => render_board(view, state, color),
+        ListViewStyle::Accessible => render_accessible(view, state),
     }
 }
 
@@ -106,30 +208,33 @@ fn render_task_list_due_split(
     color: bool,
 ) -> Vec<String> {
     match state.profile.list_view {
-        ListViewStyle::Table => render_table_grouped_by_due_presence(due, no_due, state, color),
+        ListViewStyle::Table if !accessible_view(state) => {
+            render_table_grouped_by_due_presence(due, no_due, state, color)
+        }
         _ => {
             let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
             let mut lines: Vec<String> = Vec::new();
 
+            let accessible = accessible_view(state);
             if !due.is_empty() {
-                lines.push(format_label_group_header(
-                    "== Due date ==",
-                    term_width,
-                    color,
-                ));
-                lines.extend(render_task_list_lines(due, state, color, false));
+                lines.push(if accessible {
+                    group_sentence("With a due date", due.len())
+                } else {
+                    format_label_group_header("== Due date ==", term_width, color)
+                });
+                lines.extend(render_view_lines(due, state, color));
             }
 
             if !no_due.is_empty() {
                 if !lines.is_empty() {
                     lines.push(String::new());
                 }
-                lines.push(format_label_group_header(
-                    "== No due date ==",
-                    term_width,
-                    color,
-                ));
-                lines.extend(render_task_list_lines(no_due, state, color, false));
+                lines.push(if accessible {
+                    group_sentence("Without a due date", no_due.len())
+                } else {
+                    format_label_group_header("== No due date ==", term_width, color)
+                });
+                lines.extend(render_view_lines(no_due, state, color));
             }
 
             lines
@@ -178,108 +283,172 @@ fn render_table_grouped_by_due_presence(
     lines
 }
 
-fn render_table_grouped_by_due_day(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
+/// Render `view` in groups, each under a header with its task count.
+/// The table keeps a single column header above all groups.
+fn render_grouped(view: &[&Task], state: &AppState, color: bool, group_by: GroupBy) -> Vec<String> {
+    let today = chrono::Local::now().date_naive();
+    let groups = group_tasks(view, group_by, &state.current_branch, today);
+    let accessible = accessible_view(state);
+    let table = matches!(state.profile.list_view, ListViewStyle::Table) && !accessible;
     let layout = compute_table_layout(state);
-    let term_width = layout.term_width;
-
-    let mut open_dated: std::collections::BTreeMap<chrono::NaiveDate, Vec<&Task>> =
-        std::collections::BTreeMap::new();
-    let mut open_no_due: Vec<&Task> = Vec::new();
-    let mut done_dated: std::collections::BTreeMap<chrono::NaiveDate, Vec<&Task>> =
-        std::collections::BTreeMap::new();
-    let mut done_no_due: Vec<&Task> = Vec::new();
 
-    for task in view {
-        let (dated, no_due) = if task.done {
-            (&mut done_dated, &mut done_no_due)
+    let mut lines: Vec<String> = Vec::new();
+    if table {
+        lines.extend(build_table_header(
+            &layout.cols,
+            layout.title_width,
+            layout.term_width,
+            color,
+        ));
+    }
+    for (idx, (label, tasks)) in groups.iter().enumerate() {
+        if idx > 0 {
+            lines.push(String::new());
+        }
+        lines.push(if accessible {
+            group_sentence(label, tasks.len())
         } else {
-            (&mut open_dated, &mut open_no_due)
-        };
-
-        if let Some(d) = task.due {
-            dated.entry(d).or_default().push(*task);
+            format_label_group_header(
+                &format!("== {label} ({}) ==", tasks.len()),
+                layout.term_width,
+                color,
+            )
+        });
+        if table {
+            lines.extend(render_table_rows_only(tasks, &layout, color));
         } else {
-            no_due.push(*task);
+            lines.extend(render_view_lines(tasks, state, color));
         }
     }
+    lines
+}
 
-    let mut lines: Vec<String> = Vec::new();
-    lines.extend(build_table_header(
-        &layout.cols,
-        layout.title_width,
-        layout.term_width,
-        color,
-    ));
+/// Whether lists come out as sentences: `--accessible` or the accessible view style.
+fn accessible_view(state: &AppState) -> bool {
+    output::mode().accessible || matches!(state.profile.list_view, ListViewStyle::Accessible)
+}
 
-    let mut first_group = true;
-    for (date, tasks) in open_dated.into_iter() {
-        if !first_group {
-            lines.push(String::new());
+/// One sentence per task, e.g. "Task 12, Pay rent, high priority, due tomorrow, tags work."
+fn render_accessible(view: &[&Task], state: &AppState) -> Vec<String> {
+    // Always say what matters most, then whatever else the user shows as columns.
+    let mut cols = vec![
+        ListColumn::Priority,
+        ListColumn::Due,
+        ListColumn::Tags,
+        ListColumn::Branch,
+        ListColumn::Repeat,
+    ];
+    for c in effective_columns(state) {
+        if !cols.contains(&c) {
+            cols.push(c);
         }
-        first_group = false;
-        lines.push(format_date_group_header(Some(date), term_width, color));
-        lines.extend(render_table_rows_only(&tasks, &layout, color));
     }
-    if !open_no_due.is_empty() {
-        if !first_group {
-            lines.push(String::new());
+    // Sentences speak dates their own way; only today, the format and urgency weights are used.
+    let ctx = CellContext::new(state, DateView::Classic);
+    view.iter()
+        .map(|task| accessible_sentence(task, &cols, &ctx))
+        .collect()
+}
+
+fn accessible_sentence(task: &Task, cols: &[ListColumn], ctx: &CellContext) -> String {
+    let mut phrases: Vec<String> = vec![format!("Task {}", task.id), task.title.clone()];
+    if task.done {
+        phrases.push("done".to_string());
+    }
+    if task.archived {
+        phrases.push("archived".to_string());
+    }
+    let stamp = |label: &str, value: Option<&str>| {
+        value
+            .and_then(parse_timestamp)
+            .map(|t| format!("{label} {}", spoken_date(t.date_naive(), ctx)))
+    };
+    for c in cols {
+        let phrase = match c {
+            ListColumn::Due => task.due.map(|due| {
+                if due < ctx.today && !task.done {
+                    format!("overdue, due {}", spoken_date(due, ctx))
+                } else {
+                    format!("due {}", spoken_date(due, ctx))
+                }
+            }),
+            ListColumn::Priority => task
+                .priority
+                .map(|p| format!("{} priority", format!("{p:?}").to_lowercase())),
+            ListColumn::Branch => {
+                (!is_default_branch(&task.branch)).then(|| format!("branch {}", task.branch))
+            }
+            ListColumn::Tags => match task.tags.as_slice() {
+                [] => None,
+                [tag] => Some(format!("tag {tag}")),
+                tags => Some(format!("tags {}", join_words(tags))),
+            },
+            ListColumn::Repeat => task
+                .repeat
+                .map(|r| format!("repeats {}", format!("{r:?}").to_lowercase())),
+            ListColumn::Content => task
+                .content
+                .as_deref()
+                .map(|c| format!("note: {}", c.replace('\n', " "))),
+            ListColumn::Urgency => (!task.done)
+                .then(|| format!("urgency {:.1}", urgency(task, ctx.urgency, ctx.today))),
+            ListColumn::Created | ListColumn::Age => stamp("created", Some(&task.created_at)),
+            ListColumn::Updated => stamp("updated", task.updated_at.as_deref()),
+            ListColumn::Completed => stamp("completed", task.completed_at.as_deref()),
+            ListColumn::Uid => task.uid.as_deref().map(|uid| format!("UID {uid}")),
+            ListColumn::Status => (!task.done).then(|| "open".to_string()),
+            ListColumn::Id => None,
+        };
+        if let Some(phrase) = phrase
+            && !phrases.contains(&phrase)
+        {
+            phrases.push(phrase);
         }
-        first_group = false;
-        lines.push(format_date_group_header(None, term_width, color));
-        lines.extend(render_table_rows_only(&open_no_due, &layout, color));
     }
+    format!("{}.", phrases.join(", "))
+}
 
-    if !(done_dated.is_empty() && done_no_due.is_empty()) {
-        if !first_group {
-            lines.push(String::new());
-        }
+/// A date as it would be read out: `today`, `on Friday`, `3 days ago` or `on 2026-11-20`.
+fn spoken_date(date: chrono::NaiveDate, ctx: &CellContext) -> String {
+    match (date - ctx.today).num_days() {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        2..=6 => format!("on {}", date.format("%A")),
+        -6..=-2 => format_relative_date(date, ctx.today),
+        _ => format!("on {}", format_date(date, ctx.date_format)),
+    }
+}
 
-        let mut first_done_group = true;
-        for (date, tasks) in done_dated.into_iter() {
-            if !first_done_group {
-                lines.push(String::new());
-            }
-            first_done_group = false;
-            lines.push(format_date_group_header(Some(date), term_width, color));
-            lines.extend(render_table_rows_only(&tasks, &layout, color));
-        }
-        if !done_no_due.is_empty() {
-            if !first_done_group {
-                lines.push(String::new());
-            }
-            lines.push(format_date_group_header(None, term_width, color));
-            lines.extend(render_table_rows_only(&done_no_due, &layout, color));
-        }
+/// `a`, `a and b`, `a, b and c`.
+fn join_words(words: &[String]) -> String {
+    match words {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
     }
-    lines
 }
 
-fn format_date_group_header(
-    date: Option<chrono::NaiveDate>,
-    table_width: usize,
-    color: bool,
-) -> String {
-    let label = match date {
-        Some(d) => format!("== {} ==", d),
-        None => "== No due date ==".to_string(),
-    };
-    format_label_group_header(&label, table_width, color)
+fn group_sentence(label: &str, count: usize) -> String {
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{label}, {count} task{plural}:")
 }
 
 fn format_label_group_header(label: &str, table_width: usize, color: bool) -> String {
-    let fill = table_width.saturating_sub(label.len());
-    let line = format!("{}{}", label, "─".repeat(fill));
+    let fill = table_width.saturating_sub(display_width(label));
+    let line = format!("{}{}", label, Glyph::Rule.repeat(fill));
     if color {
-        line.bright_blue().bold().to_string()
+        paint(ThemeElement::Header, &line)
     } else {
         line
     }
 }
 
-fn render_classic(view: &[&Task], color: bool) -> Vec<String> {
+fn render_classic(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
+    let ctx = CellContext::new(state, DateView::Classic);
     let mut lines: Vec<String> = Vec::new();
     for task in view {
-        lines.extend(task_lines(task, color));
+        lines.extend(task_lines(task, &ctx, color));
     }
     lines
 }
@@ -298,56 +467,55 @@ fn render_table(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
 }
 
 #[derive(Debug, Clone)]
-struct TableLayout {
+struct TableLayout<'a> {
     term_width: usize,
-    cols: Vec<ListColumn>,
+    cols: Vec<TableColumn>,
     title_width: usize,
-    today: chrono::NaiveDate,
+    wrap: bool,
+    cells: CellContext<'a>,
 }
 
-fn compute_table_layout(state: &AppState) -> TableLayout {
-    let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
-    let cols = effective_columns(state);
-    let cols = fit_columns(term_width, &cols);
+/// Narrowest the title column gets before columns are dropped.
+const MIN_TITLE_WIDTH: usize = 18;
 
-    let today = chrono::Local::now().date_naive();
+fn compute_table_layout(state: &AppState) -> TableLayout<'_> {
+    let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
+    let mut cols = fit_columns(term_width, &table_columns(state));
 
-    let mut fixed = 0usize;
-    fixed += 1; // status
-    fixed += 1; // space
-    fixed += 4; // id
-    fixed += 2; // sep
-    for c in cols.iter() {
-        fixed += 2; // sep
-        fixed += column_width(*c);
+    let spare = term_width.saturating_sub(table_fixed_width(&cols) + MIN_TITLE_WIDTH);
+    // Flexible columns and the title split the spare width evenly; the title keeps the remainder.
+    let flex = cols.iter().filter(|c| c.flex).count();
+    let share = spare / (flex + 1);
+    for c in cols.iter_mut().filter(|c| c.flex) {
+        c.width += share;
     }
-
-    let title_width = term_width.saturating_sub(fixed).clamp(18, 120);
+    let title_width = (MIN_TITLE_WIDTH + spare - share * flex).min(120);
 
     TableLayout {
         term_width,
         cols,
         title_width,
-        today,
+        wrap: state.profile.wrap_rows,
+        cells: CellContext::new(state, DateView::Table),
     }
 }
 
+/// Width of everything in a table row except the title.
+fn table_fixed_width(cols: &[TableColumn]) -> usize {
+    // status + space + id + separator, then a separator before each column
+    1 + 1 + 4 + 2 + cols.iter().map(|c| 2 + c.width).sum::<usize>()
+}
+
 fn render_table_rows_only(view: &[&Task], layout: &TableLayout, color: bool) -> Vec<String> {
     let mut lines: Vec<String> = Vec::new();
     for task in view {
-        lines.push(build_table_row(
-            task,
-            &layout.cols,
-            layout.title_width,
-            layout.today,
-            color,
-        ));
+        lines.extend(build_table_row(task, layout, color));
     }
     lines
 }
 
 fn build_table_header(
-    cols: &[ListColumn],
+    cols: &[TableColumn],
     title_width: usize,
     term_width: usize,
     color: bool,
@@ -360,76 +528,135 @@ fn build_table_header(
     header.push_str(&pad_right("Title", title_width));
     for c in cols.iter() {
         header.push_str("  ");
-        header.push_str(&pad_right(column_header(*c), column_width(*c)));
+        let label = truncate_to_width(column_header(c.kind), c.width);
+        header.push_str(&align_cell(&label, c.width, c.align));
     }
 
     let mut sep = String::new();
-    let sep_len = std::cmp::min(term_width, header.chars().count());
-    sep.push_str(&"─".repeat(sep_len));
+    let sep_len = std::cmp::min(term_width, display_width(&header));
+    sep.push_str(&Glyph::Rule.repeat(sep_len));
 
     if color {
         vec![
-            format!("{}", center_line(&header, term_width).bright_blue().bold()),
-            sep.dimmed().to_string(),
+            paint(ThemeElement::Header, &center_line(&header, term_width)),
+            paint(ThemeElement::Separator, &sep),
         ]
     } else {
         vec![center_line(&header, term_width), sep]
     }
 }
 
-fn build_table_row(
-    task: &Task,
-    cols: &[ListColumn],
-    title_width: usize,
-    today: chrono::NaiveDate,
-    color: bool,
-) -> String {
-    let status = if task.done { "✔" } else { "•" };
+/// Lines of one table row: a single line, or with `wrap_rows` as many as its tallest cell needs.
+fn build_table_row(task: &Task, layout: &TableLayout, color: bool) -> Vec<String> {
+    let status = status_glyph(task.done);
     let status = if !color {
         status.to_string()
     } else if task.done {
-        format!("{}", status.green().bold())
+        paint(ThemeElement::StatusDone, status)
     } else {
-        format!("{}", status.yellow().bold())
+        paint(ThemeElement::StatusOpen, status)
     };
 
     let id_plain = format!("#{:>3}", task.id);
     let id_cell = pad_right(&id_plain, 4);
     let id_cell = if color {
-        format!("{}", id_cell.dimmed())
+        paint(ThemeElement::Muted, &id_cell)
     } else {
         id_cell
     };
 
-    let title_plain = truncate_to_width(&task.title, title_width);
-    let title_cell = pad_right(&title_plain, title_width);
-    let title_cell = if !color {
-        title_cell
-    } else if task.done {
-        format!("{}", title_cell.dimmed())
-    } else {
-        format!("{}", title_cell.bold())
+    let fit = |kind: Option<ListColumn>, text: &str, width: usize| {
+        if layout.wrap {
+            wrap_cell(kind, text, width)
+        } else {
+            vec![truncate_to_width(text, width)]
+        }
     };
+    let title_lines = fit(None, &task.title, layout.title_width);
+    let cell_lines: Vec<Vec<String>> = layout
+        .cols
+        .iter()
+        .map(|c| {
+            fit(
+                Some(c.kind),
+                &column_text(c.kind, task, &layout.cells),
+                c.width,
+            )
+        })
+        .collect();
+    let height = cell_lines
+        .iter()
+        .map(Vec::len)
+        .chain([title_lines.len()])
+        .max()
+        .unwrap_or(1);
+
+    let mut rows: Vec<String> = Vec::new();
+    for line in 0..height {
+        let mut row = String::new();
+        if line == 0 {
+            row.push_str(&status);
+            row.push(' ');
+            row.push_str(&id_cell);
+        } else {
+            row.push_str(&" ".repeat(6));
+        }
+        row.push_str("  ");
 
-    let mut row = String::new();
-    row.push_str(&status);
-    row.push(' ');
-    row.push_str(&id_cell);
-    row.push_str("  ");
-    row.push_str(&title_cell);
+        let title_plain = title_lines.get(line).map_or("", String::as_str);
+        let title_cell = pad_right(title_plain, layout.title_width);
+        row.push_str(&if !color {
+            title_cell
+        } else if task.done {
+            paint(ThemeElement::Done, &title_cell)
+        } else {
+            paint(ThemeElement::Title, &title_cell)
+        });
 
-    for c in cols.iter() {
-        row.push_str("  ");
-        row.push_str(&format_column_cell(*c, task, today, color));
+        for (c, lines) in layout.cols.iter().zip(&cell_lines) {
+            let plain = lines.get(line).map_or("", String::as_str);
+            row.push_str("  ");
+            row.push_str(&format_column_cell(c, task, plain, &layout.cells, color));
+        }
+        rows.push(row);
     }
+    rows
+}
 
-    row
+/// Wrap cell text into lines of `width`; tag lists break between tags where they can.
+fn wrap_cell(kind: Option<ListColumn>, text: &str, width: usize) -> Vec<String> {
+    if kind != Some(ListColumn::Tags) {
+        return wrap_to_width(text, width);
+    }
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for tag in text.split(',').filter(|t| !t.is_empty()) {
+        let joined = if current.is_empty() {
+            tag.to_string()
+        } else {
+            format!("{current},{tag}")
+        };
+        if display_width(&joined) <= width {
+            current = joined;
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        let mut parts = wrap_to_width(tag, width);
+        current = parts.pop().unwrap_or_default();
+        lines.extend(parts);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
 }
 
 fn render_cards(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
     let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
     let cols = effective_columns(state);
-    let today = chrono::Local::now().date_naive();
+    let ctx = CellContext::new(state, DateView::Cards);
 
     let mut lines: Vec<String> = Vec::new();
     for (idx, task) in view.iter().enumerate() {
@@ -437,18 +664,18 @@ fn render_cards(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
             lines.push("".to_string());
         }
 
-        let status = if task.done { "✔" } else { "•" };
+        let status = status_glyph(task.done);
         let status = if !color {
             status.to_string()
         } else if task.done {
-            format!("{}", status.green().bold())
+            paint(ThemeElement::StatusDone, status)
         } else {
-            format!("{}", status.yellow().bold())
+            paint(ThemeElement::StatusOpen, status)
         };
 
         let id = format!("#{:>3}", task.id);
         let id = if color {
-            format!("{}", id.dimmed())
+            paint(ThemeElement::Muted, &id)
         } else {
             id
         };
@@ -458,18 +685,18 @@ fn render_cards(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
         let title = if !color {
             title
         } else if task.done {
-            format!("{}", title.dimmed())
+            paint(ThemeElement::Done, &title)
         } else {
-            format!("{}", title.bold())
+            paint(ThemeElement::Title, &title)
         };
 
         lines.push(format!("{status} {id}  {title}"));
 
-        let meta = build_meta_line(task, &cols, today);
+        let meta = build_meta_line(task, &cols, &ctx);
         if !meta.is_empty() {
             let meta = truncate_to_width(&meta, term_width.saturating_sub(4));
             let meta = if color {
-                format!("{}", meta.dimmed())
+                paint(ThemeElement::Muted, &meta)
             } else {
                 meta
             };
@@ -480,34 +707,263 @@ fn render_cards(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
     lines
 }
 
+/// Narrowest a board column may get before columns wrap onto another row; on a
+/// terminal narrower than two of them the columns stack one under another.
+const BOARD_MIN_WIDTH: usize = 24;
+const BOARD_GAP: usize = 2;
+/// Narrowest terminal the board is laid out for.
+const BOARD_MIN_TERM_WIDTH: usize = 20;
+
+fn render_board(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
+    let term_width = terminal_width()
+        .unwrap_or(80)
+        .clamp(BOARD_MIN_TERM_WIDTH, 240);
+    let group = state.profile.board_group;
+    let columns = board_columns(view, state);
+    if columns.is_empty() {
+        return Vec::new();
+    }
+
+    let per_row =
+        ((term_width + BOARD_GAP) / (BOARD_MIN_WIDTH + BOARD_GAP)).clamp(1, columns.len());
+    let width = (term_width - BOARD_GAP * (per_row - 1)) / per_row;
+
+    // Don't repeat the grouping in every card.
+    let cols: Vec<ListColumn> = effective_columns(state)
+        .into_iter()
+        .filter(|c| {
+            !matches!(
+                (group, c),
+                (BoardGroup::Priority, ListColumn::Priority)
+                    | (BoardGroup::Branch, ListColumn::Branch)
+            )
+        })
+        .collect();
+    let ctx = CellContext::new(state, DateView::Board);
+    // One id width for the whole board keeps titles aligned across columns.
+    let id_width = view
+        .iter()
+        .map(|t| t.id.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    let gap = " ".repeat(BOARD_GAP);
+    let mut lines: Vec<String> = Vec::new();
+    for (row_idx, row) in columns.chunks(per_row).enumerate() {
+        if row_idx > 0 {
+            lines.push(String::new());
+        }
+        let cells: Vec<Vec<String>> = row
+            .iter()
+            .map(|(label, tasks)| {
+                board_column_lines(label, tasks, width, id_width, &cols, &ctx, color)
+            })
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or(0);
+        for i in 0..height {
+            let parts: Vec<String> = cells
+                .iter()
+                .map(|cell| cell.get(i).cloned().unwrap_or_else(|| " ".repeat(width)))
+                .collect();
+            lines.push(parts.join(&gap).trim_end().to_string());
+        }
+    }
+    lines
+}
+
+/// Split `view` into labeled board columns, keeping the incoming sort order within each.
+/// Empty columns are dropped, except tags the user picked explicitly.
+fn board_columns<'a>(view: &[&'a Task], state: &AppState) -> Vec<(String, Vec<&'a Task>)> {
+    let group_by = match state.profile.board_group {
+        BoardGroup::Status => GroupBy::Status,
+        BoardGroup::Priority => GroupBy::Priority,
+        BoardGroup::Branch => GroupBy::Branch,
+        BoardGroup::Tag if state.profile.board_tags.is_empty() => GroupBy::Tag,
+        BoardGroup::Tag => return chosen_tag_columns(view, &state.profile.board_tags),
+    };
+    let today = chrono::Local::now().date_naive();
+    group_tasks(view, group_by, &state.current_branch, today)
+}
+
+fn chosen_tag_columns<'a>(view: &[&'a Task], tags: &[String]) -> Vec<(String, Vec<&'a Task>)> {
+    let mut columns: Vec<(String, Vec<&'a Task>)> = tags
+        .iter()
+        .map(|tag| {
+            let tasks = view
+                .iter()
+                .copied()
+                .filter(|t| t.tags.contains(tag))
+                .collect();
+            (format!("#{tag}"), tasks)
+        })
+        .collect();
+    let untagged: Vec<&'a Task> = view
+        .iter()
+        .copied()
+        .filter(|t| !t.tags.iter().any(|tag| tags.contains(tag)))
+        .collect();
+    if !untagged.is_empty() {
+        columns.push(("Untagged".to_string(), untagged));
+    }
+    columns
+}
+
+/// Lines of one board column, each padded to exactly `width` visible characters.
+fn board_column_lines(
+    label: &str,
+    tasks: &[&Task],
+    width: usize,
+    id_width: usize,
+    cols: &[ListColumn],
+    ctx: &CellContext,
+    color: bool,
+) -> Vec<String> {
+    let header = pad_right(
+        &truncate_to_width(&format!("{label} ({})", tasks.len()), width),
+        width,
+    );
+    let sep = Glyph::Rule.repeat(width);
+    let mut lines = if color {
+        vec![
+            paint(ThemeElement::Header, &header),
+            paint(ThemeElement::Separator, &sep),
+        ]
+    } else {
+        vec![header, sep]
+    };
+
+    if tasks.is_empty() {
+        let empty = pad_right("(empty)", width);
+        lines.push(if color {
+            paint(ThemeElement::Muted, &empty)
+        } else {
+            empty
+        });
+        return lines;
+    }
+
+    // "• #3 " before the title, the id padded to `id_width`; wrapped title lines
+    // and the meta line align under it.
+    let indent = display_width(status_glyph(false)) + id_width + 3;
+    let text_width = width.saturating_sub(indent).max(1);
+    for (idx, task) in tasks.iter().enumerate() {
+        if idx > 0 {
+            lines.push(" ".repeat(width));
+        }
+
+        let status = status_glyph(task.done);
+        let status = if !color {
+            status.to_string()
+        } else if task.done {
+            paint(ThemeElement::StatusDone, status)
+        } else {
+            paint(ThemeElement::StatusOpen, status)
+        };
+        let id = format!("#{:>id_width$}", task.id);
+        let id = if color {
+            paint(ThemeElement::Muted, &id)
+        } else {
+            id
+        };
+
+        for (line_idx, chunk) in wrap_to_width(&task.title, text_width).iter().enumerate() {
+            let chunk = pad_right(chunk, text_width);
+            let chunk = if !color {
+                chunk
+            } else if task.done {
+                paint(ThemeElement::Done, &chunk)
+            } else {
+                paint(ThemeElement::Title, &chunk)
+            };
+            if line_idx == 0 {
+                lines.push(format!("{status} {id} {chunk}"));
+            } else {
+                lines.push(format!("{}{chunk}", " ".repeat(indent)));
+            }
+        }
+
+        let meta = build_meta_line(task, cols, ctx);
+        if !meta.is_empty() {
+            let meta = pad_right(&truncate_to_width(&meta, text_width), text_width);
+            let meta = if color {
+                paint(ThemeElement::Muted, &meta)
+            } else {
+                meta
+            };
+            lines.push(format!("{}{meta}", " ".repeat(indent)));
+        }
+    }
+    lines
+}
+
+/// Word-wrap `text` into lines of at most `width` columns, splitting words that don't fit.
+fn wrap_to_width(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut used = 0usize;
+    for word in text.split_whitespace() {
+        let word_width = display_width(word);
+        let gap = usize::from(used > 0);
+        if used + gap + word_width <= width {
+            if gap > 0 {
+                current.push(' ');
+            }
+            current.push_str(word);
+            used += gap + word_width;
+            continue;
+        }
+        if used > 0 {
+            lines.push(std::mem::take(&mut current));
+            used = 0;
+        }
+        for g in word.graphemes(true) {
+            let w = g.width();
+            if used > 0 && used + w > width {
+                lines.push(std::mem::take(&mut current));
+                used = 0;
+            }
+            current.push_str(g);
+            used += w;
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
 fn render_compact(view: &[&Task], state: &AppState, color: bool) -> Vec<String> {
     let term_width = terminal_width().unwrap_or(80).clamp(60, 240);
     let cols = effective_columns(state);
-    let today = chrono::Local::now().date_naive();
+    let ctx = CellContext::new(state, DateView::Compact);
 
     let mut lines: Vec<String> = Vec::new();
     for task in view {
-        let status = if task.done { "✔" } else { "•" };
+        let status = status_glyph(task.done);
         let status = if !color {
             status.to_string()
         } else if task.done {
-            format!("{}", status.green().bold())
+            paint(ThemeElement::StatusDone, status)
         } else {
-            format!("{}", status.yellow().bold())
+            paint(ThemeElement::StatusOpen, status)
         };
 
         let id = format!("#{:>3}", task.id);
         let id = if color {
-            format!("{}", id.dimmed())
+            paint(ThemeElement::Muted, &id)
         } else {
             id
         };
 
-        let meta = build_meta_line(task, &cols, today);
+        let meta = build_meta_line(task, &cols, &ctx);
         let mut line = if meta.is_empty() {
             format!("{status} {id}  {}", task.title)
         } else {
-            format!("{status} {id}  {}  —  {meta}", task.title)
+            format!(
+                "{status} {id}  {}  {}  {meta}",
+                task.title,
+                Glyph::Dash.as_str()
+            )
         };
 
         line = truncate_to_width(&line, term_width);
@@ -516,20 +972,16 @@ fn render_compact(view: &[&Task], state: &AppState, color: bool) -> Vec<String>
     lines
 }
 
-fn build_meta_line(task: &Task, cols: &[ListColumn], today: chrono::NaiveDate) -> String {
+pub(crate) fn build_meta_line(task: &Task, cols: &[ListColumn], ctx: &CellContext) -> String {
     let mut parts: Vec<String> = Vec::new();
     for c in cols.iter() {
         match c {
             ListColumn::Due => {
                 if let Some(due) = task.due {
-                    let label = if due < today {
-                        "overdue"
-                    } else if due == today {
-                        "due"
-                    } else {
-                        "due"
-                    };
-                    parts.push(format!("{label}: {due}"));
+                    // Relative styles already say when a task is overdue.
+                    let overdue = due < ctx.today && ctx.date_style == DateStyle::Absolute;
+                    let label = if overdue { "overdue" } else { "due" };
+                    parts.push(format!("{label}: {}", ctx.due_text(due, task.done)));
                 }
             }
             ListColumn::Priority => {
@@ -558,13 +1010,34 @@ fn build_meta_line(task: &Task, cols: &[ListColumn], today: chrono::NaiveDate) -
                     parts.push(format!("note: {c}"));
                 }
             }
+            ListColumn::Urgency => {
+                if !task.done {
+                    parts.push(format!(
+                        "urgency: {:.1}",
+                        urgency(task, ctx.urgency, ctx.today)
+                    ));
+                }
+            }
+            ListColumn::Created
+            | ListColumn::Updated
+            | ListColumn::Completed
+            | ListColumn::Uid
+            | ListColumn::Age
+            | ListColumn::Status => {
+                let text = column_text(*c, task, ctx);
+                if !text.is_empty() {
+                    parts.push(format!("{}: {text}", column_header(*c).to_lowercase()));
+                }
+            }
+            // Every line already starts with the id.
+            ListColumn::Id => {}
         }
     }
 
-    parts.join(" · ")
+    parts.join(&format!(" {} ", Glyph::Separator.as_str()))
 }
 
-fn effective_columns(state: &AppState) -> Vec<ListColumn> {
+pub(crate) fn effective_columns(state: &AppState) -> Vec<ListColumn> {
     if state.profile.list_columns.is_empty() {
         crate::model::default_list_columns()
     } else {
@@ -572,26 +1045,47 @@ fn effective_columns(state: &AppState) -> Vec<ListColumn> {
     }
 }
 
-fn fit_columns(term_width: usize, cols: &[ListColumn]) -> Vec<ListColumn> {
+/// A list column with the user's width, alignment and flex settings applied.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TableColumn {
+    pub kind: ListColumn,
+    pub width: usize,
+    pub align: ColumnAlign,
+    pub flex: bool,
+}
+
+/// The columns to show in the table, with their settings.
+pub(crate) fn table_columns(state: &AppState) -> Vec<TableColumn> {
+    effective_columns(state)
+        .into_iter()
+        .map(|kind| {
+            let settings = state.profile.column_settings.get(&kind);
+            TableColumn {
+                kind,
+                width: settings
+                    .and_then(|s| s.width)
+                    .unwrap_or_else(|| column_width(kind)),
+                align: settings
+                    .and_then(|s| s.align)
+                    .unwrap_or_else(|| column_align(kind)),
+                flex: settings.is_some_and(|s| s.flex),
+            }
+        })
+        .collect()
+}
+
+fn fit_columns(term_width: usize, cols: &[TableColumn]) -> Vec<TableColumn> {
     let mut selected = cols.to_vec();
-    // Try to keep at least 18 chars for title.
-    let min_title = 18usize;
-    loop {
-        let mut fixed = 0usize;
-        fixed += 1 + 1 + 4 + 2; // status + space + id + sep
-        for c in selected.iter() {
-            fixed += 2 + column_width(*c);
-        }
-        if term_width.saturating_sub(fixed) >= min_title {
-            return selected;
-        }
-        if selected.is_empty() {
-            return selected;
-        }
+    // Try to keep at least MIN_TITLE_WIDTH for the title.
+    while !selected.is_empty()
+        && term_width.saturating_sub(table_fixed_width(&selected)) < MIN_TITLE_WIDTH
+    {
         selected.pop();
     }
+    selected
 }
 
+/// Default width of a column.
 fn column_width(c: ListColumn) -> usize {
     match c {
         ListColumn::Due => 10,
@@ -600,10 +1094,24 @@ fn column_width(c: ListColumn) -> usize {
         ListColumn::Tags => 18,
         ListColumn::Repeat => 7,
         ListColumn::Content => 20,
+        ListColumn::Urgency => 5,
+        ListColumn::Created | ListColumn::Updated | ListColumn::Completed => 10,
+        ListColumn::Uid => 36,
+        ListColumn::Status => 6,
+        ListColumn::Age => 4,
+        ListColumn::Id => 4,
     }
 }
 
-fn column_header(c: ListColumn) -> &'static str {
+/// Default alignment of a column: numbers on the right, text on the left.
+fn column_align(c: ListColumn) -> ColumnAlign {
+    match c {
+        ListColumn::Urgency | ListColumn::Age | ListColumn::Id => ColumnAlign::Right,
+        _ => ColumnAlign::Left,
+    }
+}
+
+pub(crate) fn column_header(c: ListColumn) -> &'static str {
     match c {
         ListColumn::Due => "Due",
         ListColumn::Priority => "P",
@@ -611,96 +1119,133 @@ fn column_header(c: ListColumn) -> &'static str {
         ListColumn::Tags => "Tags",
         ListColumn::Repeat => "Repeat",
         ListColumn::Content => "Note",
+        ListColumn::Urgency => "Urg",
+        ListColumn::Created => "Created",
+        ListColumn::Updated => "Updated",
+        ListColumn::Completed => "Completed",
+        ListColumn::Uid => "UID",
+        ListColumn::Status => "Status",
+        ListColumn::Age => "Age",
+        ListColumn::Id => "ID",
     }
 }
 
-fn format_column_cell(c: ListColumn, task: &Task, today: chrono::NaiveDate, color: bool) -> String {
-    let width = column_width(c);
+/// Unpadded, uncolored text of a list column for `task`.
+pub(crate) fn column_text(c: ListColumn, task: &Task, ctx: &CellContext) -> String {
+    let stamp = |value: Option<&str>| {
+        value
+            .and_then(parse_timestamp)
+            .map(|t| ctx.date_text(t.date_naive()))
+            .unwrap_or_default()
+    };
     match c {
+        ListColumn::Due => task
+            .due
+            .map(|d| ctx.due_text(d, task.done))
+            .unwrap_or_default(),
+        ListColumn::Priority => task
+            .priority
+            .map(|p| match p {
+                Priority::High => "H",
+                Priority::Medium => "M",
+                Priority::Low => "L",
+            })
+            .unwrap_or(" ")
+            .to_string(),
+        ListColumn::Branch => task.branch.clone(),
+        ListColumn::Tags => task.tags.join(","),
+        ListColumn::Repeat => task
+            .repeat
+            .map(|r| format!("{r:?}").to_lowercase())
+            .unwrap_or_default(),
+        ListColumn::Content => task.content.as_deref().unwrap_or("").replace('\n', " "),
+        ListColumn::Urgency if task.done => String::new(),
+        ListColumn::Urgency => format!("{:.1}", urgency(task, ctx.urgency, ctx.today)),
+        ListColumn::Created => stamp(Some(&task.created_at)),
+        ListColumn::Updated => stamp(task.updated_at.as_deref()),
+        ListColumn::Completed => stamp(task.completed_at.as_deref()),
+        ListColumn::Uid => task.uid.clone().unwrap_or_default(),
+        ListColumn::Status => if task.done { "done" } else { "todo" }.to_string(),
+        ListColumn::Age => parse_timestamp(&task.created_at)
+            .map(|t| format_short_span((ctx.today - t.date_naive()).num_days().max(0)))
+            .unwrap_or_default(),
+        ListColumn::Id => format!("#{}", task.id),
+    }
+}
+
+/// Pad `plain` (already cut to fit) to the column's width and color it.
+fn format_column_cell(
+    c: &TableColumn,
+    task: &Task,
+    plain: &str,
+    ctx: &CellContext,
+    color: bool,
+) -> String {
+    let cell = align_cell(plain, c.width, c.align);
+    if !color {
+        return cell;
+    }
+    match c.kind {
         ListColumn::Due => {
-            let plain = task.due.map(|d| d.to_string()).unwrap_or_default();
-            let cell = pad_right(&plain, width);
-            if !color {
-                return cell;
-            }
             if task.done {
-                return format!("{}", cell.dimmed());
-            }
-            if let Some(due) = task.due {
-                match due.cmp(&today) {
-                    Ordering::Less => format!("{}", cell.red().bold()),
-                    Ordering::Equal => format!("{}", cell.yellow().bold()),
-                    Ordering::Greater => format!("{}", cell.cyan()),
-                }
-            } else {
-                cell
-            }
-        }
-        ListColumn::Priority => {
-            let plain = task
-                .priority
-                .map(|p| match p {
-                    Priority::High => "H",
-                    Priority::Medium => "M",
-                    Priority::Low => "L",
-                })
-                .unwrap_or(" ");
-            let cell = pad_right(plain, width);
-            if !color {
-                return cell;
+                return paint(ThemeElement::Done, &cell);
             }
-            match task.priority {
-                Some(Priority::High) => format!("{}", cell.red().bold()),
-                Some(Priority::Medium) => format!("{}", cell.yellow().bold()),
-                Some(Priority::Low) => format!("{}", cell.green().bold()),
+            match task.due {
+                Some(due) => paint(due_element(due, ctx.today), &cell),
                 None => cell,
             }
         }
-        ListColumn::Branch => {
-            let plain = truncate_to_width(&task.branch, width);
-            let cell = pad_right(&plain, width);
-            if color {
-                format!("{}", cell.dimmed())
-            } else {
-                cell
-            }
-        }
+        ListColumn::Priority => match task.priority {
+            Some(p) => paint(priority_element(p), &cell),
+            None => cell,
+        },
+        ListColumn::Branch => paint_branch(&task.branch, &cell, Some(ThemeElement::Branch)),
         ListColumn::Tags => {
-            let plain = truncate_to_width(&task.tags.join(","), width);
-            let cell = pad_right(&plain, width);
-            if color {
-                format!("{}", cell.dimmed())
-            } else {
-                cell
-            }
-        }
-        ListColumn::Repeat => {
-            let plain = task
-                .repeat
-                .map(|r| format!("{r:?}").to_lowercase())
-                .unwrap_or_default();
-            let plain = truncate_to_width(&plain, width);
-            let cell = pad_right(&plain, width);
-            if color {
-                format!("{}", cell.dimmed())
-            } else {
-                cell
-            }
+            let (left, right) = alignment_padding(plain, c.width, c.align);
+            format!(
+                "{}{}{}",
+                " ".repeat(left),
+                paint_tag_list(plain, ",", Some(ThemeElement::Tag)),
+                " ".repeat(right)
+            )
         }
-        ListColumn::Content => {
-            let plain = task.content.as_deref().unwrap_or("").replace('\n', " ");
-            let plain = truncate_to_width(&plain, width);
-            let cell = pad_right(&plain, width);
-            if color {
-                format!("{}", cell.dimmed())
+        ListColumn::Status => paint(
+            if task.done {
+                ThemeElement::StatusDone
             } else {
-                cell
-            }
-        }
+                ThemeElement::StatusOpen
+            },
+            &cell,
+        ),
+        ListColumn::Repeat
+        | ListColumn::Content
+        | ListColumn::Urgency
+        | ListColumn::Created
+        | ListColumn::Updated
+        | ListColumn::Completed
+        | ListColumn::Uid
+        | ListColumn::Age
+        | ListColumn::Id => paint(ThemeElement::Muted, &cell),
+    }
+}
+
+/// Fill `text` out to `width` columns on the side(s) `align` asks for.
+fn align_cell(text: &str, width: usize, align: ColumnAlign) -> String {
+    let (left, right) = alignment_padding(text, width, align);
+    format!("{}{text}{}", " ".repeat(left), " ".repeat(right))
+}
+
+/// Spaces before and after `text` to fill `width` columns.
+fn alignment_padding(text: &str, width: usize, align: ColumnAlign) -> (usize, usize) {
+    let fill = width.saturating_sub(display_width(text));
+    match align {
+        ColumnAlign::Left => (0, fill),
+        ColumnAlign::Right => (fill, 0),
+        ColumnAlign::Center => (fill / 2, fill - fill / 2),
     }
 }
 
-fn output_text(text: &str, auto_pager: bool, color: bool) {
+pub(crate) fn output_text(text: &str, auto_pager: bool, color: bool) {
     if !auto_pager || !std::io::stdout().is_terminal() || std::env::var_os("NO_PAGER").is_some() {
         print!("{text}");
         if !text.ends_with('\n') {
@@ -754,7 +1299,7 @@ fn output_text(text: &str, auto_pager: bool, color: bool) {
     let _ = child.wait();
 }
 
-fn terminal_width() -> Option<usize> {
+pub(crate) fn terminal_width() -> Option<usize> {
     std::env::var("COLUMNS")
         .ok()
         .and_then(|v| v.trim().parse::<usize>().ok())
@@ -768,35 +1313,113 @@ fn terminal_height() -> Option<usize> {
         .filter(|h| *h > 0)
 }
 
-fn truncate_to_width(text: &str, width: usize) -> String {
+/// Shorten `text` to at most `width` terminal columns, ending in `…` when cut.
+/// Escape sequences are kept, and a cut colored text is reset after the `…`.
+pub(crate) fn truncate_to_width(text: &str, width: usize) -> String {
     if width == 0 {
         return String::new();
     }
-
-    let len = text.chars().count();
-    if len <= width {
+    if display_width(text) <= width {
         return text.to_string();
     }
 
-    if width == 1 {
-        return "…".to_string();
+    let ellipsis = Glyph::Ellipsis.as_str();
+    let Some(room) = width.checked_sub(display_width(ellipsis)) else {
+        return take_columns(text, width).0;
+    };
+    let (mut out, styled) = take_columns(text, room);
+    out.push_str(ellipsis);
+    if styled {
+        out.push_str(ANSI_RESET);
     }
+    out
+}
 
-    let mut out: String = text.chars().take(width - 1).collect();
-    out.push('…');
+/// Pad `text` with spaces to exactly `width` columns, cutting it (without `…`) if wider.
+pub(crate) fn pad_right(text: &str, width: usize) -> String {
+    let (mut out, styled) = take_columns(text, width);
+    if styled && out.len() < text.len() {
+        out.push_str(ANSI_RESET);
+    }
+    let used = display_width(&out);
+    out.push_str(&" ".repeat(width.saturating_sub(used)));
     out
 }
 
-fn pad_right(text: &str, width: usize) -> String {
-    let len = text.chars().count();
-    if len >= width {
-        return text.chars().take(width).collect();
+/// Columns `text` takes up in a terminal: wide (CJK, emoji) clusters count two,
+/// combining marks none, and ANSI escape sequences are skipped.
+pub(crate) fn display_width(text: &str) -> usize {
+    segments(text)
+        .filter(|(_, escape)| !escape)
+        .map(|(seg, _)| seg.width())
+        .sum()
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// The longest prefix of `text` that fits in `width` columns, and whether it held escapes.
+/// A wide cluster that would straddle the limit is left out.
+fn take_columns(text: &str, width: usize) -> (String, bool) {
+    let mut out = String::new();
+    let mut used = 0usize;
+    let mut styled = false;
+    for (seg, escape) in segments(text) {
+        if escape {
+            out.push_str(seg);
+            styled = true;
+            continue;
+        }
+        let w = seg.width();
+        if used + w > width {
+            break;
+        }
+        out.push_str(seg);
+        used += w;
+    }
+    (out, styled)
+}
+
+/// Split `text` into ANSI escape sequences (`true`) and grapheme clusters (`false`).
+fn segments(text: &str) -> impl Iterator<Item = (&str, bool)> {
+    let mut rest = text;
+    let mut graphemes = "".graphemes(true);
+    std::iter::from_fn(move || {
+        if let Some(g) = graphemes.next() {
+            return Some((g, false));
+        }
+        if rest.is_empty() {
+            return None;
+        }
+        if rest.starts_with('\x1b') {
+            let len = escape_len(rest);
+            let (seq, tail) = rest.split_at(len);
+            rest = tail;
+            return Some((seq, true));
+        }
+        let end = rest.find('\x1b').unwrap_or(rest.len());
+        let (plain, tail) = rest.split_at(end);
+        rest = tail;
+        graphemes = plain.graphemes(true);
+        graphemes.next().map(|g| (g, false))
+    })
+}
+
+/// Byte length of the escape sequence `text` starts with: a CSI sequence such as
+/// `\x1b[1;31m`, or ESC and one more character.
+fn escape_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    match bytes.get(1) {
+        Some(b'[') => bytes[2..]
+            .iter()
+            .position(|b| (0x40..=0x7e).contains(b))
+            .map_or(bytes.len(), |end| end + 3),
+        Some(_) => 1 + text[1..].chars().next().map_or(0, char::len_utf8),
+        None => 1,
     }
-    format!("{}{}", text, " ".repeat(width - len))
 }
 
 fn center_line(text: &str, width: usize) -> String {
-    let len = text.chars().count();
+    let len = display_width(text);
     if width <= len {
         return text.to_string();
     }
@@ -804,7 +1427,7 @@ fn center_line(text: &str, width: usize) -> String {
     format!("{}{}", " ".repeat(pad), text)
 }
 
-fn task_lines(task: &Task, color: bool) -> Vec<String> {
+fn task_lines(task: &Task, ctx: &CellContext, color: bool) -> Vec<String> {
     let status = format_status(task.done, color);
     let id = format_id(task.id, color);
     let title = format_title(&task.title, color);
@@ -822,7 +1445,7 @@ fn task_lines(task: &Task, color: bool) -> Vec<String> {
         meta_parts.push(format!("{} {}", format_label("archived:", color), "yes"));
     }
     if let Some(due) = task.due {
-        let due_text = format_due(due, task.done, color);
+        let due_text = format_due(due, task.done, &ctx.due_text(due, task.done), color);
         meta_parts.push(format!("{} {due_text}", format_label("due:", color)));
     }
     if let Some(priority) = task.priority {
@@ -853,70 +1476,101 @@ fn task_lines(task: &Task, color: bool) -> Vec<String> {
     lines
 }
 
-pub fn print_task_view(task: &Task, color: bool) {
-    println!(
+pub fn print_task_view(task: &Task, state: &AppState, color: bool) {
+    let ctx = CellContext::new(state, DateView::Detail);
+    for line in task_view_lines(task, &ctx, color) {
+        println!("{line}");
+    }
+}
+
+pub(crate) fn task_view_lines(task: &Task, ctx: &CellContext, color: bool) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    lines.push(format!(
         "{} {}",
         format_label("ID:", color),
         format_id(task.id, color)
-    );
-    println!(
+    ));
+    lines.push(format!(
         "{} {}",
         format_label("Title:", color),
         format_title(&task.title, color)
-    );
+    ));
     if !is_default_branch(&task.branch) {
-        println!("{} {}", format_label("Branch:", color), task.branch);
+        lines.push(format!(
+            "{} {}",
+            format_label("Branch:", color),
+            if color {
+                paint_branch(&task.branch, &task.branch, None)
+            } else {
+                task.branch.clone()
+            }
+        ));
     }
     if let Some(content) = task.content.as_deref() {
-        println!("{} {content}", format_label("Content:", color));
+        lines.push(format!("{} {content}", format_label("Content:", color)));
     }
     if let Some(due) = task.due {
-        println!(
+        // Keep the date itself next to a relative form.
+        let date = format_date(due, ctx.date_format);
+        let mut text = ctx.due_text(due, task.done);
+        if text != date {
+            text = format!("{text} ({date})");
+        }
+        lines.push(format!(
             "{} {}",
             format_label("Due:", color),
-            format_due(due, task.done, color)
-        );
+            format_due(due, task.done, &text, color)
+        ));
     }
     if let Some(priority) = task.priority {
-        println!(
+        lines.push(format!(
             "{} {}",
             format_label("Priority:", color),
             format_priority(priority, color)
-        );
+        ));
     }
     if let Some(repeat) = task.repeat {
-        println!("{} {repeat:?}", format_label("Repeat:", color));
+        lines.push(format!("{} {repeat:?}", format_label("Repeat:", color)));
     }
     if !task.tags.is_empty() {
-        println!("{} {}", format_label("Tags:", color), task.tags.join(", "));
+        lines.push(format!(
+            "{} {}",
+            format_label("Tags:", color),
+            if color {
+                paint_tag_list(&task.tags.join(", "), ", ", None)
+            } else {
+                task.tags.join(", ")
+            }
+        ));
     }
     if let Some(uid) = task.uid.as_deref() {
-        println!("{} {uid}", format_label("UID:", color));
+        lines.push(format!("{} {uid}", format_label("UID:", color)));
     }
     if task.archived {
-        println!("{} yes", format_label("Archived:", color));
+        lines.push(format!("{} yes", format_label("Archived:", color)));
     }
-    println!(
+    lines.push(format!(
         "{} {}",
         format_label("Status:", color),
         format_status(task.done, color)
-    );
-    println!(
+    ));
+    lines.push(format!(
         "{} {}",
         format_label("Created:", color),
         format_created(&task.created_at, color)
-    );
+    ));
+    lines
 }
 
 fn format_status(done: bool, color: bool) -> String {
     if done {
         if color {
-            format!("{}", "done".green())
+            paint(ThemeElement::StatusDone, "done")
         } else {
             "done".to_string()
         }
     } else if color {
-        format!("{}", "todo".yellow())
+        paint(ThemeElement::StatusOpen, "todo")
     } else {
         "todo".to_string()
     }
@@ -925,7 +1579,7 @@ fn format_status(done: bool, color: bool) -> String {
 fn format_id(id: u64, color: bool) -> String {
     let value = format!("#{:>3}", id);
     if color {
-        format!("{}", value.dimmed())
+        paint(ThemeElement::Muted, &value)
     } else {
         value
     }
@@ -933,7 +1587,7 @@ fn format_id(id: u64, color: bool) -> String {
 
 fn format_title(title: &str, color: bool) -> String {
     if color {
-        format!("{}", title.bold())
+        paint(ThemeElement::Title, title)
     } else {
         title.to_string()
     }
@@ -941,27 +1595,40 @@ fn format_title(title: &str, color: bool) -> String {
 
 fn format_label(label: &str, color: bool) -> String {
     if color {
-        format!("{}", label.dimmed())
+        paint(ThemeElement::Muted, label)
     } else {
         label.to_string()
     }
 }
 
-fn format_due(due: chrono::NaiveDate, done: bool, color: bool) -> String {
-    let text = due.to_string();
+/// Color `text`, the rendering of `due`, by how close the date is.
+fn format_due(due: chrono::NaiveDate, done: bool, text: &str, color: bool) -> String {
     if !color {
-        return text;
+        return text.to_string();
     }
 
     if done {
-        return format!("{}", text.dimmed());
+        return paint(ThemeElement::Done, text);
     }
 
     let today = chrono::Local::now().date_naive();
+    paint(due_element(due, today), text)
+}
+
+/// Theme element for a due date relative to `today`.
+pub(crate) fn due_element(due: chrono::NaiveDate, today: chrono::NaiveDate) -> ThemeElement {
     match due.cmp(&today) {
-        Ordering::Less => format!("{}", text.red()),
-        Ordering::Equal => format!("{}", text.yellow()),
-        Ordering::Greater => format!("{}", text.cyan()),
+        Ordering::Less => ThemeElement::Overdue,
+        Ordering::Equal => ThemeElement::DueToday,
+        Ordering::Greater => ThemeElement::DueLater,
+    }
+}
+
+fn priority_element(priority: Priority) -> ThemeElement {
+    match priority {
+        Priority::High => ThemeElement::PriorityHigh,
+        Priority::Medium => ThemeElement::PriorityMedium,
+        Priority::Low => ThemeElement::PriorityLow,
     }
 }
 
@@ -971,16 +1638,12 @@ fn format_priority(priority: Priority, color: bool) -> String {
         return text;
     }
 
-    match priority {
-        Priority::High => format!("{}", text.red()),
-        Priority::Medium => format!("{}", text.yellow()),
-        Priority::Low => format!("{}", text.green()),
-    }
+    paint(priority_element(priority), &text)
 }
 
 fn format_created(value: &str, color: bool) -> String {
     if color {
-        format!("{}", value.dimmed())
+        paint(ThemeElement::Muted, value)
     } else {
         value.to_string()
     }
@@ -989,7 +1652,7 @@ fn format_created(value: &str, color: bool) -> String {
 fn format_header(branch: &str, color: bool) -> String {
     let text = format!("== {branch} ==");
     if color {
-        format!("{}", text.bold())
+        paint_branch(branch, &text, Some(ThemeElement::Header))
     } else {
         text
     }
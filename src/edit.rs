@@ -1,6 +1,13 @@
-use crate::model::{DEFAULT_BRANCH, Priority, Repeat, Task, default_branch};
+use std::io::Write;
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use crate::app::{mark_done_with_repeat, next_task_id};
+use crate::branch::normalize_branch;
+use crate::model::{AppConfig, DEFAULT_BRANCH, Priority, Repeat, Task, default_branch};
 use crate::util::normalize_tags;
-use crate::util::{parse_due, prompt_input};
+use crate::util::{parse_bool_flag, parse_due, prompt_input};
 
 pub fn edit_interactive(task: &mut Task) {
     let title = prompt_input(&format!("Title [{}]: ", task.title));
@@ -140,3 +147,375 @@ pub fn edit_interactive(task: &mut Task) {
         }
     }
 }
+
+const DOC_FENCE: &str = "---";
+const ERROR_PREFIX: &str = "# error:";
+
+/// Edit the tasks at `indices` in `$VISUAL`/`$EDITOR` as frontmatter documents.
+///
+/// Each task becomes a `---` header block with one `key: value` per line,
+/// followed by the content as the body. Lines starting with `#` in the header
+/// are comments. When a field is invalid the editor reopens with an
+/// `# error:` comment above the offending block. Returns how many tasks were
+/// written back (0 when the buffer was left unchanged). Repeating tasks marked
+/// done get their next occurrence appended, as with `todo done`.
+pub fn edit_in_editor(
+    tasks: &mut Vec<Task>,
+    indices: &[usize],
+    config: &AppConfig,
+) -> Result<usize, String> {
+    let original: String = indices
+        .iter()
+        .map(|idx| render_document(&tasks[*idx]))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let mut text = original.clone();
+    loop {
+        let path = write_temp_file(&text)?;
+        let status = open_editor(&path);
+        let edited = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+        status?;
+        let edited = edited.map_err(|e| format!("Failed to read temp file: {e}"))?;
+
+        if edited.trim().is_empty() {
+            return Err("Edit cancelled (empty buffer)".to_string());
+        }
+        if edited == original {
+            return Ok(0);
+        }
+
+        match apply_documents(tasks, indices, &edited, config) {
+            Ok(changed) => return Ok(changed),
+            Err(annotated) => {
+                eprintln!("Invalid fields, reopening editor");
+                text = annotated;
+            }
+        }
+    }
+}
+
+/// Write `text` to a new file in the temp dir that nobody else can read, or have
+/// planted there beforehand: a random name, created exclusively, mode 0600.
+fn write_temp_file(text: &str) -> Result<PathBuf, String> {
+    let path = std::env::temp_dir().join(format!("todo-edit-{}.md", Uuid::new_v4().simple()));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .map_err(|e| format!("Failed to create temp file: {e}"))?;
+    if let Err(e) = file.write_all(text.as_bytes()) {
+        let _ = std::fs::remove_file(&path);
+        return Err(format!("Failed to write temp file: {e}"));
+    }
+    Ok(path)
+}
+
+fn open_editor(path: &std::path::Path) -> Result<(), String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("Failed to start editor '{program}': {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Editor '{program}' exited with {status}"))
+    }
+}
+
+fn render_document(task: &Task) -> String {
+    let mut lines = vec![DOC_FENCE.to_string()];
+    lines.push(format!("id: {}", task.id));
+    if let Some(uid) = task.uid.as_deref() {
+        lines.push(format!("# uid: {uid}"));
+    }
+    lines.push(format!("title: {}", task.title));
+    lines.push(format!("branch: {}", task.branch));
+    lines.push(format!(
+        "due: {}",
+        task.due.map(|d| d.to_string()).unwrap_or_default()
+    ));
+    lines.push(format!(
+        "priority: {}",
+        task.priority
+            .map(|p| format!("{p:?}").to_lowercase())
+            .unwrap_or_default()
+    ));
+    lines.push(format!(
+        "repeat: {}",
+        task.repeat
+            .map(|r| format!("{r:?}").to_lowercase())
+            .unwrap_or_default()
+    ));
+    lines.push(format!("tags: {}", task.tags.join(", ")));
+    lines.push(format!("done: {}", task.done));
+    lines.push(format!("archived: {}", task.archived));
+    lines.push(DOC_FENCE.to_string());
+    if let Some(content) = task.content.as_deref() {
+        lines.push(content.to_string());
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+struct Document {
+    /// Header lines (without fences), including comments.
+    header: Vec<String>,
+    body: Vec<String>,
+}
+
+fn split_documents(text: &str) -> Result<Vec<Document>, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut docs: Vec<Document> = Vec::new();
+    let mut i = 0usize;
+
+    while i < lines.len() && lines[i].trim().is_empty() {
+        i += 1;
+    }
+    while i < lines.len() {
+        if lines[i].trim_end() != DOC_FENCE {
+            return Err(format!("expected '{DOC_FENCE}' on line {}", i + 1));
+        }
+        i += 1;
+
+        let mut header: Vec<String> = Vec::new();
+        while i < lines.len() && lines[i].trim_end() != DOC_FENCE {
+            header.push(lines[i].to_string());
+            i += 1;
+        }
+        if i >= lines.len() {
+            return Err("unterminated header block".to_string());
+        }
+        i += 1;
+
+        // The body runs until the next fence that opens a header with an `id:` line.
+        let mut body: Vec<String> = Vec::new();
+        while i < lines.len() && !starts_document(&lines[i..]) {
+            body.push(lines[i].to_string());
+            i += 1;
+        }
+        while body.last().is_some_and(|l| l.trim().is_empty()) {
+            body.pop();
+        }
+        docs.push(Document { header, body });
+    }
+
+    Ok(docs)
+}
+
+fn starts_document(lines: &[&str]) -> bool {
+    if lines.first().map(|l| l.trim_end()) != Some(DOC_FENCE) {
+        return false;
+    }
+    lines[1..]
+        .iter()
+        .map(|l| l.trim())
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .is_some_and(|l| l.starts_with("id:"))
+}
+
+/// Validate every document and apply them all, or return the buffer annotated with errors.
+fn apply_documents(
+    tasks: &mut Vec<Task>,
+    indices: &[usize],
+    text: &str,
+    config: &AppConfig,
+) -> Result<usize, String> {
+    let docs = match split_documents(text) {
+        Ok(docs) => docs,
+        Err(e) => return Err(format!("{ERROR_PREFIX} {e}\n{}", strip_errors(text))),
+    };
+
+    let mut updates: Vec<(usize, Task, bool)> = Vec::new();
+    let mut errors: Vec<Vec<String>> = Vec::new();
+    for doc in docs.iter() {
+        match parse_document(tasks, indices, doc, &updates) {
+            Ok(update) => {
+                updates.push(update);
+                errors.push(Vec::new());
+            }
+            Err(doc_errors) => errors.push(doc_errors),
+        }
+    }
+
+    if errors.iter().any(|e| !e.is_empty()) {
+        let mut out: Vec<String> = Vec::new();
+        for (doc, doc_errors) in docs.iter().zip(errors.iter()) {
+            out.push(DOC_FENCE.to_string());
+            for error in doc_errors {
+                out.push(format!("{ERROR_PREFIX} {error}"));
+            }
+            out.extend(
+                doc.header
+                    .iter()
+                    .filter(|l| !l.trim_start().starts_with(ERROR_PREFIX))
+                    .cloned(),
+            );
+            out.push(DOC_FENCE.to_string());
+            out.extend(doc.body.iter().cloned());
+            out.push(String::new());
+        }
+        return Err(out.join("\n"));
+    }
+
+    let mut changed = 0usize;
+    for (idx, mut updated, completes) in updates {
+        if completes {
+            let mut next_id = next_task_id(tasks, config.id_scope, Some(&updated.branch));
+            if let Some(next) = mark_done_with_repeat(&mut updated, &mut next_id, config.use_uuid) {
+                tasks.push(next);
+            }
+        }
+        let same = serde_json::to_string(&updated).ok() == serde_json::to_string(&tasks[idx]).ok();
+        if !same {
+            updated.touch();
+            tasks[idx] = updated;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+fn strip_errors(text: &str) -> String {
+    text.lines()
+        .filter(|l| !l.trim_start().starts_with(ERROR_PREFIX))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+fn parse_document(
+    tasks: &[Task],
+    indices: &[usize],
+    doc: &Document,
+    seen: &[(usize, Task, bool)],
+) -> Result<(usize, Task, bool), Vec<String>> {
+    let mut errors: Vec<String> = Vec::new();
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in doc.header.iter() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        match trimmed.split_once(':') {
+            Some((key, value)) => {
+                fields.push((key.trim().to_lowercase(), value.trim().to_string()));
+            }
+            None => errors.push(format!("expected 'key: value', got '{trimmed}'")),
+        }
+    }
+
+    let id = fields
+        .iter()
+        .find(|(k, _)| k == "id")
+        .map(|(_, v)| v.clone());
+    let Some(id) = id else {
+        return Err(vec!["missing 'id' field".to_string()]);
+    };
+    let Some(idx) = indices
+        .iter()
+        .copied()
+        .find(|idx| tasks[*idx].id.to_string() == id)
+    else {
+        return Err(vec![format!(
+            "id {id} is not one of the tasks being edited"
+        )]);
+    };
+    if seen.iter().any(|(i, _, _)| *i == idx) {
+        return Err(vec![format!("id {id} appears more than once")]);
+    }
+
+    let mut task = tasks[idx].clone();
+    // Completing is left to `apply_documents`, which also creates the next occurrence.
+    let mut completes = false;
+    for (key, value) in fields {
+        match key.as_str() {
+            "id" => {}
+            "title" => {
+                if value.is_empty() {
+                    errors.push("title cannot be empty".to_string());
+                } else {
+                    task.title = value;
+                }
+            }
+            "branch" => {
+                task.branch = normalize_branch(Some(value)).unwrap_or_else(default_branch);
+            }
+            "due" => {
+                if value.is_empty() {
+                    task.due = None;
+                } else {
+                    match parse_due(&value) {
+                        Ok(due) => task.due = Some(due),
+                        Err(e) => errors.push(format!("due: {e}")),
+                    }
+                }
+            }
+            "priority" => {
+                if value.is_empty() {
+                    task.priority = None;
+                } else {
+                    match value.parse::<Priority>() {
+                        Ok(priority) => task.priority = Some(priority),
+                        Err(e) => errors.push(format!("priority: {e}")),
+                    }
+                }
+            }
+            "repeat" => {
+                if value.is_empty() {
+                    task.repeat = None;
+                } else {
+                    match value.parse::<Repeat>() {
+                        Ok(repeat) => task.repeat = Some(repeat),
+                        Err(e) => errors.push(format!("repeat: {e}")),
+                    }
+                }
+            }
+            "tags" => {
+                let parts: Vec<String> = value.split(',').map(|t| t.to_string()).collect();
+                task.tags = normalize_tags(&parts);
+            }
+            "done" => match parse_bool_flag(&value) {
+                Some(true) => completes = !task.done,
+                Some(false) => {
+                    if task.done {
+                        task.set_done(false);
+                    }
+                }
+                None => errors.push("done: expected true|false".to_string()),
+            },
+            "archived" => match parse_bool_flag(&value) {
+                Some(archived) => task.archived = archived,
+                None => errors.push("archived: expected true|false".to_string()),
+            },
+            other => errors.push(format!("unknown field '{other}'")),
+        }
+    }
+
+    let content = doc.body.join("\n");
+    task.content = if content.trim().is_empty() {
+        None
+    } else {
+        Some(content)
+    };
+
+    if errors.is_empty() {
+        Ok((idx, task, completes))
+    } else {
+        Err(errors)
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::output;
+
+/// Why a command failed. Every kind has its own documented exit code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodoError {
+    /// Something went wrong that fits no other kind, e.g. the editor or terminal failed (1).
+    Failed(String),
+    /// A flag, argument or value is invalid, or options conflict (2, like clap's usage errors).
+    Usage(String),
+    /// A task, branch or saved command given by id or name does not exist (3).
+    NotFound(String),
+    /// A bulk command, `archive`, `import` or `edit --query` had nothing to act on (4).
+    NothingMatched(String),
+    /// The data directory or its files could not be read or written (5).
+    Storage(String),
+}
+
+/// The error object printed on stderr with `--json`.
+#[derive(Serialize)]
+struct ErrorDoc<'a> {
+    error: ErrorBody<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    kind: &'static str,
+    code: i32,
+    message: &'a str,
+}
+
+impl TodoError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TodoError::Failed(_) => 1,
+            TodoError::Usage(_) => 2,
+            TodoError::NotFound(_) => 3,
+            TodoError::NothingMatched(_) => 4,
+            TodoError::Storage(_) => 5,
+        }
+    }
+
+    /// The `kind` field of the `--json` error object.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TodoError::Failed(_) => "failed",
+            TodoError::Usage(_) => "usage",
+            TodoError::NotFound(_) => "not-found",
+            TodoError::NothingMatched(_) => "nothing-matched",
+            TodoError::Storage(_) => "storage",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            TodoError::Failed(m)
+            | TodoError::Usage(m)
+            | TodoError::NotFound(m)
+            | TodoError::NothingMatched(m)
+            | TodoError::Storage(m) => m,
+        }
+    }
+
+    /// Print the error on stderr, as `error: …` or a JSON object with `--json`.
+    pub fn report(&self) {
+        if output::mode().json {
+            let doc = ErrorDoc {
+                error: ErrorBody {
+                    kind: self.kind(),
+                    code: self.exit_code(),
+                    message: self.message(),
+                },
+            };
+            let text = serde_json::to_string(&doc).expect("serialize error");
+            eprintln!("{text}");
+        } else {
+            eprintln!("error: {self}");
+        }
+    }
+
+    /// Report the error and exit with its code.
+    pub fn exit(self) -> ! {
+        self.report();
+        std::process::exit(self.exit_code())
+    }
+}
+
+impl fmt::Display for TodoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for TodoError {}
@@ -1,45 +1,40 @@
+use std::fmt::Write as _;
+use std::io::Write;
+
+use clap::ValueEnum;
+use csv::{QuoteStyle, WriterBuilder};
+
 use crate::branch::is_default_branch;
-use crate::cli::ExportFormat;
+use crate::cli::{CsvColumn, CsvQuote, ExportFormat};
 use crate::display::task_lines_plain;
-use crate::model::Task;
+use crate::error::TodoError;
+use crate::ics::write_calendar;
+use crate::model::{ISO_DATE_FORMAT, Task};
+use crate::taskwarrior;
+use crate::theme::value_name;
+use crate::todotxt;
+
+/// How `export csv` lays out its output.
+pub struct CsvOptions {
+    /// Empty means every column.
+    pub columns: Vec<CsvColumn>,
+    pub delimiter: u8,
+    pub header: bool,
+    pub quote: CsvQuote,
+}
 
-pub fn export_tasks(view: &[&Task], format: ExportFormat) {
+pub fn export_tasks(
+    view: &[&Task],
+    format: ExportFormat,
+    csv: &CsvOptions,
+) -> Result<(), TodoError> {
     match format {
         ExportFormat::Json => {
             let data: Vec<&Task> = view.to_vec();
             let bytes = serde_json::to_vec_pretty(&data).expect("serialize tasks");
             println!("{}", String::from_utf8_lossy(&bytes));
         }
-        ExportFormat::Markdown => {
-            for task in view {
-                let status = if task.done { "x" } else { " " };
-                println!("- [{status}] {} (#{})", task.title, task.id);
-                if !is_default_branch(&task.branch) {
-                    println!("  - branch: {}", task.branch);
-                }
-                if let Some(due) = task.due {
-                    println!("  - due: {due}");
-                }
-                if let Some(priority) = task.priority {
-                    println!("  - priority: {priority:?}");
-                }
-                if let Some(repeat) = task.repeat {
-                    println!("  - repeat: {repeat:?}");
-                }
-                if !task.tags.is_empty() {
-                    println!("  - tags: {}", task.tags.join(", "));
-                }
-                if let Some(uid) = task.uid.as_deref() {
-                    println!("  - uid: {uid}");
-                }
-                if task.archived {
-                    println!("  - archived: true");
-                }
-                if let Some(content) = task.content.as_deref() {
-                    println!("  - content: {content}");
-                }
-            }
-        }
+        ExportFormat::Markdown => print!("{}", write_markdown(view)),
         ExportFormat::Text => {
             for task in view {
                 for line in task_lines_plain(task) {
@@ -47,5 +42,226 @@ pub fn export_tasks(view: &[&Task], format: ExportFormat) {
                 }
             }
         }
+        ExportFormat::Csv => return export_csv(view, csv),
+        ExportFormat::Ics => print!("{}", write_calendar(view)),
+        ExportFormat::Todotxt => print!("{}", todotxt::write_tasks(view)),
+        ExportFormat::Taskwarrior => println!("{}", taskwarrior::write_tasks(view)),
+    }
+    Ok(())
+}
+
+/// The checklist `export markdown` writes and `import markdown` reads back.
+pub(crate) fn write_markdown(view: &[&Task]) -> String {
+    let mut out = String::new();
+    for task in view {
+        let status = if task.done { "x" } else { " " };
+        let _ = writeln!(out, "- [{status}] {} (#{})", task.title, task.id);
+        if !is_default_branch(&task.branch) {
+            let _ = writeln!(out, "  - branch: {}", task.branch);
+        }
+        if let Some(due) = task.due {
+            let _ = writeln!(out, "  - due: {due}");
+        }
+        if let Some(priority) = task.priority {
+            let _ = writeln!(out, "  - priority: {priority:?}");
+        }
+        if let Some(repeat) = task.repeat {
+            let _ = writeln!(out, "  - repeat: {repeat:?}");
+        }
+        if !task.tags.is_empty() {
+            let _ = writeln!(out, "  - tags: {}", task.tags.join(", "));
+        }
+        if let Some(uid) = task.uid.as_deref() {
+            let _ = writeln!(out, "  - uid: {uid}");
+        }
+        if task.archived {
+            let _ = writeln!(out, "  - archived: true");
+        }
+        if let Some(content) = task.content.as_deref() {
+            // Continuation lines are indented so `import markdown` reads them back.
+            let _ = writeln!(out, "  - content: {}", content.replace('\n', "\n    "));
+        }
+    }
+    out
+}
+
+fn export_csv(view: &[&Task], options: &CsvOptions) -> Result<(), TodoError> {
+    write_csv(std::io::stdout(), view, options)
+        .map_err(|e| TodoError::Failed(format!("cannot write CSV: {e}")))
+}
+
+/// The columns `options` asks for; empty means all of them.
+fn csv_columns(options: &CsvOptions) -> &[CsvColumn] {
+    if options.columns.is_empty() {
+        CsvColumn::value_variants()
+    } else {
+        &options.columns
+    }
+}
+
+fn write_csv<W: Write>(out: W, view: &[&Task], options: &CsvOptions) -> csv::Result<()> {
+    let quote = match options.quote {
+        CsvQuote::Necessary => QuoteStyle::Necessary,
+        CsvQuote::Always => QuoteStyle::Always,
+        CsvQuote::Never => QuoteStyle::Never,
+        CsvQuote::NonNumeric => QuoteStyle::NonNumeric,
+    };
+    let mut writer = WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .quote_style(quote)
+        .from_writer(out);
+    let columns = csv_columns(options);
+    if options.header {
+        writer.write_record(columns.iter().map(|c| value_name(*c)))?;
+    }
+    for task in view {
+        writer.write_record(columns.iter().map(|c| csv_field(task, *c)))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// A task's value for `column`, in the form `import csv` reads back.
+fn csv_field(task: &Task, column: CsvColumn) -> String {
+    let optional = |value: Option<&String>| value.cloned().unwrap_or_default();
+    match column {
+        CsvColumn::Id => task.id.to_string(),
+        CsvColumn::Uid => optional(task.uid.as_ref()),
+        CsvColumn::Title => task.title.clone(),
+        CsvColumn::Content => optional(task.content.as_ref()),
+        CsvColumn::Tags => join_tags(&task.tags),
+        CsvColumn::Due => task
+            .due
+            .map(|d| d.format(ISO_DATE_FORMAT).to_string())
+            .unwrap_or_default(),
+        CsvColumn::Priority => task.priority.map(value_name).unwrap_or_default(),
+        CsvColumn::Repeat => task.repeat.map(value_name).unwrap_or_default(),
+        CsvColumn::Branch => task.branch.clone(),
+        CsvColumn::Done => task.done.to_string(),
+        CsvColumn::Archived => task.archived.to_string(),
+        CsvColumn::CreatedAt => task.created_at.clone(),
+        CsvColumn::UpdatedAt => optional(task.updated_at.as_ref()),
+        CsvColumn::CompletedAt => optional(task.completed_at.as_ref()),
+    }
+}
+
+/// Tags for one CSV field: comma-separated, with `\` escaping a comma or backslash in a tag.
+fn join_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| tag.replace('\\', "\\\\").replace(',', "\\,"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Read back a tags field written by `join_tags`.
+pub(crate) fn split_tags(raw: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut current = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => current.extend(chars.next()),
+            ',' => tags.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    tags.push(current);
+    tags.into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::parse_csv;
+    use crate::model::{Priority, Repeat};
+
+    fn task() -> Task {
+        Task {
+            id: 4,
+            uid: Some("7f0c7a52-5b8e-4c57-9c1a-2f3c1e0d9a11".to_string()),
+            title: "Water the plants, \"all\" of them".to_string(),
+            content: Some("first line\nsecond; line".to_string()),
+            tags: vec!["home".to_string(), "a,b".to_string()],
+            due: "2026-10-24".parse().ok(),
+            priority: Some(Priority::High),
+            repeat: Some(Repeat::Weekly),
+            branch: "work".to_string(),
+            archived: true,
+            done: true,
+            created_at: "2026-10-18T09:30:00+02:00".to_string(),
+            updated_at: Some("2026-10-19T08:00:00+02:00".to_string()),
+            completed_at: Some("2026-10-19T07:59:30+02:00".to_string()),
+        }
+    }
+
+    fn round_trip(options: &CsvOptions) {
+        let task = task();
+        let mut bytes = Vec::new();
+        write_csv(&mut bytes, &[&task], options).unwrap();
+
+        let headerless = (!options.header).then(|| csv_columns(options));
+        let (rows, skipped) = parse_csv(&bytes, options.delimiter, headerless).unwrap();
+        assert_eq!(skipped, 0);
+        let [row] = rows.as_slice() else {
+            panic!("expected one row, got {}", rows.len());
+        };
+        assert_eq!(row.title, task.title);
+        assert_eq!(row.uid, task.uid);
+        assert_eq!(row.content, task.content);
+        assert_eq!(row.tags, task.tags);
+        assert_eq!(row.due.as_deref(), Some("2026-10-24"));
+        assert_eq!(row.priority, task.priority);
+        assert!(matches!(row.repeat, Some(Repeat::Weekly)));
+        assert_eq!(row.branch.as_deref(), Some("work"));
+        assert_eq!(row.done, Some(true));
+        assert_eq!(row.archived, Some(true));
+        assert_eq!(row.created_at.as_deref(), Some(task.created_at.as_str()));
+        assert_eq!(row.updated_at, task.updated_at);
+        assert_eq!(row.completed_at, task.completed_at);
+    }
+
+    #[test]
+    fn csv_round_trips_every_field() {
+        round_trip(&CsvOptions {
+            columns: Vec::new(),
+            delimiter: b',',
+            header: true,
+            quote: CsvQuote::Necessary,
+        });
+        round_trip(&CsvOptions {
+            columns: Vec::new(),
+            delimiter: b';',
+            header: true,
+            quote: CsvQuote::Always,
+        });
+    }
+
+    #[test]
+    fn csv_without_a_header_round_trips_with_its_columns() {
+        round_trip(&CsvOptions {
+            columns: Vec::new(),
+            delimiter: b'\t',
+            header: false,
+            quote: CsvQuote::Necessary,
+        });
+        let mut columns = CsvColumn::value_variants().to_vec();
+        columns.reverse();
+        round_trip(&CsvOptions {
+            columns,
+            delimiter: b',',
+            header: false,
+            quote: CsvQuote::Necessary,
+        });
+    }
+
+    #[test]
+    fn tags_with_commas_survive_a_csv_field() {
+        let tags = vec!["a,b".to_string(), r"c\d".to_string(), "home".to_string()];
+        assert_eq!(join_tags(&tags), r"a\,b,c\\d,home");
+        assert_eq!(split_tags(&join_tags(&tags)), tags);
+        assert_eq!(split_tags(" work , home,,"), vec!["work", "home"]);
     }
 }
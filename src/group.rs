@@ -0,0 +1,210 @@
+use chrono::{Datelike, Days, NaiveDate};
+
+use crate::branch::order_branches;
+use crate::cli::GroupBy;
+use crate::model::{Priority, Task};
+
+/// Split an already sorted view into labeled groups, keeping the order within each group.
+/// Empty groups are dropped. With tag grouping a task appears under each of its tags.
+pub fn group_tasks<'a>(
+    view: &[&'a Task],
+    group_by: GroupBy,
+    current_branch: &str,
+    today: NaiveDate,
+) -> Vec<(String, Vec<&'a Task>)> {
+    let mut groups: Vec<(String, Vec<&'a Task>)> = match group_by {
+        GroupBy::DueDay => {
+            // Open tasks first, then completed ones, each by date with undated tasks last.
+            let mut groups: Vec<(String, Vec<&'a Task>)> = Vec::new();
+            for done in [false, true] {
+                let part: Vec<&'a Task> = view.iter().copied().filter(|t| t.done == done).collect();
+                let suffix = if done { ", done" } else { "" };
+                groups.extend(
+                    bucket_by_due(&part, |d| d, |d| d.to_string())
+                        .into_iter()
+                        .map(|(label, tasks)| (format!("{label}{suffix}"), tasks)),
+                );
+            }
+            groups
+        }
+        GroupBy::DueWeek => bucket_by_due(view, week_start, |d| {
+            format!("Week of {d} (W{:02})", d.iso_week().week())
+        }),
+        GroupBy::DueMonth => bucket_by_due(
+            view,
+            |d| d.with_day(1).unwrap_or(d),
+            |d| d.format("%B %Y").to_string(),
+        ),
+        GroupBy::Relative => {
+            let end_of_week = week_start(today) + Days::new(6);
+            let labels = ["Overdue", "Today", "This week", "Later", "Someday", "Done"];
+            // Done tasks get their own group instead of counting as overdue.
+            let bucket = |task: &Task| match task.due {
+                _ if task.done => 5,
+                Some(d) if d < today => 0,
+                Some(d) if d == today => 1,
+                Some(d) if d <= end_of_week => 2,
+                Some(_) => 3,
+                None => 4,
+            };
+            labels
+                .iter()
+                .enumerate()
+                .map(|(idx, label)| {
+                    let tasks = view.iter().copied().filter(|t| bucket(t) == idx).collect();
+                    (label.to_string(), tasks)
+                })
+                .collect()
+        }
+        GroupBy::Priority => [
+            ("High", Some(Priority::High)),
+            ("Medium", Some(Priority::Medium)),
+            ("Low", Some(Priority::Low)),
+            ("No priority", None),
+        ]
+        .into_iter()
+        .map(|(label, priority)| {
+            let tasks = view
+                .iter()
+                .copied()
+                .filter(|t| t.priority == priority)
+                .collect();
+            (label.to_string(), tasks)
+        })
+        .collect(),
+        GroupBy::Tag => {
+            let mut tags: Vec<String> = view.iter().flat_map(|t| t.tags.clone()).collect();
+            tags.sort();
+            tags.dedup();
+            let mut groups: Vec<(String, Vec<&'a Task>)> = tags
+                .into_iter()
+                .map(|tag| {
+                    let tasks = view
+                        .iter()
+                        .copied()
+                        .filter(|t| t.tags.contains(&tag))
+                        .collect();
+                    (format!("#{tag}"), tasks)
+                })
+                .collect();
+            groups.push((
+                "Untagged".to_string(),
+                view.iter().copied().filter(|t| t.tags.is_empty()).collect(),
+            ));
+            groups
+        }
+        GroupBy::Branch => {
+            let mut branches: Vec<String> = Vec::new();
+            for task in view {
+                if !branches
+                    .iter()
+                    .any(|b| b.eq_ignore_ascii_case(&task.branch))
+                {
+                    branches.push(task.branch.clone());
+                }
+            }
+            branches.sort_by_key(|b| b.to_lowercase());
+            order_branches(&mut branches, current_branch);
+            branches
+                .into_iter()
+                .map(|branch| {
+                    let tasks = view
+                        .iter()
+                        .copied()
+                        .filter(|t| t.branch.eq_ignore_ascii_case(&branch))
+                        .collect();
+                    (branch, tasks)
+                })
+                .collect()
+        }
+        GroupBy::Status => vec![
+            (
+                "Todo".to_string(),
+                view.iter().copied().filter(|t| !t.done).collect(),
+            ),
+            (
+                "Done".to_string(),
+                view.iter().copied().filter(|t| t.done).collect(),
+            ),
+        ],
+    };
+    groups.retain(|(_, tasks)| !tasks.is_empty());
+    groups
+}
+
+/// Group dated tasks by `key(due)` in ascending key order, with undated tasks in a final group.
+fn bucket_by_due<'a>(
+    view: &[&'a Task],
+    key: impl Fn(NaiveDate) -> NaiveDate,
+    label: impl Fn(NaiveDate) -> String,
+) -> Vec<(String, Vec<&'a Task>)> {
+    let mut dated: std::collections::BTreeMap<NaiveDate, Vec<&'a Task>> =
+        std::collections::BTreeMap::new();
+    let mut no_due: Vec<&'a Task> = Vec::new();
+    for task in view {
+        match task.due {
+            Some(d) => dated.entry(key(d)).or_default().push(*task),
+            None => no_due.push(*task),
+        }
+    }
+
+    let mut groups: Vec<(String, Vec<&'a Task>)> = dated
+        .into_iter()
+        .map(|(date, tasks)| (label(date), tasks))
+        .collect();
+    groups.push(("No due date".to_string(), no_due));
+    groups
+}
+
+/// Monday of the week containing `date`.
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: u64, due: Option<NaiveDate>, done: bool) -> Task {
+        Task {
+            id,
+            uid: None,
+            title: format!("Task {id}"),
+            content: None,
+            tags: Vec::new(),
+            due,
+            priority: None,
+            repeat: None,
+            branch: "main".to_string(),
+            archived: false,
+            done,
+            created_at: "2026-10-01T09:00:00+00:00".to_string(),
+            updated_at: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn relative_groups_keep_done_tasks_out_of_overdue() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let past = NaiveDate::from_ymd_opt(2026, 10, 10);
+        let tasks = [
+            task(1, past, false),
+            task(2, past, true),
+            task(3, None, true),
+        ];
+        let view: Vec<&Task> = tasks.iter().collect();
+
+        let groups = group_tasks(&view, GroupBy::Relative, "main", today);
+        let ids = |label: &str| -> Vec<u64> {
+            groups
+                .iter()
+                .find(|(l, _)| l == label)
+                .map(|(_, tasks)| tasks.iter().map(|t| t.id).collect())
+                .unwrap_or_default()
+        };
+        assert_eq!(ids("Overdue"), vec![1]);
+        assert_eq!(ids("Done"), vec![2, 3]);
+        assert_eq!(ids("Someday"), Vec::<u64>::new());
+    }
+}
@@ -0,0 +1,404 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use crate::app::TaskParts;
+use crate::branch::is_default_branch;
+use crate::model::{ISO_DATE_FORMAT, Priority, Repeat, Task};
+use crate::util::parse_timestamp;
+
+const PRODID: &str = "-//clitodo//todo//EN";
+const BRANCH_PROP: &str = "X-CLITODO-BRANCH";
+const ARCHIVED_PROP: &str = "X-CLITODO-ARCHIVED";
+/// Bookkeeping properties that carry nothing worth keeping on import.
+const IGNORED_PROPS: [&str; 2] = ["DTSTAMP", "SEQUENCE"];
+
+/// An iCalendar (RFC 5545) calendar with one VTODO per task, CRLF line endings.
+pub fn write_calendar(view: &[&Task]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{PRODID}"));
+    for task in view {
+        write_todo(&mut out, task, &stamp);
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+fn write_todo(out: &mut String, task: &Task, stamp: &str) {
+    // Tasks without a uid still need a stable UID for calendar clients.
+    let uid = task
+        .uid
+        .clone()
+        .unwrap_or_else(|| format!("{}-{}@clitodo", task.branch, task.id));
+    push_line(out, "BEGIN:VTODO");
+    push_line(out, &format!("UID:{}", escape_text(&uid)));
+    push_line(out, &format!("DTSTAMP:{stamp}"));
+    if let Some(created) = utc_stamp(&task.created_at) {
+        push_line(out, &format!("CREATED:{created}"));
+    }
+    if let Some(modified) = task.updated_at.as_deref().and_then(utc_stamp) {
+        push_line(out, &format!("LAST-MODIFIED:{modified}"));
+    }
+    push_line(out, &format!("SUMMARY:{}", escape_text(&task.title)));
+    if let Some(content) = task.content.as_deref() {
+        push_line(out, &format!("DESCRIPTION:{}", escape_text(content)));
+    }
+    if let Some(due) = task.due {
+        push_line(out, &format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+    }
+    if let Some(priority) = task.priority {
+        let value = match priority {
+            Priority::High => 1,
+            Priority::Medium => 5,
+            Priority::Low => 9,
+        };
+        push_line(out, &format!("PRIORITY:{value}"));
+    }
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|t| escape_text(t)).collect();
+        push_line(out, &format!("CATEGORIES:{}", tags.join(",")));
+    }
+    let status = if task.done {
+        "COMPLETED"
+    } else {
+        "NEEDS-ACTION"
+    };
+    push_line(out, &format!("STATUS:{status}"));
+    if task.done
+        && let Some(completed) = task.completed_at.as_deref().and_then(utc_stamp)
+    {
+        push_line(out, &format!("COMPLETED:{completed}"));
+    }
+    if let Some(repeat) = task.repeat {
+        let freq = match repeat {
+            Repeat::Daily => "DAILY",
+            Repeat::Weekly => "WEEKLY",
+            Repeat::Monthly => "MONTHLY",
+        };
+        push_line(out, &format!("RRULE:FREQ={freq}"));
+    }
+    if !is_default_branch(&task.branch) {
+        push_line(out, &format!("{BRANCH_PROP}:{}", escape_text(&task.branch)));
+    }
+    if task.archived {
+        push_line(out, &format!("{ARCHIVED_PROP}:TRUE"));
+    }
+    push_line(out, "END:VTODO");
+}
+
+/// Append a content line, folded at 75 octets.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Split a raw list value (`CATEGORIES`) on its unescaped commas.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for ch in value.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(ch);
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == ',' {
+            items.push(unescape_text(&current));
+            current.clear();
+        } else {
+            current.push(ch);
+        }
+    }
+    items.push(unescape_text(&current));
+    items
+}
+
+pub(crate) fn utc_stamp(value: &str) -> Option<String> {
+    parse_timestamp(value).map(|dt| dt.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string())
+}
+
+/// A DATE or DATE-TIME value; floating and `TZID` times are read as local time.
+pub(crate) fn parse_date_time(value: &str) -> Option<DateTime<Local>> {
+    let value = value.trim();
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(Utc.from_utc_datetime(&naive).with_timezone(&Local));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+/// One property line: `NAME;PARAM=…:value`.
+struct Property {
+    name: String,
+    value: String,
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first colon outside a quoted parameter value.
+    let mut quoted = false;
+    let split = line.char_indices().find_map(|(i, ch)| match ch {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let head = &line[..split];
+    let name = head.split(';').next().unwrap_or(head).trim();
+    if name.is_empty() {
+        return None;
+    }
+    Some(Property {
+        name: name.to_ascii_uppercase(),
+        value: line[split + 1..].to_string(),
+    })
+}
+
+/// Join folded lines back into content lines.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(rest) = raw.strip_prefix([' ', '\t'])
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(rest);
+        } else if !raw.trim().is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// Read every VTODO (and VEVENT with `events`) in `text` as task parts.
+pub(crate) fn parse_calendar(text: &str, events: bool) -> Result<Vec<TaskParts>, String> {
+    let mut parts = Vec::new();
+    let mut in_calendar = false;
+    let mut seen_calendar = false;
+    // The component being read, and how deep we are in components inside it (VALARM).
+    let mut current: Option<(String, Vec<Property>)> = None;
+    let mut nested = 0usize;
+
+    for line in unfold(text) {
+        let Some(prop) = parse_property(&line) else {
+            continue;
+        };
+        let component = prop.value.trim().to_ascii_uppercase();
+        match prop.name.as_str() {
+            "BEGIN" if current.is_some() => nested += 1,
+            "BEGIN" if component == "VCALENDAR" => {
+                in_calendar = true;
+                seen_calendar = true;
+            }
+            "BEGIN" if in_calendar && (component == "VTODO" || component == "VEVENT") => {
+                current = Some((component, Vec::new()));
+            }
+            "BEGIN" if in_calendar => nested += 1,
+            "END" if nested > 0 => nested -= 1,
+            "END" if current.is_some() => {
+                let (kind, props) = current.take().unwrap_or_default();
+                if kind != component {
+                    return Err(format!("BEGIN:{kind} ends with END:{component}"));
+                }
+                if kind == "VTODO" || events {
+                    parts.push(component_parts(&kind, props));
+                }
+            }
+            "END" if component == "VCALENDAR" => in_calendar = false,
+            _ if nested > 0 => {}
+            _ => {
+                if let Some((_, props)) = current.as_mut() {
+                    props.push(prop);
+                }
+            }
+        }
+    }
+
+    if !seen_calendar {
+        return Err("no VCALENDAR found".to_string());
+    }
+    if let Some((kind, _)) = current {
+        return Err(format!("BEGIN:{kind} has no END"));
+    }
+    Ok(parts)
+}
+
+fn component_parts(kind: &str, props: Vec<Property>) -> TaskParts {
+    let mut parts = TaskParts::default();
+    let mut description: Option<String> = None;
+    let mut extra: Vec<String> = Vec::new();
+    let date_prop = if kind == "VEVENT" { "DTSTART" } else { "DUE" };
+
+    for prop in props {
+        let value = prop.value.as_str();
+        let mapped = match prop.name.as_str() {
+            "UID" => {
+                parts.uid = Some(unescape_text(value)).filter(|v| !v.trim().is_empty());
+                true
+            }
+            "SUMMARY" => {
+                parts.title = unescape_text(value);
+                true
+            }
+            "DESCRIPTION" => {
+                description = Some(unescape_text(value));
+                true
+            }
+            "CATEGORIES" => {
+                parts.tags.extend(split_list(value));
+                true
+            }
+            "PRIORITY" => match value.trim().parse::<u8>() {
+                Ok(0) => true,
+                Ok(1..=4) => {
+                    parts.priority = Some(Priority::High);
+                    true
+                }
+                Ok(5) => {
+                    parts.priority = Some(Priority::Medium);
+                    true
+                }
+                Ok(6..=9) => {
+                    parts.priority = Some(Priority::Low);
+                    true
+                }
+                _ => false,
+            },
+            "STATUS" => match value.trim().to_ascii_uppercase().as_str() {
+                "COMPLETED" => {
+                    parts.done = Some(true);
+                    true
+                }
+                "NEEDS-ACTION" | "CONFIRMED" => true,
+                _ => false,
+            },
+            "RRULE" => match parse_rrule(value) {
+                Some(repeat) => {
+                    parts.repeat = Some(repeat);
+                    true
+                }
+                None => false,
+            },
+            "CREATED" => set_timestamp(&mut parts.created_at, value),
+            "LAST-MODIFIED" => set_timestamp(&mut parts.updated_at, value),
+            "COMPLETED" => set_timestamp(&mut parts.completed_at, value),
+            name if name == date_prop => match parse_date_time(value) {
+                Some(dt) => {
+                    parts.due = Some(dt.date_naive().format(ISO_DATE_FORMAT).to_string());
+                    true
+                }
+                None => false,
+            },
+            BRANCH_PROP => {
+                parts.branch = Some(unescape_text(value));
+                true
+            }
+            ARCHIVED_PROP => {
+                parts.archived = Some(value.trim().eq_ignore_ascii_case("TRUE"));
+                true
+            }
+            name => IGNORED_PROPS.contains(&name),
+        };
+        if !mapped {
+            extra.push(format!("{}: {}", prop.name, unescape_text(value)));
+        }
+    }
+
+    if parts.completed_at.is_some() && parts.done.is_none() {
+        parts.done = Some(true);
+    }
+    // Properties with no task field are kept as `NAME: value` lines in the content.
+    let mut content = description.unwrap_or_default();
+    if !extra.is_empty() {
+        if !content.is_empty() {
+            content.push_str("\n\n");
+        }
+        content.push_str(&extra.join("\n"));
+    }
+    parts.content = Some(content).filter(|c| !c.is_empty());
+    parts
+}
+
+fn set_timestamp(field: &mut Option<String>, value: &str) -> bool {
+    match parse_date_time(value) {
+        Some(dt) => {
+            *field = Some(dt.to_rfc3339());
+            true
+        }
+        None => false,
+    }
+}
+
+/// `FREQ=DAILY|WEEKLY|MONTHLY` with an interval of 1; anything else has no `Repeat`.
+fn parse_rrule(value: &str) -> Option<Repeat> {
+    let mut repeat = None;
+    for rule in value.split(';') {
+        let (key, val) = rule.split_once('=')?;
+        match key.trim().to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                repeat = Some(match val.trim().to_ascii_uppercase().as_str() {
+                    "DAILY" => Repeat::Daily,
+                    "WEEKLY" => Repeat::Weekly,
+                    "MONTHLY" => Repeat::Monthly,
+                    _ => return None,
+                });
+            }
+            "INTERVAL" if val.trim() == "1" => {}
+            "WKST" => {}
+            _ => return None,
+        }
+    }
+    repeat
+}
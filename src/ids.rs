@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::error::TodoError;
+use crate::model::{IdScope, Task};
+
+/// Number of uid characters shown as a task's short handle.
+pub const SHORT_UID_LEN: usize = 8;
+
+pub fn short_uid(uid: &str) -> &str {
+    match uid.char_indices().nth(SHORT_UID_LEN) {
+        Some((idx, _)) => &uid[..idx],
+        None => uid,
+    }
+}
+
+/// Resolve a task reference (numeric id or unique uid prefix) to an index into `tasks`.
+///
+/// Numeric ids win over uid prefixes. When ids are branch-scoped and several
+/// branches share the same id, the task in `current_branch` is preferred.
+pub fn resolve_task_ref(
+    tasks: &[Task],
+    key: &str,
+    current_branch: &str,
+) -> Result<usize, TodoError> {
+    let key = key.trim().trim_start_matches('#');
+    if key.is_empty() {
+        return Err(TodoError::Usage("task id cannot be empty".to_string()));
+    }
+
+    if let Ok(id) = key.parse::<u64>() {
+        let matches: Vec<usize> = tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.id == id)
+            .map(|(idx, _)| idx)
+            .collect();
+        match matches.as_slice() {
+            [idx] => return Ok(*idx),
+            [] => {}
+            _ => {
+                if let Some(idx) = matches
+                    .iter()
+                    .find(|idx| tasks[**idx].branch.eq_ignore_ascii_case(current_branch))
+                {
+                    return Ok(*idx);
+                }
+                let branches: Vec<&str> = matches
+                    .iter()
+                    .map(|idx| tasks[*idx].branch.as_str())
+                    .collect();
+                return Err(TodoError::Usage(format!(
+                    "id {id} exists in several branches ({}); use a uid prefix or switch branch",
+                    branches.join(", ")
+                )));
+            }
+        }
+    }
+
+    let prefix = key.to_lowercase();
+    let matches: Vec<usize> = tasks
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| {
+            t.uid
+                .as_deref()
+                .is_some_and(|uid| uid.to_lowercase().starts_with(&prefix))
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+
+    match matches.as_slice() {
+        [idx] => Ok(*idx),
+        [] => Err(TodoError::NotFound(format!("no task with id {key}"))),
+        _ => {
+            let ids: Vec<String> = matches
+                .iter()
+                .map(|idx| format!("#{}", tasks[*idx].id))
+                .collect();
+            Err(TodoError::Usage(format!(
+                "uid prefix '{key}' is ambiguous (matches {})",
+                ids.join(", ")
+            )))
+        }
+    }
+}
+
+/// Give every task without a uid a fresh one. Returns how many were added.
+pub fn backfill_uids(tasks: &mut [Task]) -> usize {
+    let mut added = 0usize;
+    for task in tasks.iter_mut() {
+        if task.uid.is_none() {
+            task.uid = Some(Uuid::new_v4().to_string());
+            added += 1;
+        }
+    }
+    added
+}
+
+/// Compact ids to 1..n, per branch for branch scope or across all tasks for global scope.
+/// Relative order and uids are preserved. Returns how many ids changed.
+pub fn renumber_ids(tasks: &mut [Task], scope: IdScope) -> usize {
+    let mut order: Vec<usize> = (0..tasks.len()).collect();
+    order.sort_by(|a, b| {
+        tasks[*a]
+            .id
+            .cmp(&tasks[*b].id)
+            .then_with(|| tasks[*a].created_at.cmp(&tasks[*b].created_at))
+    });
+
+    let mut counters: HashMap<String, u64> = HashMap::new();
+    let mut changed = 0usize;
+    for idx in order {
+        let key = match scope {
+            IdScope::Global => String::new(),
+            IdScope::Branch => tasks[idx].branch.to_lowercase(),
+        };
+        let next = counters.entry(key).or_insert(0);
+        *next += 1;
+        if tasks[idx].id != *next {
+            tasks[idx].id = *next;
+            changed += 1;
+        }
+    }
+    changed
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::calendar::Occurrence;
+use crate::model::{AppConfig, Task, ThemeConfig, ThemeElement, UserProfile};
+use crate::theme::{preset_spec, value_name};
+
+/// Print `doc` as the command's JSON document (`--json`).
+pub fn emit<T: Serialize>(doc: &T) {
+    let text = serde_json::to_string_pretty(doc).expect("serialize json output");
+    println!("{text}");
+}
+
+/// `list`, `list-all`, `list-done`, `search` and the `--dry-run` previews.
+#[derive(Serialize)]
+pub struct TaskList<'a> {
+    pub tasks: Vec<&'a Task>,
+}
+
+impl<'a> TaskList<'a> {
+    pub fn new(tasks: &[&'a Task]) -> Self {
+        Self {
+            tasks: tasks.to_vec(),
+        }
+    }
+}
+
+/// A task with its urgency score (`next`).
+#[derive(Serialize)]
+pub struct RankedTask<'a> {
+    #[serde(flatten)]
+    pub task: &'a Task,
+    pub urgency: f64,
+}
+
+#[derive(Serialize)]
+pub struct RankedList<'a> {
+    pub tasks: Vec<RankedTask<'a>>,
+}
+
+/// A task on a date: its stored due date, or a projected recurrence.
+#[derive(Serialize)]
+pub struct OccurrenceEntry<'a> {
+    #[serde(flatten)]
+    pub task: &'a Task,
+    pub date: NaiveDate,
+    pub projected: bool,
+}
+
+impl<'a> From<&Occurrence<'a>> for OccurrenceEntry<'a> {
+    fn from(occurrence: &Occurrence<'a>) -> Self {
+        Self {
+            task: occurrence.task,
+            date: occurrence.date,
+            projected: occurrence.projected,
+        }
+    }
+}
+
+/// `list-repeat`; `projections` only with `--project`.
+#[derive(Serialize)]
+pub struct RepeatList<'a> {
+    pub tasks: Vec<&'a Task>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projections: Option<Vec<OccurrenceEntry<'a>>>,
+}
+
+/// `split-due`.
+#[derive(Serialize)]
+pub struct DueSplit<'a> {
+    pub due: Vec<&'a Task>,
+    pub no_due: Vec<&'a Task>,
+}
+
+/// `reminders`.
+#[derive(Serialize)]
+pub struct Reminders<'a> {
+    pub overdue: Vec<&'a Task>,
+    pub today: Vec<&'a Task>,
+    pub upcoming: Vec<&'a Task>,
+}
+
+/// `calendar` and `agenda`: every occurrence in `from..=until`, by date.
+#[derive(Serialize)]
+pub struct Occurrences<'a> {
+    pub from: NaiveDate,
+    pub until: NaiveDate,
+    /// Open tasks due before `from` (`agenda` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overdue: Option<Vec<&'a Task>>,
+    pub occurrences: Vec<OccurrenceEntry<'a>>,
+}
+
+/// `view`.
+#[derive(Serialize)]
+pub struct TaskView<'a> {
+    pub task: &'a Task,
+    pub urgency: f64,
+}
+
+/// What a mutating command changed. `tasks` holds the affected tasks as stored
+/// afterwards, or as they were for commands that remove them.
+#[derive(Serialize)]
+pub struct Action {
+    pub action: &'static str,
+    pub count: usize,
+    pub tasks: Vec<Task>,
+    /// Recurrences created by completing repeating tasks.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub created: Vec<Task>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Rows `import` could not use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<usize>,
+}
+
+impl Action {
+    pub fn new(action: &'static str, tasks: Vec<Task>) -> Self {
+        Self {
+            action,
+            count: tasks.len(),
+            tasks,
+            created: Vec::new(),
+            from: None,
+            to: None,
+            skipped: None,
+        }
+    }
+
+    /// `from` and `to` branches for the branch and move commands.
+    pub fn between(mut self, from: &str, to: &str) -> Self {
+        self.from = Some(from.to_string());
+        self.to = Some(to.to_string());
+        self
+    }
+}
+
+/// A bulk command's `--dry-run`.
+#[derive(Serialize)]
+pub struct DryRun<'a> {
+    pub action: &'static str,
+    pub dry_run: bool,
+    pub count: usize,
+    pub tasks: Vec<&'a Task>,
+}
+
+/// `branch`.
+#[derive(Serialize)]
+pub struct Branches {
+    pub current: String,
+    pub branches: Vec<String>,
+}
+
+/// `stats`.
+#[derive(Serialize)]
+pub struct Stats {
+    pub total: usize,
+    pub open: usize,
+    pub done: usize,
+    pub overdue: usize,
+    pub due_today: usize,
+    pub archived: usize,
+    pub repeating: usize,
+    pub branches: Vec<BranchStats>,
+}
+
+#[derive(Serialize)]
+pub struct BranchStats {
+    pub name: String,
+    pub current: bool,
+    pub total: usize,
+    pub done: usize,
+    pub archived: usize,
+}
+
+/// `config`.
+#[derive(Serialize)]
+pub struct Config<'a> {
+    pub updated: bool,
+    pub config: &'a AppConfig,
+}
+
+/// `settings`.
+#[derive(Serialize)]
+pub struct Settings<'a> {
+    pub updated: bool,
+    pub settings: &'a UserProfile,
+}
+
+/// `theme`: every element's effective style spec.
+#[derive(Serialize)]
+pub struct Theme<'a> {
+    pub preset: String,
+    pub styles: BTreeMap<String, String>,
+    pub custom: Vec<String>,
+    pub tag_colors: &'a BTreeMap<String, String>,
+    pub branch_colors: &'a BTreeMap<String, String>,
+}
+
+impl<'a> Theme<'a> {
+    pub fn new(config: &'a ThemeConfig) -> Self {
+        let styles = ThemeElement::value_variants()
+            .iter()
+            .map(|el| {
+                let spec = config
+                    .styles
+                    .get(el)
+                    .cloned()
+                    .unwrap_or_else(|| preset_spec(config.preset, *el).to_string());
+                (value_name(*el), spec)
+            })
+            .collect();
+        Self {
+            preset: value_name(config.preset),
+            styles,
+            custom: config.styles.keys().map(|el| value_name(*el)).collect(),
+            tag_colors: &config.tag_colors,
+            branch_colors: &config.branch_colors,
+        }
+    }
+}
+
+/// `saved`: every saved command after the change, or the one asked for by `saved show`.
+#[derive(Serialize)]
+pub struct SavedCommands<'a> {
+    pub saved_commands: BTreeMap<&'a str, &'a [String]>,
+}
@@ -1,12 +1,28 @@
 pub mod app;
 pub mod branch;
+pub mod calendar;
 pub mod cli;
 pub mod display;
 pub mod edit;
+pub mod error;
 pub mod export;
+pub mod group;
+pub mod ics;
+pub mod ids;
+pub mod json;
+pub mod markdown;
 pub mod model;
+pub mod output;
+pub mod quick;
+pub mod shell;
 pub mod sort;
 pub mod storage;
+pub mod taskwarrior;
+pub mod template;
+pub mod theme;
+pub mod todotxt;
+pub mod tui;
+pub mod urgency;
 pub mod util;
 
 pub use app::run;
@@ -0,0 +1,446 @@
+use crate::app::TaskParts;
+use crate::cli::{MarkdownHeadings, MarkdownNested};
+use crate::model::{Priority, Repeat};
+
+/// What a list line nests under: a task being imported, or a checklist item
+/// kept as a content line of task `owner`.
+#[derive(Clone, Copy)]
+enum Open {
+    Task { indent: usize, index: usize },
+    Inline { indent: usize, owner: usize },
+}
+
+impl Open {
+    fn indent(self) -> usize {
+        match self {
+            Open::Task { indent, .. } | Open::Inline { indent, .. } => indent,
+        }
+    }
+
+    fn owner(self) -> usize {
+        match self {
+            Open::Task { index, .. } => index,
+            Open::Inline { owner, .. } => owner,
+        }
+    }
+}
+
+/// A task read so far; `content` is the `content:` bullet, `lines` everything
+/// else nested under the item.
+struct Item {
+    parts: TaskParts,
+    content: Option<String>,
+    lines: Vec<String>,
+}
+
+/// Read the GitHub-style checklist items (`- [ ] title`, `- [x] title`, `1. [ ] title`)
+/// in `text`. Sub-bullets in the `key: value` form `export markdown` writes set fields;
+/// other nested lines become content. Fenced code blocks are skipped.
+pub(crate) fn parse_checklists(
+    text: &str,
+    headings: MarkdownHeadings,
+    nested: MarkdownNested,
+) -> Vec<TaskParts> {
+    let mut items: Vec<Item> = Vec::new();
+    let mut titles: Vec<(usize, String)> = Vec::new();
+    let mut open: Vec<Open> = Vec::new();
+    // Set after a `content:` bullet, whose deeper lines continue the value.
+    let mut content_indent: Option<usize> = None;
+    // The opening fence of the code block we are in.
+    let mut fence: Option<&str> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some(open_fence) = fence {
+            if closes_fence(trimmed, open_fence) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = indent_width(line);
+
+        if let Some(ci) = content_indent {
+            if indent > ci
+                && let Some(top) = open.last()
+                && let Some(content) = items[top.owner()].content.as_mut()
+            {
+                content.push('\n');
+                content.push_str(trimmed);
+                continue;
+            }
+            content_indent = None;
+        }
+
+        if let Some(open_fence) = opening_fence(trimmed) {
+            fence = Some(open_fence);
+            continue;
+        }
+
+        if indent == 0
+            && let Some((level, title)) = parse_heading(trimmed)
+        {
+            titles.retain(|(l, _)| *l < level);
+            titles.push((level, title.to_string()));
+            open.clear();
+            continue;
+        }
+
+        while open.last().is_some_and(|top| top.indent() >= indent) {
+            open.pop();
+        }
+
+        if let Some((done, title)) = parse_checkbox(trimmed) {
+            match open.last().copied() {
+                Some(parent) if nested == MarkdownNested::Content => {
+                    let owner = parent.owner();
+                    let depth = indent.saturating_sub(owner_indent(&open, owner));
+                    items[owner]
+                        .lines
+                        .push(format!("{}{trimmed}", " ".repeat(depth.saturating_sub(2))));
+                    open.push(Open::Inline { indent, owner });
+                }
+                parent => {
+                    let mut parts = TaskParts {
+                        title: strip_id(title).to_string(),
+                        done: Some(done),
+                        ..TaskParts::default()
+                    };
+                    apply_headings(&mut parts, &titles, headings);
+                    let mut lines = Vec::new();
+                    if let Some(parent) = parent {
+                        let owner = &items[parent.owner()].parts.title;
+                        lines.push(format!("Subtask of: {owner}"));
+                    }
+                    items.push(Item {
+                        parts,
+                        content: None,
+                        lines,
+                    });
+                    open.push(Open::Task {
+                        indent,
+                        index: items.len() - 1,
+                    });
+                }
+            }
+            continue;
+        }
+
+        let Some(top) = open.last().copied() else {
+            // Text outside a checklist is just notes.
+            continue;
+        };
+        let bullet = parse_bullet(trimmed);
+        if let (Open::Task { index, .. }, Some(body)) = (top, bullet)
+            && let Some((key, value)) = body.split_once(':')
+            && apply_field(&mut items[index], key.trim(), value.trim())
+        {
+            if key.trim() == "content" {
+                content_indent = Some(indent);
+            }
+            continue;
+        }
+        let owner = top.owner();
+        let depth = indent.saturating_sub(owner_indent(&open, owner));
+        items[owner]
+            .lines
+            .push(format!("{}{trimmed}", " ".repeat(depth.saturating_sub(2))));
+    }
+
+    items
+        .into_iter()
+        .map(|item| {
+            let mut parts = item.parts;
+            let mut content: Vec<String> = item.content.into_iter().collect();
+            content.extend(item.lines);
+            parts.content = Some(content.join("\n")).filter(|c| !c.is_empty());
+            parts
+        })
+        .collect()
+}
+
+/// The indent of the task item `owner`, so inline lines keep relative nesting.
+fn owner_indent(open: &[Open], owner: usize) -> usize {
+    open.iter()
+        .find_map(|o| match o {
+            Open::Task { indent, index } if *index == owner => Some(*indent),
+            _ => None,
+        })
+        .unwrap_or(0)
+}
+
+/// Set a field from a `key: value` sub-bullet; false for keys `export markdown`
+/// doesn't write or values that don't parse.
+fn apply_field(item: &mut Item, key: &str, value: &str) -> bool {
+    let parts = &mut item.parts;
+    match key {
+        "branch" if !value.is_empty() => parts.branch = Some(value.to_string()),
+        "due" if !value.is_empty() => parts.due = Some(value.to_string()),
+        "priority" => match value.parse::<Priority>() {
+            Ok(p) => parts.priority = Some(p),
+            Err(_) => return false,
+        },
+        "repeat" => match value.parse::<Repeat>() {
+            Ok(r) => parts.repeat = Some(r),
+            Err(_) => return false,
+        },
+        "tags" => parts.tags.extend(
+            value
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty()),
+        ),
+        "uid" if !value.is_empty() => parts.uid = Some(value.to_string()),
+        "archived" => match value {
+            "true" => parts.archived = Some(true),
+            "false" => parts.archived = Some(false),
+            _ => return false,
+        },
+        "content" => item.content = Some(value.to_string()),
+        _ => return false,
+    }
+    true
+}
+
+fn apply_headings(parts: &mut TaskParts, titles: &[(usize, String)], mode: MarkdownHeadings) {
+    match mode {
+        MarkdownHeadings::Branch => {
+            parts.branch = titles.last().map(|(_, title)| title.clone());
+        }
+        MarkdownHeadings::Tag => {
+            parts.tags = titles.iter().map(|(_, title)| title.clone()).collect();
+        }
+        MarkdownHeadings::Ignore => {}
+    }
+}
+
+/// Tabs count as four columns.
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// `## Title` → `(2, "Title")`.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let title = line[level..]
+        .strip_prefix(' ')?
+        .trim()
+        .trim_end_matches('#')
+        .trim();
+    (!title.is_empty()).then_some((level, title))
+}
+
+/// The fence (three or more backticks or tildes) that opens a code block on `line`.
+fn opening_fence(line: &str) -> Option<&str> {
+    ['`', '~'].into_iter().find_map(|c| {
+        let len = line.len() - line.trim_start_matches(c).len();
+        (len >= 3).then(|| &line[..len])
+    })
+}
+
+/// A closing fence uses the same character, at least as many times, and nothing else.
+fn closes_fence(line: &str, open: &str) -> bool {
+    opening_fence(line).is_some_and(|fence| {
+        fence.starts_with(&open[..1]) && fence.len() >= open.len() && fence == line
+    })
+}
+
+/// The text after a `-`, `*` or `+` list marker, or an ordered one like `1.` or `1)`.
+fn parse_bullet(line: &str) -> Option<&str> {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return Some(rest);
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if !(1..=9).contains(&digits) {
+        return None;
+    }
+    line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))
+}
+
+/// `- [x] title` → `(true, "title")`.
+fn parse_checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = parse_bullet(line)?;
+    let (done, title) = if let Some(title) = rest.strip_prefix("[ ]") {
+        (false, title)
+    } else if let Some(title) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, title)
+    } else {
+        return None;
+    };
+    Some((done, title.trim()))
+}
+
+/// Drop the ` (#12)` id suffix `export markdown` adds.
+fn strip_id(title: &str) -> &str {
+    if let Some(start) = title.rfind(" (#")
+        && let Some(id) = title[start + 3..].strip_suffix(')')
+        && !id.is_empty()
+        && id.chars().all(|c| c.is_ascii_digit())
+    {
+        return &title[..start];
+    }
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::write_markdown;
+    use crate::model::Task;
+
+    fn parse(text: &str) -> Vec<TaskParts> {
+        parse_checklists(text, MarkdownHeadings::Tag, MarkdownNested::Content)
+    }
+
+    fn titles(parts: &[TaskParts]) -> Vec<&str> {
+        parts.iter().map(|p| p.title.as_str()).collect()
+    }
+
+    #[test]
+    fn reads_back_export_markdown() {
+        let task = Task {
+            id: 12,
+            uid: Some("7f0c7a52-5b8e-4c57-9c1a-2f3c1e0d9a11".to_string()),
+            title: "Water the plants".to_string(),
+            content: Some("first line\n- [ ] not a task\nthird: line".to_string()),
+            tags: vec!["home".to_string(), "errand".to_string()],
+            due: "2026-10-24".parse().ok(),
+            priority: Some(Priority::High),
+            repeat: Some(Repeat::Weekly),
+            branch: "work".to_string(),
+            archived: true,
+            done: true,
+            created_at: "2026-10-18T09:30:00+02:00".to_string(),
+            updated_at: None,
+            completed_at: None,
+        };
+        let plain = Task {
+            id: 13,
+            uid: None,
+            title: "Call (#mom)".to_string(),
+            content: None,
+            tags: Vec::new(),
+            due: None,
+            priority: None,
+            repeat: None,
+            branch: crate::model::DEFAULT_BRANCH.to_string(),
+            archived: false,
+            done: false,
+            ..task.clone()
+        };
+
+        let parts = parse(&write_markdown(&[&task, &plain]));
+        assert_eq!(titles(&parts), vec!["Water the plants", "Call (#mom)"]);
+        let first = &parts[0];
+        assert_eq!(first.uid, task.uid);
+        assert_eq!(first.content, task.content);
+        assert_eq!(first.tags, task.tags);
+        assert_eq!(first.due.as_deref(), Some("2026-10-24"));
+        assert_eq!(first.priority, Some(Priority::High));
+        assert!(matches!(first.repeat, Some(Repeat::Weekly)));
+        assert_eq!(first.branch.as_deref(), Some("work"));
+        assert_eq!(first.done, Some(true));
+        assert_eq!(first.archived, Some(true));
+
+        let second = &parts[1];
+        assert_eq!(second.done, Some(false));
+        assert_eq!(second.branch, None);
+        assert_eq!(second.content, None);
+    }
+
+    #[test]
+    fn skips_fenced_code_blocks() {
+        let text = "\
+- [ ] real
+```md
+- [ ] in code block
+# not a heading
+```
+~~~~
+- [ ] in tildes
+~~~
+- [ ] still in tildes
+~~~~
+- [x] also real
+";
+        let parts = parse(text);
+        assert_eq!(titles(&parts), vec!["real", "also real"]);
+        assert!(
+            parts
+                .iter()
+                .all(|p| p.tags.is_empty() && p.content.is_none())
+        );
+    }
+
+    #[test]
+    fn reads_ordered_list_checkboxes() {
+        let parts = parse("1. [ ] first\n2) [x] second\n10. plain item\n");
+        assert_eq!(titles(&parts), vec!["first", "second"]);
+        assert_eq!(parts[1].done, Some(true));
+    }
+
+    #[test]
+    fn maps_headings_to_tags_or_branches() {
+        let text = "# Home\n- [ ] Sweep\n## Garden\n- [ ] Mow\n# Work\n- [ ] Report\n";
+
+        let parts = parse(text);
+        let tags: Vec<Vec<String>> = parts.iter().map(|p| p.tags.clone()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                vec!["Home".to_string()],
+                vec!["Home".to_string(), "Garden".to_string()],
+                vec!["Work".to_string()],
+            ]
+        );
+
+        let parts = parse_checklists(text, MarkdownHeadings::Branch, MarkdownNested::Content);
+        let branches: Vec<Option<&str>> = parts.iter().map(|p| p.branch.as_deref()).collect();
+        assert_eq!(branches, vec![Some("Home"), Some("Garden"), Some("Work")]);
+
+        let parts = parse_checklists(text, MarkdownHeadings::Ignore, MarkdownNested::Content);
+        assert!(
+            parts
+                .iter()
+                .all(|p| p.tags.is_empty() && p.branch.is_none())
+        );
+    }
+
+    #[test]
+    fn nests_items_as_content_or_tasks() {
+        let text = "- [ ] Trip\n  - [x] Book hotel\n    - [ ] Pay deposit\n  notes\n- [ ] Other\n";
+
+        let parts = parse(text);
+        assert_eq!(titles(&parts), vec!["Trip", "Other"]);
+        assert_eq!(
+            parts[0].content.as_deref(),
+            Some("- [x] Book hotel\n  - [ ] Pay deposit\nnotes")
+        );
+
+        let parts = parse_checklists(text, MarkdownHeadings::Tag, MarkdownNested::Tasks);
+        assert_eq!(
+            titles(&parts),
+            vec!["Trip", "Book hotel", "Pay deposit", "Other"]
+        );
+        assert_eq!(parts[1].content.as_deref(), Some("Subtask of: Trip"));
+        assert_eq!(parts[2].content.as_deref(), Some("Subtask of: Book hotel"));
+        assert_eq!(parts[0].content.as_deref(), Some("notes"));
+    }
+}
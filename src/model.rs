@@ -61,6 +61,10 @@ fn default_list_view() -> ListViewStyle {
     ListViewStyle::Table
 }
 
+fn default_board_group() -> BoardGroup {
+    BoardGroup::Status
+}
+
 pub fn default_list_columns() -> Vec<ListColumn> {
     vec![ListColumn::Due, ListColumn::Priority]
 }
@@ -71,9 +75,64 @@ pub enum ListViewStyle {
     Compact,
     Cards,
     Classic,
+    Board,
+    /// One plain sentence per task, for screen readers
+    Accessible,
+}
+
+/// How due dates are written.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+pub enum DateStyle {
+    /// The date itself, in the configured date format
+    #[default]
+    Absolute,
+    /// Distance from today: `today`, `in 3d`, `2d overdue`, `5d ago`
+    Relative,
+    /// Words for the next week (`tomorrow`, `Fri`), the date after that; overdue stays relative
+    Human,
+}
+
+/// Places that can use their own date style.
+#[derive(
+    Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum DateView {
+    Table,
+    Compact,
+    Cards,
+    Classic,
+    Board,
+    Agenda,
+    /// `todo view` and the TUI's detail pane
+    Detail,
+}
+
+impl From<ListViewStyle> for DateView {
+    fn from(style: ListViewStyle) -> Self {
+        match style {
+            ListViewStyle::Table => DateView::Table,
+            ListViewStyle::Compact => DateView::Compact,
+            ListViewStyle::Cards => DateView::Cards,
+            ListViewStyle::Classic => DateView::Classic,
+            ListViewStyle::Board => DateView::Board,
+            // Sentences speak dates their own way; the TUI lists it like the classic view.
+            ListViewStyle::Accessible => DateView::Classic,
+        }
+    }
 }
 
+/// What the columns of the board view represent.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+pub enum BoardGroup {
+    Status,
+    Priority,
+    Branch,
+    Tag,
+}
+
+#[derive(
+    Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq, PartialOrd, Ord,
+)]
 pub enum ListColumn {
     Due,
     Priority,
@@ -81,6 +140,37 @@ pub enum ListColumn {
     Tags,
     Repeat,
     Content,
+    Urgency,
+    Created,
+    Updated,
+    Completed,
+    Uid,
+    /// `todo` or `done`
+    Status,
+    /// Time since the task was created
+    Age,
+    Id,
+}
+
+/// How text sits in a table column.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+pub enum ColumnAlign {
+    Left,
+    Right,
+    Center,
+}
+
+/// User overrides for one table column.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ColumnSettings {
+    /// Width in terminal columns; the column's own default when unset.
+    #[serde(default)]
+    pub width: Option<usize>,
+    #[serde(default)]
+    pub align: Option<ColumnAlign>,
+    /// Grow with the title into width the fixed columns leave over.
+    #[serde(default)]
+    pub flex: bool,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum)]
@@ -128,8 +218,29 @@ pub struct UserProfile {
     pub list_view: ListViewStyle,
     #[serde(default = "default_list_columns")]
     pub list_columns: Vec<ListColumn>,
+    #[serde(default)]
+    pub column_settings: BTreeMap<ListColumn, ColumnSettings>,
+    /// Wrap long table cells onto more lines instead of truncating them.
+    #[serde(default)]
+    pub wrap_rows: bool,
     #[serde(default = "default_auto_pager")]
     pub auto_pager: bool,
+    #[serde(default = "default_board_group")]
+    pub board_group: BoardGroup,
+    /// Tags shown as board columns when grouping by tag. Empty means every tag in view.
+    #[serde(default)]
+    pub board_tags: Vec<String>,
+    /// Output template used by `list` instead of the view style.
+    #[serde(default)]
+    pub list_format: Option<String>,
+    /// Date style for views without their own entry in `date_styles`.
+    #[serde(default)]
+    pub date_style: DateStyle,
+    #[serde(default)]
+    pub date_styles: BTreeMap<DateView, DateStyle>,
+    /// strftime format for absolute dates; ISO `%Y-%m-%d` when unset.
+    #[serde(default)]
+    pub date_format: Option<String>,
     /// User-defined saved commands (aliases). Key is the command name; value is argv tokens after `todo`.
     #[serde(default)]
     pub saved_commands: BTreeMap<String, Vec<String>>,
@@ -151,15 +262,41 @@ impl Default for UserProfile {
             encouragement_mode: default_encouragement_mode(),
             list_view: default_list_view(),
             list_columns: default_list_columns(),
+            column_settings: BTreeMap::new(),
+            wrap_rows: false,
             auto_pager: default_auto_pager(),
+            board_group: default_board_group(),
+            board_tags: Vec::new(),
+            list_format: None,
+            date_style: DateStyle::default(),
+            date_styles: BTreeMap::new(),
+            date_format: None,
             saved_commands: BTreeMap::new(),
             last_greeted: None,
         }
     }
 }
 
-fn default_sort() -> SortKey {
-    SortKey::Due
+impl UserProfile {
+    /// The date style `view` uses: its own setting, else the general one.
+    pub fn date_style_for(&self, view: DateView) -> DateStyle {
+        self.date_styles
+            .get(&view)
+            .copied()
+            .unwrap_or(self.date_style)
+    }
+
+    /// The strftime format for absolute dates.
+    pub fn date_format(&self) -> &str {
+        self.date_format.as_deref().unwrap_or(ISO_DATE_FORMAT)
+    }
+}
+
+/// Format used for absolute dates unless the user picks another.
+pub const ISO_DATE_FORMAT: &str = "%Y-%m-%d";
+
+fn default_sort() -> SortSpec {
+    SortKey::Due.into()
 }
 
 fn default_desc() -> bool {
@@ -182,10 +319,18 @@ fn default_use_uuid() -> bool {
     false
 }
 
+fn default_bulk_confirm_threshold() -> usize {
+    5
+}
+
+fn default_quick_add() -> bool {
+    false
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AppConfig {
     #[serde(default = "default_sort")]
-    pub default_sort: SortKey,
+    pub default_sort: SortSpec,
     #[serde(default = "default_desc")]
     pub default_desc: bool,
     #[serde(default = "default_color")]
@@ -196,6 +341,118 @@ pub struct AppConfig {
     pub id_scope: IdScope,
     #[serde(default = "default_use_uuid")]
     pub use_uuid: bool,
+    #[serde(default = "default_bulk_confirm_threshold")]
+    pub bulk_confirm_threshold: usize,
+    #[serde(default = "default_quick_add")]
+    pub quick_add: bool,
+    #[serde(default)]
+    pub urgency: UrgencyCoefficients,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+}
+
+/// Built-in color themes.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    Monochrome,
+}
+
+/// Parts of the output a theme can style.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+pub enum ThemeElement {
+    /// Table, group and day headers
+    Header,
+    /// Rules and calendar grid lines
+    Separator,
+    /// Ids, labels, meta lines and placeholders
+    Muted,
+    /// Titles of open tasks
+    Title,
+    /// Titles and cells of completed tasks
+    Done,
+    StatusOpen,
+    StatusDone,
+    Overdue,
+    DueToday,
+    DueLater,
+    PriorityHigh,
+    PriorityMedium,
+    PriorityLow,
+    /// Tags without a color of their own
+    Tag,
+    /// Branches without a color of their own
+    Branch,
+    /// Projected occurrences of recurring tasks
+    Projection,
+    /// Today's cell in the calendar
+    Today,
+    /// The daily greeting
+    Greeting,
+    /// The daily message
+    Message,
+}
+
+/// Style specs are words such as `bold red on black`, `bright-blue underline` or `#ff8800`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub preset: ThemePreset,
+    /// Per-element styles replacing the preset's.
+    pub styles: BTreeMap<ThemeElement, String>,
+    /// Keyed by lowercase tag.
+    pub tag_colors: BTreeMap<String, String>,
+    /// Keyed by lowercase branch name.
+    pub branch_colors: BTreeMap<String, String>,
+}
+
+/// Weights for the urgency score; see `urgency::urgency`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrgencyCoefficients {
+    pub due: f64,
+    pub overdue: f64,
+    pub priority_high: f64,
+    pub priority_medium: f64,
+    pub priority_low: f64,
+    pub age: f64,
+    pub tags: f64,
+    pub blocked: f64,
+    pub repeat: f64,
+    /// Extra weight added for each of these tags on a task.
+    pub tag_weights: BTreeMap<String, f64>,
+}
+
+impl Default for UrgencyCoefficients {
+    fn default() -> Self {
+        Self {
+            due: 12.0,
+            overdue: 3.0,
+            priority_high: 6.0,
+            priority_medium: 3.9,
+            priority_low: 1.8,
+            age: 2.0,
+            tags: 1.0,
+            blocked: -5.0,
+            repeat: -1.0,
+            tag_weights: BTreeMap::new(),
+        }
+    }
 }
 
 impl Default for AppConfig {
@@ -207,16 +464,100 @@ impl Default for AppConfig {
             reminder_days: default_reminder_days(),
             id_scope: default_id_scope(),
             use_uuid: default_use_uuid(),
+            bulk_confirm_threshold: default_bulk_confirm_threshold(),
+            quick_add: default_quick_add(),
+            urgency: UrgencyCoefficients::default(),
+            theme: ThemeConfig::default(),
         }
     }
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
 pub enum SortKey {
     Due,
     Priority,
     Created,
     Id,
+    Title,
+    Branch,
+    Status,
+    Updated,
+    Completed,
+    Urgency,
+}
+
+/// One key of a sort spec and its direction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SortField {
+    pub key: SortKey,
+    pub desc: bool,
+}
+
+/// Ordered sort keys, written as `priority,-due,title` (a leading `-` sorts that key descending).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SortSpec(pub Vec<SortField>);
+
+impl From<SortKey> for SortSpec {
+    fn from(key: SortKey) -> Self {
+        SortSpec(vec![SortField { key, desc: false }])
+    }
+}
+
+impl FromStr for SortSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields: Vec<SortField> = Vec::new();
+        for part in s.split(',') {
+            let part = part.trim();
+            let (name, desc) = match part.strip_prefix('-') {
+                Some(name) => (name, true),
+                None => (part.strip_prefix('+').unwrap_or(part), false),
+            };
+            let key = <SortKey as clap::ValueEnum>::from_str(name.trim(), true).map_err(|_| {
+                format!(
+                    "unknown sort key '{name}' (expected due, priority, created, updated, completed, urgency, id, title, branch or status)"
+                )
+            })?;
+            if fields.iter().any(|f| f.key == key) {
+                return Err(format!("sort key '{name}' is given twice"));
+            }
+            fields.push(SortField { key, desc });
+        }
+        if fields.is_empty() {
+            return Err("sort spec is empty".to_string());
+        }
+        Ok(SortSpec(fields))
+    }
+}
+
+impl std::fmt::Display for SortSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .0
+            .iter()
+            .map(|field| {
+                let name = format!("{:?}", field.key).to_lowercase();
+                if field.desc { format!("-{name}") } else { name }
+            })
+            .collect();
+        f.write_str(&parts.join(","))
+    }
+}
+
+impl TryFrom<String> for SortSpec {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<SortSpec> for String {
+    fn from(spec: SortSpec) -> Self {
+        spec.to_string()
+    }
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
@@ -225,7 +566,7 @@ pub enum IdScope {
     Branch,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
 pub enum Priority {
     Low,
     Medium,
@@ -283,4 +624,24 @@ pub struct Task {
     pub archived: bool,
     pub done: bool,
     pub created_at: String, // keep simple for v1
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub completed_at: Option<String>,
+}
+
+impl Task {
+    /// Record that the task was just modified.
+    pub fn touch(&mut self) {
+        self.updated_at = Some(chrono::Local::now().to_rfc3339());
+    }
+
+    /// Mark the task done or open, keeping `completed_at` in sync.
+    pub fn set_done(&mut self, done: bool) {
+        if self.done != done {
+            self.completed_at = done.then(|| chrono::Local::now().to_rfc3339());
+        }
+        self.done = done;
+        self.touch();
+    }
 }
@@ -0,0 +1,105 @@
+use std::cell::Cell;
+
+thread_local! {
+    static MODE: Cell<OutputMode> = const { Cell::new(OutputMode { ascii: false, accessible: false, json: false }) };
+}
+
+/// How text output is drawn on this thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputMode {
+    /// Plain ASCII stand-ins for every glyph (`--ascii`).
+    pub ascii: bool,
+    /// One sentence per task and no decoration (`--accessible`); implies `ascii`.
+    pub accessible: bool,
+    /// One JSON document per command instead of text (`--json`).
+    pub json: bool,
+}
+
+/// Make `mode` the one used by `Glyph::as_str` and the list views on this thread.
+pub fn install(mode: OutputMode) {
+    let mode = OutputMode {
+        ascii: mode.ascii || mode.accessible,
+        ..mode
+    };
+    MODE.with(|m| m.set(mode));
+}
+
+pub fn mode() -> OutputMode {
+    MODE.with(Cell::get)
+}
+
+/// Characters the text views draw with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    Done,
+    Open,
+    Ellipsis,
+    /// Between meta fields: `due: … · tags: …`
+    Separator,
+    /// Between a title and its meta fields in the compact view.
+    Dash,
+    /// Marks projected recurrences.
+    Projection,
+    Rule,
+    Vertical,
+    TopLeft,
+    TopMiddle,
+    TopRight,
+    MiddleLeft,
+    Cross,
+    MiddleRight,
+    BottomLeft,
+    BottomMiddle,
+    BottomRight,
+    HeavyRule,
+    HeavyVertical,
+    HeavyTopLeft,
+    HeavyTopRight,
+    HeavyBottomLeft,
+    HeavyBottomRight,
+}
+
+impl Glyph {
+    pub fn as_str(self) -> &'static str {
+        let (unicode, ascii) = match self {
+            Glyph::Done => ("✔", "x"),
+            Glyph::Open => ("•", "-"),
+            Glyph::Ellipsis => ("…", "..."),
+            Glyph::Separator => ("·", "|"),
+            Glyph::Dash => ("—", "-"),
+            Glyph::Projection => ("↻", "~"),
+            Glyph::Rule => ("─", "-"),
+            Glyph::Vertical => ("│", "|"),
+            Glyph::TopLeft => ("┌", "+"),
+            Glyph::TopMiddle => ("┬", "+"),
+            Glyph::TopRight => ("┐", "+"),
+            Glyph::MiddleLeft => ("├", "+"),
+            Glyph::Cross => ("┼", "+"),
+            Glyph::MiddleRight => ("┤", "+"),
+            Glyph::BottomLeft => ("└", "+"),
+            Glyph::BottomMiddle => ("┴", "+"),
+            Glyph::BottomRight => ("┘", "+"),
+            Glyph::HeavyRule => ("━", "="),
+            Glyph::HeavyVertical => ("┃", "|"),
+            Glyph::HeavyTopLeft => ("┏", "+"),
+            Glyph::HeavyTopRight => ("┓", "+"),
+            Glyph::HeavyBottomLeft => ("┗", "+"),
+            Glyph::HeavyBottomRight => ("┛", "+"),
+        };
+        if mode().ascii { ascii } else { unicode }
+    }
+
+    /// The glyph repeated `count` times.
+    pub fn repeat(self, count: usize) -> String {
+        self.as_str().repeat(count)
+    }
+}
+
+/// The status mark for a task.
+pub fn status_glyph(done: bool) -> &'static str {
+    if done {
+        Glyph::Done.as_str()
+    } else {
+        Glyph::Open.as_str()
+    }
+}
@@ -0,0 +1,301 @@
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+
+use crate::branch::normalize_branch;
+use crate::model::{Priority, Repeat};
+use crate::output::Glyph;
+use crate::util::{normalize_tag, parse_due};
+
+/// Metadata pulled out of a quick-add title.
+///
+/// Recognized tokens:
+/// - `#tag` adds a tag
+/// - `!high`, `!medium`, `!low` (or `!h`/`!m`/`!l`, `!1`-`!3`) set the priority
+/// - `+branch` sets the branch
+/// - `due:<date>` sets the due date anywhere in the text; `today`, `tomorrow`, a weekday
+///   name or `YYYY-MM-DD` only do so among the other tokens at the end of the text, so
+///   "Read about sun spots" keeps its "sun"
+/// - `every day|week|month` sets the repeat interval
+///
+/// Times of day such as `9:00` are not parsed and stay in the title, since tasks only
+/// have a due date. They don't end the metadata at the end of the text, so the
+/// `tomorrow` in "Call dentist tomorrow 9:00" is still read.
+///
+/// A token prefixed with `\` is kept literally (without the backslash), e.g. `\#1`.
+#[derive(Debug, Default, Clone)]
+pub struct QuickAdd {
+    pub title: String,
+    pub due: Option<NaiveDate>,
+    pub priority: Option<Priority>,
+    pub tags: Vec<String>,
+    pub branch: Option<String>,
+    pub repeat: Option<Repeat>,
+}
+
+impl QuickAdd {
+    pub fn has_metadata(&self) -> bool {
+        self.due.is_some()
+            || self.priority.is_some()
+            || !self.tags.is_empty()
+            || self.branch.is_some()
+            || self.repeat.is_some()
+    }
+
+    /// One-line summary of how the input was interpreted.
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!("\"{}\"", self.title)];
+        if let Some(due) = self.due {
+            parts.push(format!("due {due}"));
+        }
+        if let Some(priority) = self.priority {
+            parts.push(format!(
+                "priority {}",
+                format!("{priority:?}").to_lowercase()
+            ));
+        }
+        if !self.tags.is_empty() {
+            parts.push(format!("tags {}", self.tags.join(", ")));
+        }
+        if let Some(branch) = self.branch.as_deref() {
+            parts.push(format!("branch {branch}"));
+        }
+        if let Some(repeat) = self.repeat {
+            parts.push(format!("repeat {}", format!("{repeat:?}").to_lowercase()));
+        }
+        parts.join(&format!(" {} ", Glyph::Separator.as_str()))
+    }
+}
+
+pub fn parse_quick_add(input: &str, today: NaiveDate) -> Result<QuickAdd, String> {
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    let mut out = QuickAdd::default();
+    let mut title: Vec<String> = Vec::new();
+    let tail = metadata_tail(&tokens);
+
+    let mut i = 0usize;
+    while i < tokens.len() {
+        let token = tokens[i];
+        i += 1;
+
+        if let Some(literal) = token.strip_prefix('\\')
+            && !literal.is_empty()
+        {
+            title.push(literal.to_string());
+            continue;
+        }
+
+        if let Some(tag) = token.strip_prefix('#')
+            && let Some(tag) = normalize_tag(tag)
+        {
+            if !out.tags.contains(&tag) {
+                out.tags.push(tag);
+            }
+            continue;
+        }
+
+        if let Some(value) = token.strip_prefix('!')
+            && let Some(priority) = parse_priority_token(value)
+        {
+            out.priority = Some(priority);
+            continue;
+        }
+
+        if let Some(value) = token.strip_prefix('+')
+            && let Some(branch) = normalize_branch(Some(value.to_string()))
+        {
+            out.branch = Some(branch);
+            continue;
+        }
+
+        if token.eq_ignore_ascii_case("every")
+            && let Some(repeat) = tokens.get(i).and_then(|next| parse_repeat_unit(next))
+        {
+            out.repeat = Some(repeat);
+            i += 1;
+            continue;
+        }
+
+        if let Some(due) = parse_due_token(token, today, i > tail) {
+            out.due = Some(due);
+            continue;
+        }
+
+        title.push(token.to_string());
+    }
+
+    out.title = title.join(" ");
+    if out.title.is_empty() {
+        return Err("title is empty after removing quick-add tokens".to_string());
+    }
+    out.tags.sort();
+    Ok(out)
+}
+
+fn parse_priority_token(value: &str) -> Option<Priority> {
+    match value.to_lowercase().as_str() {
+        "high" | "h" | "1" => Some(Priority::High),
+        "medium" | "med" | "m" | "2" => Some(Priority::Medium),
+        "low" | "l" | "3" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+fn parse_repeat_unit(value: &str) -> Option<Repeat> {
+    match value.to_lowercase().as_str() {
+        "day" | "daily" => Some(Repeat::Daily),
+        "week" | "weekly" => Some(Repeat::Weekly),
+        "month" | "monthly" => Some(Repeat::Monthly),
+        _ => None,
+    }
+}
+
+/// Index of the first token in the run of metadata tokens (and times of day) that ends
+/// the input.
+fn metadata_tail(tokens: &[&str]) -> usize {
+    let mut start = tokens.len();
+    while start > 0 {
+        let token = tokens[start - 1];
+        if start >= 2
+            && tokens[start - 2].eq_ignore_ascii_case("every")
+            && parse_repeat_unit(token).is_some()
+        {
+            start -= 2;
+        } else if is_metadata_token(token) || is_time_of_day(token) {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+fn is_metadata_token(token: &str) -> bool {
+    if token.starts_with('\\') {
+        return false;
+    }
+    let lower = token.to_lowercase();
+    token.strip_prefix('#').and_then(normalize_tag).is_some()
+        || token
+            .strip_prefix('!')
+            .and_then(parse_priority_token)
+            .is_some()
+        || token
+            .strip_prefix('+')
+            .is_some_and(|b| normalize_branch(Some(b.to_string())).is_some())
+        || lower.starts_with("due:")
+        || lower == "today"
+        || lower == "tomorrow"
+        || NaiveDate::parse_from_str(&lower, "%Y-%m-%d").is_ok()
+        || parse_weekday(&lower).is_some()
+}
+
+/// `9:00`, `14:30` and the like.
+fn is_time_of_day(token: &str) -> bool {
+    NaiveTime::parse_from_str(token, "%H:%M").is_ok()
+}
+
+/// A due date from `due:<date>`, or from a bare date word when `in_tail`.
+fn parse_due_token(token: &str, today: NaiveDate, in_tail: bool) -> Option<NaiveDate> {
+    let lower = token.to_lowercase();
+    if let Some(value) = lower.strip_prefix("due:") {
+        return parse_due(value).ok();
+    }
+    if !in_tail {
+        return None;
+    }
+    if lower == "today" || lower == "tomorrow" {
+        return parse_due(&lower).ok();
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&lower, "%Y-%m-%d") {
+        return Some(date);
+    }
+    let weekday = parse_weekday(&lower)?;
+    next_weekday(today, weekday)
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// The next date after `today` that falls on `weekday` (1 to 7 days ahead).
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> Option<NaiveDate> {
+    let current = today.weekday().num_days_from_monday();
+    let target = weekday.num_days_from_monday();
+    let ahead = (target + 7 - current) % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    today.checked_add_days(chrono::Days::new(ahead as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        // A Sunday.
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    #[test]
+    fn parses_the_full_example() {
+        let quick = parse_quick_add(
+            "Call dentist tomorrow 9:00 #health !high +personal every month",
+            today(),
+        )
+        .unwrap();
+        assert_eq!(quick.title, "Call dentist 9:00");
+        assert_eq!(quick.due, NaiveDate::from_ymd_opt(2026, 10, 19));
+        assert_eq!(quick.priority, Some(Priority::High));
+        assert_eq!(quick.tags, vec!["health".to_string()]);
+        assert_eq!(quick.branch.as_deref(), Some("personal"));
+        assert!(matches!(quick.repeat, Some(Repeat::Monthly)));
+    }
+
+    #[test]
+    fn reads_a_date_word_before_a_trailing_time() {
+        let quick = parse_quick_add("Call dentist tomorrow 9:00", today()).unwrap();
+        assert_eq!(quick.title, "Call dentist 9:00");
+        assert_eq!(quick.due, NaiveDate::from_ymd_opt(2026, 10, 19));
+
+        let quick = parse_quick_add("Standup friday 14:30", today()).unwrap();
+        assert_eq!(quick.title, "Standup 14:30");
+        assert_eq!(quick.due, NaiveDate::from_ymd_opt(2026, 10, 23));
+    }
+
+    #[test]
+    fn keeps_date_words_inside_the_title() {
+        let quick = parse_quick_add("Read about sun spots today", today()).unwrap();
+        assert_eq!(quick.title, "Read about sun spots");
+        assert_eq!(quick.due, Some(today()));
+
+        let quick = parse_quick_add("Plan today's meeting", today()).unwrap();
+        assert_eq!(quick.title, "Plan today's meeting");
+        assert!(!quick.has_metadata());
+    }
+
+    #[test]
+    fn reads_due_tokens_anywhere() {
+        let quick = parse_quick_add("Pay due:2026-11-01 the rent", today()).unwrap();
+        assert_eq!(quick.title, "Pay the rent");
+        assert_eq!(quick.due, NaiveDate::from_ymd_opt(2026, 11, 1));
+    }
+
+    #[test]
+    fn keeps_escaped_tokens_literally() {
+        let quick = parse_quick_add(r"Fix bug \#12 \!important \+1 \tomorrow", today()).unwrap();
+        assert_eq!(quick.title, "Fix bug #12 !important +1 tomorrow");
+        assert!(!quick.has_metadata());
+    }
+
+    #[test]
+    fn rejects_a_title_of_only_metadata() {
+        assert!(parse_quick_add("#health !high tomorrow", today()).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use clap::{CommandFactory, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::app::{execute, expand_saved_command_args, resolve_color, usage_error};
+use crate::branch::{collect_branches, normalize_state};
+use crate::cli::{Cli, Commands};
+use crate::error::TodoError;
+use crate::model::{AppState, Task};
+use crate::output::{self, OutputMode};
+use crate::storage::{history_path, load_state, load_tasks};
+
+thread_local! {
+    static IN_SHELL: Cell<bool> = const { Cell::new(false) };
+}
+
+pub fn run_shell(
+    state: &mut AppState,
+    tasks: &mut Vec<Task>,
+    color: bool,
+    path: &PathBuf,
+    state_path: &PathBuf,
+) -> Result<(), TodoError> {
+    if IN_SHELL.with(Cell::get) {
+        println!("Already in the shell");
+        return Ok(());
+    }
+
+    let mut editor: Editor<ShellHelper, rustyline::history::DefaultHistory> =
+        Editor::new().map_err(|e| TodoError::Failed(format!("cannot start the shell: {e}")))?;
+    editor.set_helper(Some(ShellHelper::new(state, tasks)));
+    // Flags given to `todo shell` itself apply to every command.
+    let outer_mode = output::mode();
+    let history = history_path();
+    let _ = editor.load_history(&history);
+
+    println!("todo shell: type a command without `todo`, `help` for usage, `exit` to quit");
+    IN_SHELL.with(|flag| flag.set(true));
+
+    loop {
+        let prompt = format!("todo:{}> ", state.current_branch);
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                eprintln!("{e}");
+                break;
+            }
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+        if line == "exit" || line == "quit" {
+            break;
+        }
+
+        let mut args = match split_args(line) {
+            Ok(args) => args,
+            Err(e) => {
+                TodoError::Usage(e).report();
+                continue;
+            }
+        };
+        if args.first().is_some_and(|a| a == "todo") {
+            args.remove(0);
+        }
+        args.insert(0, "todo".to_string());
+        let args = expand_saved_command_args(&args, state);
+
+        let cli = match Cli::try_parse_from(args) {
+            Ok(cli) => cli,
+            Err(e) if outer_mode.json && e.use_stderr() => {
+                usage_error(&e).report();
+                continue;
+            }
+            Err(e) => {
+                let _ = e.print();
+                continue;
+            }
+        };
+        if matches!(cli.command, Commands::Shell) {
+            println!("Already in the shell");
+            continue;
+        }
+
+        let color = color && resolve_color(&cli, &state.config);
+        output::install(OutputMode {
+            ascii: outer_mode.ascii || cli.ascii,
+            accessible: outer_mode.accessible || cli.accessible,
+            json: outer_mode.json || cli.json,
+        });
+        if let Err(e) = execute(cli.command, state, tasks, color, path, state_path) {
+            e.report();
+            // The command failed; drop any partial in-memory changes.
+            if let Err(e) = reload(state, tasks, path, state_path) {
+                e.report();
+            }
+        }
+
+        if let Some(helper) = editor.helper_mut() {
+            helper.refresh(state, tasks);
+        }
+    }
+
+    IN_SHELL.with(|flag| flag.set(false));
+    let _ = editor.save_history(&history);
+    Ok(())
+}
+
+fn reload(
+    state: &mut AppState,
+    tasks: &mut Vec<Task>,
+    path: &PathBuf,
+    state_path: &PathBuf,
+) -> Result<(), TodoError> {
+    *tasks = load_tasks(path)?;
+    *state = load_state(state_path)?;
+    normalize_state(state);
+    Ok(())
+}
+
+/// Split a shell line into arguments, honoring single quotes, double quotes and backslashes.
+fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("unterminated double quote".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                in_arg = true;
+                if let Some(c) = chars.next() {
+                    current.push(c);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+struct ShellHelper {
+    commands: Vec<String>,
+    /// Long flags per command name and alias.
+    flags: HashMap<String, Vec<String>>,
+    ids: Vec<String>,
+    tags: Vec<String>,
+    branches: Vec<String>,
+}
+
+impl ShellHelper {
+    fn new(state: &AppState, tasks: &[Task]) -> Self {
+        let mut commands: Vec<String> = Vec::new();
+        let mut flags: HashMap<String, Vec<String>> = HashMap::new();
+        for sub in Cli::command().get_subcommands() {
+            let long: Vec<String> = sub
+                .get_arguments()
+                .filter_map(|a| a.get_long())
+                .map(|l| format!("--{l}"))
+                .collect();
+            let mut names = vec![sub.get_name().to_string()];
+            names.extend(sub.get_all_aliases().map(str::to_string));
+            for name in names {
+                flags.insert(name.clone(), long.clone());
+                commands.push(name);
+            }
+        }
+        commands.extend(["exit".to_string(), "help".to_string(), "quit".to_string()]);
+
+        let mut helper = Self {
+            commands,
+            flags,
+            ids: Vec::new(),
+            tags: Vec::new(),
+            branches: Vec::new(),
+        };
+        helper.refresh(state, tasks);
+        helper
+    }
+
+    fn refresh(&mut self, state: &AppState, tasks: &[Task]) {
+        // Ids repeat across branches.
+        let mut ids: Vec<_> = tasks
+            .iter()
+            .filter(|t| !t.done && !t.archived)
+            .map(|t| t.id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        self.ids = ids.iter().map(ToString::to_string).collect();
+
+        let mut tags: Vec<String> = tasks.iter().flat_map(|t| t.tags.clone()).collect();
+        tags.sort();
+        tags.dedup();
+        self.tags = tags;
+
+        let mut branches = collect_branches(tasks);
+        if !branches
+            .iter()
+            .any(|b| b.eq_ignore_ascii_case(&state.current_branch))
+        {
+            branches.push(state.current_branch.clone());
+        }
+        self.branches = branches;
+
+        let saved: Vec<String> = state.profile.saved_commands.keys().cloned().collect();
+        for name in saved {
+            if !self.commands.contains(&name) {
+                self.commands.push(name);
+            }
+        }
+    }
+
+    fn candidates(&self, previous: &[&str], word: &str) -> Vec<String> {
+        let Some(command) = previous.first() else {
+            return self.commands.clone();
+        };
+
+        let last = previous.last().copied().unwrap_or("");
+        if matches!(last, "--tag" | "--remove-tag") {
+            return self.tags.clone();
+        }
+        if matches!(last, "-b" | "--branch") {
+            return self.branches.clone();
+        }
+        if word.starts_with('-') {
+            return self.flags.get(*command).cloned().unwrap_or_default();
+        }
+        if matches!(
+            *command,
+            "branch"
+                | "b"
+                | "switch"
+                | "branch-delete"
+                | "br-del"
+                | "branch-move"
+                | "br-mv"
+                | "branch-duplicate"
+                | "branch-dup"
+                | "br-cp"
+                | "branch-merge"
+                | "br-merge"
+        ) {
+            return self.branches.clone();
+        }
+        self.ids.clone()
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let head = &line[..pos];
+        let start = head
+            .rfind(char::is_whitespace)
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        let word = &head[start..];
+        let previous: Vec<&str> = head[..start].split_whitespace().collect();
+
+        let matches = self
+            .candidates(&previous, word)
+            .into_iter()
+            .filter(|c| c.starts_with(word))
+            .map(|c| Pair {
+                display: c.clone(),
+                replacement: c,
+            })
+            .collect();
+        Ok((start, matches))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
@@ -1,41 +1,115 @@
 use std::cmp::Ordering;
 
-use crate::model::{Priority, SortKey, Task};
-use crate::util::priority_rank;
+use chrono::{Local, NaiveDate};
 
-pub fn sort_tasks(view: &mut Vec<&Task>, key: SortKey, desc: bool) {
-    view.sort_by(|a, b| compare_tasks(a, b, key, desc));
+use crate::model::{Priority, SortField, SortKey, SortSpec, Task, UrgencyCoefficients};
+use crate::urgency::urgency;
+use crate::util::{parse_timestamp, priority_rank};
+
+/// Sort `view` by `spec`. `desc` flips the direction of the first key (the `--desc` flag).
+/// The sort is stable, and ties fall back to the usual secondary keys and finally the id.
+/// `coeffs` is only consulted for the urgency key.
+pub fn sort_tasks(view: &mut [&Task], spec: &SortSpec, desc: bool, coeffs: &UrgencyCoefficients) {
+    let fields = effective_fields(spec, desc);
+    let urgency = Scorer::new(coeffs);
+    view.sort_by(|a, b| compare_tasks(a, b, &fields, &urgency));
+}
+
+/// Sort indices into `tasks` with the same ordering as `sort_tasks`.
+pub fn sort_indices(
+    tasks: &[Task],
+    indices: &mut [usize],
+    spec: &SortSpec,
+    desc: bool,
+    coeffs: &UrgencyCoefficients,
+) {
+    let fields = effective_fields(spec, desc);
+    let urgency = Scorer::new(coeffs);
+    indices.sort_by(|a, b| compare_tasks(&tasks[*a], &tasks[*b], &fields, &urgency));
 }
 
-fn compare_tasks(a: &Task, b: &Task, key: SortKey, desc: bool) -> Ordering {
+struct Scorer<'a> {
+    coeffs: &'a UrgencyCoefficients,
+    today: NaiveDate,
+}
+
+impl<'a> Scorer<'a> {
+    fn new(coeffs: &'a UrgencyCoefficients) -> Self {
+        Self {
+            coeffs,
+            today: Local::now().date_naive(),
+        }
+    }
+
+    fn score(&self, task: &Task) -> f64 {
+        urgency(task, self.coeffs, self.today)
+    }
+}
+
+fn effective_fields(spec: &SortSpec, desc: bool) -> Vec<SortField> {
+    let mut fields = spec.0.clone();
+    if desc && let Some(first) = fields.first_mut() {
+        first.desc = !first.desc;
+    }
+
+    let tie_breakers: &[SortKey] = match fields.first().map(|f| f.key) {
+        Some(SortKey::Due) => &[SortKey::Priority, SortKey::Id],
+        Some(SortKey::Priority) => &[SortKey::Due, SortKey::Id],
+        Some(SortKey::Urgency) => &[SortKey::Due, SortKey::Priority, SortKey::Id],
+        _ => &[SortKey::Id],
+    };
+    for key in tie_breakers {
+        if !fields.iter().any(|f| f.key == *key) {
+            fields.push(SortField {
+                key: *key,
+                desc: false,
+            });
+        }
+    }
+    fields
+}
+
+fn compare_tasks(a: &Task, b: &Task, fields: &[SortField], urgency: &Scorer) -> Ordering {
+    fields
+        .iter()
+        .map(|field| compare_key(a, b, field.key, field.desc, urgency))
+        .find(|ord| ord.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Compare a single key. Missing values sort last in either direction.
+fn compare_key(a: &Task, b: &Task, key: SortKey, desc: bool, urgency: &Scorer) -> Ordering {
+    let directed = |ord: Ordering| if desc { ord.reverse() } else { ord };
     match key {
-        SortKey::Due => compare_due(a, b, desc)
-            .then_with(|| compare_priority(a, b, false))
-            .then_with(|| a.id.cmp(&b.id)),
-        SortKey::Priority => compare_priority(a, b, desc)
-            .then_with(|| compare_due(a, b, false))
-            .then_with(|| a.id.cmp(&b.id)),
-        SortKey::Created => {
-            let ord = a.created_at.cmp(&b.created_at);
-            if desc { ord.reverse() } else { ord }
+        SortKey::Due => compare_present(a.due, b.due, desc),
+        SortKey::Priority => compare_priority(a, b, desc),
+        SortKey::Created => directed(compare_timestamps(&a.created_at, &b.created_at)),
+        SortKey::Updated => {
+            compare_optional_timestamps(a.updated_at.as_deref(), b.updated_at.as_deref(), desc)
         }
-        SortKey::Id => {
-            let ord = a.id.cmp(&b.id);
-            if desc { ord.reverse() } else { ord }
+        SortKey::Completed => {
+            compare_optional_timestamps(a.completed_at.as_deref(), b.completed_at.as_deref(), desc)
         }
+        SortKey::Id => directed(a.id.cmp(&b.id)),
+        SortKey::Title => directed(compare_text(&a.title, &b.title)),
+        SortKey::Branch => directed(compare_text(&a.branch, &b.branch)),
+        // Open before done.
+        SortKey::Status => directed(a.done.cmp(&b.done)),
+        // Most urgent first, like priority.
+        SortKey::Urgency => directed(urgency.score(b).total_cmp(&urgency.score(a))),
     }
 }
 
-fn compare_due(a: &Task, b: &Task, desc: bool) -> Ordering {
-    match (a.due, b.due) {
+fn compare_present<T: Ord>(a: Option<T>, b: Option<T>, desc: bool) -> Ordering {
+    match (a, b) {
         (None, None) => Ordering::Equal,
         (None, Some(_)) => Ordering::Greater,
         (Some(_), None) => Ordering::Less,
-        (Some(ad), Some(bd)) => {
+        (Some(a), Some(b)) => {
             if desc {
-                bd.cmp(&ad)
+                b.cmp(&a)
             } else {
-                ad.cmp(&bd)
+                a.cmp(&b)
             }
         }
     }
@@ -56,3 +130,125 @@ fn priority_sort_value(p: Option<Priority>, desc: bool) -> u8 {
         None => 3,
     }
 }
+
+/// Compare as points in time when both parse, falling back to the raw strings.
+fn compare_timestamps(a: &str, b: &str) -> Ordering {
+    match (parse_timestamp(a), parse_timestamp(b)) {
+        (Some(at), Some(bt)) => at.cmp(&bt),
+        _ => a.cmp(b),
+    }
+}
+
+fn compare_optional_timestamps(a: Option<&str>, b: Option<&str>, desc: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let ord = compare_timestamps(a, b);
+            if desc { ord.reverse() } else { ord }
+        }
+        (a, b) => compare_present(a.map(|_| ()), b.map(|_| ()), desc),
+    }
+}
+
+/// Human ordering for text: accents and case are folded and digit runs compare by value,
+/// so "Élan" sorts with "elan" and "Item 2" comes before "Item 10".
+fn compare_text(a: &str, b: &str) -> Ordering {
+    lexical_sort::natural_lexical_cmp(a, b).then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::AppConfig;
+
+    fn task(id: u64, due: Option<&str>, priority: Option<Priority>) -> Task {
+        Task {
+            id,
+            uid: None,
+            title: format!("Task {id}"),
+            content: None,
+            tags: Vec::new(),
+            due: due.map(|d| d.parse().unwrap()),
+            priority,
+            repeat: None,
+            branch: "main".to_string(),
+            archived: false,
+            done: false,
+            created_at: "2026-10-01T09:00:00+00:00".to_string(),
+            updated_at: None,
+            completed_at: None,
+        }
+    }
+
+    fn sorted_ids(tasks: &[Task], spec: &str, desc: bool) -> Vec<u64> {
+        let spec: SortSpec = spec.parse().unwrap();
+        let mut view: Vec<&Task> = tasks.iter().collect();
+        sort_tasks(&mut view, &spec, desc, &UrgencyCoefficients::default());
+        view.iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn parses_keys_case_insensitively_with_directions() {
+        let spec: SortSpec = " Due , -PRIORITY, +title".parse().unwrap();
+        assert_eq!(
+            spec.0,
+            vec![
+                SortField {
+                    key: SortKey::Due,
+                    desc: false
+                },
+                SortField {
+                    key: SortKey::Priority,
+                    desc: true
+                },
+                SortField {
+                    key: SortKey::Title,
+                    desc: false
+                },
+            ]
+        );
+        assert_eq!(spec.to_string(), "due,-priority,title");
+    }
+
+    #[test]
+    fn rejects_unknown_repeated_and_empty_keys() {
+        let err = "due,size".parse::<SortSpec>().unwrap_err();
+        assert!(err.contains("unknown sort key 'size'"), "{err}");
+        assert!(err.contains("urgency"), "{err}");
+        assert!("due,-due".parse::<SortSpec>().is_err());
+        assert!("".parse::<SortSpec>().is_err());
+    }
+
+    #[test]
+    fn loads_configs_saved_with_a_single_key() {
+        let config: AppConfig = serde_json::from_str(r#"{"default_sort":"Due"}"#).unwrap();
+        assert_eq!(config.default_sort, SortSpec::from(SortKey::Due));
+
+        let config: AppConfig = serde_json::from_str(r#"{"default_sort":"Priority"}"#).unwrap();
+        assert_eq!(config.default_sort, SortSpec::from(SortKey::Priority));
+    }
+
+    #[test]
+    fn keeps_tasks_without_a_due_date_last_in_both_directions() {
+        let tasks = [
+            task(1, None, None),
+            task(2, Some("2026-10-20"), None),
+            task(3, Some("2026-10-25"), None),
+        ];
+        assert_eq!(sorted_ids(&tasks, "due", false), vec![2, 3, 1]);
+        assert_eq!(sorted_ids(&tasks, "-due", false), vec![3, 2, 1]);
+        // `--desc` flips the first key.
+        assert_eq!(sorted_ids(&tasks, "due", true), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn later_keys_break_ties() {
+        let tasks = [
+            task(1, Some("2026-10-20"), Some(Priority::Low)),
+            task(2, Some("2026-10-20"), Some(Priority::High)),
+            task(3, Some("2026-10-19"), None),
+        ];
+        assert_eq!(sorted_ids(&tasks, "due", false), vec![3, 2, 1]);
+        assert_eq!(sorted_ids(&tasks, "due,-priority", false), vec![3, 1, 2]);
+        assert_eq!(sorted_ids(&tasks, "priority", false), vec![2, 1, 3]);
+    }
+}
@@ -1,13 +1,19 @@
 use dirs::data_local_dir;
-use std::{fs, path::PathBuf};
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use std::{fs, io, path::PathBuf};
+use uuid::Uuid;
 
+use crate::error::TodoError;
 use crate::model::{AppState, Task};
 
 fn base_dir() -> PathBuf {
     // /Users/<user>/Library/Application Support/todo/
     let mut base = data_local_dir().unwrap_or_else(|| PathBuf::from("."));
     base.push("todo");
-    fs::create_dir_all(&base).expect("Failed to create data dir");
+    if let Err(e) = fs::create_dir_all(&base) {
+        TodoError::Storage(format!("cannot create {}: {e}", base.display())).exit();
+    }
     base
 }
 
@@ -23,26 +29,68 @@ pub fn state_path() -> PathBuf {
     base
 }
 
-pub fn load_tasks(path: &PathBuf) -> Vec<Task> {
-    let Ok(bytes) = fs::read(path) else {
-        return vec![];
-    };
-    serde_json::from_slice(&bytes).unwrap_or_else(|_| vec![])
+pub fn history_path() -> PathBuf {
+    let mut base = base_dir();
+    base.push("shell_history.txt");
+    base
 }
 
-pub fn save_tasks(path: &PathBuf, tasks: &Vec<Task>) {
+pub fn load_tasks(path: &PathBuf) -> Result<Vec<Task>, TodoError> {
+    Ok(load_json(path)?.unwrap_or_default())
+}
+
+pub fn save_tasks(path: &PathBuf, tasks: &Vec<Task>) -> Result<(), TodoError> {
     let bytes = serde_json::to_vec_pretty(tasks).expect("serialize tasks");
-    fs::write(path, bytes).expect("write tasks");
+    write_file(path, bytes)
 }
 
-pub fn load_state(path: &PathBuf) -> AppState {
-    let Ok(bytes) = fs::read(path) else {
-        return AppState::default();
-    };
-    serde_json::from_slice(&bytes).unwrap_or_else(|_| AppState::default())
+pub fn load_state(path: &PathBuf) -> Result<AppState, TodoError> {
+    Ok(load_json(path)?.unwrap_or_default())
 }
 
-pub fn save_state(path: &PathBuf, state: &AppState) {
+pub fn save_state(path: &PathBuf, state: &AppState) -> Result<(), TodoError> {
     let bytes = serde_json::to_vec_pretty(state).expect("serialize state");
-    fs::write(path, bytes).expect("write state");
+    write_file(path, bytes)
+}
+
+/// `None` when the file doesn't exist yet. A file that can't be read or parsed is a
+/// storage error rather than an empty store, so the next save can't overwrite it.
+fn load_json<T: DeserializeOwned>(path: &PathBuf) -> Result<Option<T>, TodoError> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(TodoError::Storage(format!(
+                "cannot read {}: {e}",
+                path.display()
+            )));
+        }
+    };
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| TodoError::Storage(format!("cannot parse {}: {e}", path.display())))
+}
+
+/// Write a temporary file next to `path` and rename it over `path`, so a crash or a
+/// full disk never leaves a half-written store.
+fn write_file(path: &PathBuf, bytes: Vec<u8>) -> Result<(), TodoError> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!(".{name}.{}.tmp", Uuid::new_v4().simple()));
+    let written = write_synced(&temp, &bytes).and_then(|()| fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    written.map_err(|e| TodoError::Storage(format!("cannot write {}: {e}", path.display())))
+}
+
+fn write_synced(path: &PathBuf, bytes: &[u8]) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
 }
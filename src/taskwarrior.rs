@@ -0,0 +1,428 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::app::TaskParts;
+use crate::branch::is_default_branch;
+use crate::ics::{parse_date_time, utc_stamp};
+use crate::model::{ISO_DATE_FORMAT, Priority, Repeat, Task};
+use crate::util::parse_timestamp;
+
+/// Fields Taskwarrior computes or uses for its own bookkeeping.
+const IGNORED_FIELDS: [&str; 5] = ["id", "urgency", "mask", "imask", "parent"];
+
+/// Namespace of the UUIDs made for tasks without a UUID uid.
+const UUID_NAMESPACE: Uuid = Uuid::from_u128(0xde8c1b6a_73d0_452e_967f_a8dbd7aa609d);
+
+/// A task as `task export` writes it and `task import` reads it.
+#[derive(Serialize)]
+struct TwTask<'a> {
+    uuid: String,
+    description: &'a str,
+    entry: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recur: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Annotation {
+    entry: Option<String>,
+    description: String,
+}
+
+/// Taskwarrior JSON for `view`: a JSON array `task import` accepts.
+pub fn write_tasks(view: &[&Task]) -> String {
+    let data: Vec<TwTask> = view.iter().map(|task| to_taskwarrior(task)).collect();
+    serde_json::to_string_pretty(&data).expect("serialize tasks")
+}
+
+fn to_taskwarrior(task: &Task) -> TwTask<'_> {
+    let uuid = task_uuid(task).to_string();
+    let entry = utc_stamp(&task.created_at).unwrap_or_else(|| tw_stamp(Utc::now()));
+    let modified = task.updated_at.as_deref().and_then(utc_stamp);
+    // The task is one instance of its series: it stays pending and carries
+    // `recur`, which Taskwarrior rejects without `due`.
+    let recur = task.repeat.filter(|_| task.due.is_some() && !task.done);
+    let status = if task.done {
+        "completed"
+    } else if task.archived {
+        "deleted"
+    } else {
+        "pending"
+    };
+    let end = match status {
+        "completed" => task
+            .completed_at
+            .as_deref()
+            .and_then(utc_stamp)
+            .or_else(|| modified.clone()),
+        "deleted" => modified.clone().or_else(|| Some(tw_stamp(Utc::now()))),
+        _ => None,
+    };
+    // Noon UTC keeps the calendar date in every zone within twelve hours of
+    // UTC, both in the stamp itself and once Taskwarrior shows it locally.
+    let due = task
+        .due
+        .and_then(|date| date.and_hms_opt(12, 0, 0))
+        .map(|noon| tw_stamp(noon.and_utc()));
+    // One annotation per content line, as `import taskwarrior` joins them.
+    let annotations = task
+        .content
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Annotation {
+            entry: Some(modified.clone().unwrap_or_else(|| entry.clone())),
+            description: line.to_string(),
+        })
+        .collect();
+
+    TwTask {
+        uuid,
+        description: &task.title,
+        entry,
+        modified,
+        end,
+        status,
+        project: Some(task.branch.as_str()).filter(|b| !is_default_branch(b)),
+        tags: &task.tags,
+        due,
+        priority: task.priority.map(|p| match p {
+            Priority::High => "H",
+            Priority::Medium => "M",
+            Priority::Low => "L",
+        }),
+        recur: recur.map(|r| match r {
+            Repeat::Daily => "daily",
+            Repeat::Weekly => "weekly",
+            Repeat::Monthly => "monthly",
+        }),
+        annotations,
+    }
+}
+
+/// Taskwarrior needs a real UUID. Tasks without one get a name-based UUID from their
+/// uid, or from the branch and id like the ics export's UID, so exporting twice
+/// updates the same Taskwarrior tasks instead of adding copies.
+fn task_uuid(task: &Task) -> Uuid {
+    match task.uid.as_deref() {
+        Some(uid) => {
+            Uuid::parse_str(uid).unwrap_or_else(|_| Uuid::new_v5(&UUID_NAMESPACE, uid.as_bytes()))
+        }
+        None => {
+            let name = format!("{}-{}@clitodo", task.branch, task.id);
+            Uuid::new_v5(&UUID_NAMESPACE, name.as_bytes())
+        }
+    }
+}
+
+fn tw_stamp(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Read `task export` JSON. Recurring templates are only kept when none of their
+/// pending instances is in the file, and only the next pending instance of each.
+pub(crate) fn parse_tasks(bytes: &[u8]) -> Result<Vec<TaskParts>, String> {
+    let rows: Vec<BTreeMap<String, Value>> =
+        serde_json::from_slice(bytes).map_err(|e| e.to_string())?;
+
+    let text = |row: &BTreeMap<String, Value>, key: &str| {
+        row.get(key).and_then(Value::as_str).map(str::to_string)
+    };
+    let mut next_instance: HashMap<String, (usize, String)> = HashMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        if text(row, "status").as_deref() != Some("pending") {
+            continue;
+        }
+        if let Some(parent) = text(row, "parent") {
+            let due = text(row, "due").unwrap_or_default();
+            let entry = next_instance.entry(parent).or_insert((index, due.clone()));
+            if due < entry.1 {
+                *entry = (index, due);
+            }
+        }
+    }
+
+    let mut parts = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let status = text(row, "status").unwrap_or_default();
+        if status == "recurring"
+            && text(row, "uuid").is_some_and(|uuid| next_instance.contains_key(&uuid))
+        {
+            continue;
+        }
+        if status == "pending"
+            && let Some(parent) = text(row, "parent")
+            && next_instance.get(&parent).is_some_and(|(i, _)| *i != index)
+        {
+            continue;
+        }
+        parts.push(row_parts(row));
+    }
+    Ok(parts)
+}
+
+fn row_parts(row: &BTreeMap<String, Value>) -> TaskParts {
+    let mut parts = TaskParts::default();
+    let mut notes: Vec<String> = Vec::new();
+    let mut extra: Vec<String> = Vec::new();
+
+    for (key, value) in row {
+        let text = value.as_str().unwrap_or_default();
+        let mapped = match key.as_str() {
+            "description" => {
+                parts.title = text.to_string();
+                true
+            }
+            "uuid" => {
+                parts.uid = Some(text.to_string()).filter(|u| !u.is_empty());
+                true
+            }
+            "project" => {
+                parts.branch = Some(text.to_string());
+                true
+            }
+            "tags" => match value.as_array() {
+                Some(tags) => {
+                    parts
+                        .tags
+                        .extend(tags.iter().filter_map(Value::as_str).map(str::to_string));
+                    true
+                }
+                None => false,
+            },
+            "priority" => {
+                parts.priority = match text {
+                    "H" => Some(Priority::High),
+                    "M" => Some(Priority::Medium),
+                    "L" => Some(Priority::Low),
+                    _ => None,
+                };
+                parts.priority.is_some()
+            }
+            "status" => match text {
+                "completed" => {
+                    parts.done = Some(true);
+                    true
+                }
+                "deleted" => {
+                    parts.archived = Some(true);
+                    true
+                }
+                "pending" | "waiting" | "recurring" => true,
+                _ => false,
+            },
+            "recur" => {
+                parts.repeat = parse_recur(text);
+                parts.repeat.is_some()
+            }
+            "due" => match tw_date(text) {
+                Some(dt) => {
+                    parts.due = Some(dt.date_naive().format(ISO_DATE_FORMAT).to_string());
+                    true
+                }
+                None => false,
+            },
+            "entry" => set_timestamp(&mut parts.created_at, text),
+            "modified" => set_timestamp(&mut parts.updated_at, text),
+            "end" => set_timestamp(&mut parts.completed_at, text),
+            "annotations" => match serde_json::from_value::<Vec<Annotation>>(value.clone()) {
+                Ok(annotations) => {
+                    notes.extend(annotations.into_iter().map(|a| a.description));
+                    true
+                }
+                Err(_) => false,
+            },
+            key => IGNORED_FIELDS.contains(&key),
+        };
+        if !mapped {
+            let shown = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            extra.push(format!("{key}: {shown}"));
+        }
+    }
+
+    // A deleted task's `end` is when it was deleted, not completed.
+    if parts.done != Some(true) {
+        parts.completed_at = None;
+    }
+    // Fields with no task equivalent (wait, scheduled, UDAs, …) follow the annotations.
+    notes.extend(extra);
+    parts.content = Some(notes.join("\n")).filter(|c| !c.is_empty());
+    parts
+}
+
+/// Taskwarrior's `20261020T000000Z`, or an ISO timestamp from older versions.
+fn tw_date(value: &str) -> Option<DateTime<Local>> {
+    parse_date_time(value).or_else(|| parse_timestamp(value).map(|dt| dt.with_timezone(&Local)))
+}
+
+fn set_timestamp(field: &mut Option<String>, value: &str) -> bool {
+    match tw_date(value) {
+        Some(dt) => {
+            *field = Some(dt.to_rfc3339());
+            true
+        }
+        None => false,
+    }
+}
+
+fn parse_recur(value: &str) -> Option<Repeat> {
+    match value.to_ascii_lowercase().as_str() {
+        "daily" | "day" | "1d" | "p1d" => Some(Repeat::Daily),
+        "weekly" | "week" | "1w" | "1wk" | "7d" | "p1w" | "p7d" => Some(Repeat::Weekly),
+        "monthly" | "month" | "1mo" | "p1m" => Some(Repeat::Monthly),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DEFAULT_BRANCH;
+
+    fn task(id: u64) -> Task {
+        Task {
+            id,
+            uid: None,
+            title: format!("Task {id}"),
+            content: None,
+            tags: Vec::new(),
+            due: None,
+            priority: None,
+            repeat: None,
+            branch: DEFAULT_BRANCH.to_string(),
+            archived: false,
+            done: false,
+            created_at: "2026-10-18T09:30:00+02:00".to_string(),
+            updated_at: None,
+            completed_at: None,
+        }
+    }
+
+    fn round_trip(tasks: &[Task]) -> (Value, Vec<TaskParts>) {
+        let view: Vec<&Task> = tasks.iter().collect();
+        let json = write_tasks(&view);
+        let parts = parse_tasks(json.as_bytes()).unwrap();
+        (serde_json::from_str(&json).unwrap(), parts)
+    }
+
+    fn same_instant(a: Option<&str>, b: Option<&str>) -> bool {
+        a.and_then(parse_timestamp) == b.and_then(parse_timestamp)
+    }
+
+    #[test]
+    fn round_trips_status_recur_and_annotations() {
+        let repeating = Task {
+            uid: Some("7f0c7a52-5b8e-4c57-9c1a-2f3c1e0d9a11".to_string()),
+            content: Some("first line\nsecond line".to_string()),
+            tags: vec!["home".to_string()],
+            due: chrono::NaiveDate::from_ymd_opt(2026, 10, 20),
+            priority: Some(Priority::High),
+            repeat: Some(Repeat::Weekly),
+            branch: "work".to_string(),
+            updated_at: Some("2026-10-19T08:00:00+02:00".to_string()),
+            ..task(1)
+        };
+        let done = Task {
+            done: true,
+            completed_at: Some("2026-10-19T07:59:30+02:00".to_string()),
+            priority: Some(Priority::Low),
+            ..task(2)
+        };
+        let archived = Task {
+            archived: true,
+            priority: Some(Priority::Medium),
+            ..task(3)
+        };
+        let (json, parts) = round_trip(&[repeating.clone(), done.clone(), archived]);
+
+        let statuses: Vec<&str> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(statuses, vec!["pending", "completed", "deleted"]);
+        assert_eq!(json[0]["recur"], "weekly");
+        assert_eq!(json[0]["due"], "20261020T120000Z");
+        assert_eq!(json[0]["annotations"].as_array().map(Vec::len), Some(2));
+        assert_eq!(json[1].get("project"), None);
+
+        let [first, second, third] = parts.as_slice() else {
+            panic!("expected three tasks, got {}", parts.len());
+        };
+        assert_eq!(first.uid, repeating.uid);
+        assert_eq!(first.title, repeating.title);
+        assert_eq!(first.content, repeating.content);
+        assert_eq!(first.tags, repeating.tags);
+        assert_eq!(first.due.as_deref(), Some("2026-10-20"));
+        assert_eq!(first.priority, Some(Priority::High));
+        assert!(matches!(first.repeat, Some(Repeat::Weekly)));
+        assert_eq!(first.branch.as_deref(), Some("work"));
+        assert_eq!(first.done, None);
+        assert!(same_instant(
+            first.created_at.as_deref(),
+            Some(&repeating.created_at)
+        ));
+        assert!(same_instant(
+            first.updated_at.as_deref(),
+            repeating.updated_at.as_deref()
+        ));
+
+        assert_eq!(second.done, Some(true));
+        assert_eq!(second.priority, Some(Priority::Low));
+        assert!(same_instant(
+            second.completed_at.as_deref(),
+            done.completed_at.as_deref()
+        ));
+
+        assert_eq!(third.archived, Some(true));
+        assert_eq!(third.done, None);
+        assert_eq!(third.completed_at, None);
+        assert_eq!(third.priority, Some(Priority::Medium));
+    }
+
+    #[test]
+    fn keeps_uuids_stable_across_exports() {
+        let from_ics = Task {
+            uid: Some("abc123@calendar.example".to_string()),
+            ..task(1)
+        };
+        let without_uid = task(2);
+        let uuid = |task: &Task| to_taskwarrior(task).uuid;
+
+        assert_eq!(uuid(&from_ics), uuid(&from_ics.clone()));
+        assert_eq!(uuid(&without_uid), uuid(&without_uid.clone()));
+        assert_ne!(uuid(&from_ics), uuid(&without_uid));
+        assert_ne!(uuid(&without_uid), uuid(&task(3)));
+        assert!(Uuid::parse_str(&uuid(&from_ics)).is_ok());
+
+        let with_uuid = Task {
+            uid: Some("7f0c7a52-5b8e-4c57-9c1a-2f3c1e0d9a11".to_string()),
+            ..task(4)
+        };
+        assert_eq!(uuid(&with_uuid), "7f0c7a52-5b8e-4c57-9c1a-2f3c1e0d9a11");
+    }
+}
@@ -0,0 +1,433 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+
+use crate::display::{display_width, pad_right, truncate_to_width};
+use crate::model::{DateStyle, ISO_DATE_FORMAT, Task, UrgencyCoefficients};
+use crate::urgency::urgency;
+use crate::util::{format_date, format_due_date, parse_timestamp};
+
+/// Placeholder names accepted in templates.
+pub const FIELD_NAMES: [&str; 16] = [
+    "id",
+    "uid",
+    "title",
+    "content",
+    "tags",
+    "due",
+    "priority",
+    "repeat",
+    "branch",
+    "status",
+    "done",
+    "archived",
+    "created",
+    "updated",
+    "completed",
+    "urgency",
+];
+
+/// A parsed output template such as `{id} {title:trunc(30)}{?due} ({due:relative}){/}`.
+///
+/// - `{field}` or `{field:filter:filter(arg)}` inserts a task field
+/// - `{?field}...{/}` keeps its body only when the field is not empty, `{!field}...{/}` only when it is
+/// - `{{` and `}}` are literal braces; `\n` and `\t` are a newline and a tab
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Literal(String),
+    Field(Field, Vec<Filter>),
+    Section {
+        field: Field,
+        negate: bool,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Uid,
+    Title,
+    Content,
+    Tags,
+    Due,
+    Priority,
+    Repeat,
+    Branch,
+    Status,
+    Done,
+    Archived,
+    Created,
+    Updated,
+    Completed,
+    Urgency,
+}
+
+#[derive(Debug, Clone)]
+enum Filter {
+    Relative,
+    Date(String),
+    Join(String),
+    Trunc(usize),
+    Pad(usize),
+    LeftPad(usize),
+    Upper,
+    Lower,
+    Default(String),
+}
+
+/// What a field or filter produces before it is turned into text.
+enum Value {
+    Text(String),
+    Date(NaiveDate),
+    Time(DateTime<FixedOffset>),
+    List(Vec<String>),
+}
+
+/// A section being parsed: its field and negation (none for the root) and its nodes so far.
+type Frame = (Option<(Field, bool)>, Vec<Node>);
+
+/// Values a template needs besides the task itself.
+pub struct RenderContext<'a> {
+    pub today: NaiveDate,
+    pub urgency: &'a UrgencyCoefficients,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut chars = source.chars().peekable();
+        // Open sections; the last entry collects the nodes currently being parsed.
+        let mut stack: Vec<Frame> = vec![(None, Vec::new())];
+        let mut literal = String::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err("unexpected '}' (use '}}' for a literal brace)".to_string()),
+                '\\' => match chars.next() {
+                    Some('n') => literal.push('\n'),
+                    Some('t') => literal.push('\t'),
+                    Some(other) => {
+                        literal.push('\\');
+                        literal.push(other);
+                    }
+                    None => literal.push('\\'),
+                },
+                '{' => {
+                    let tag = read_tag(&mut chars)?;
+                    let nodes = &mut stack.last_mut().expect("template root").1;
+                    if !literal.is_empty() {
+                        nodes.push(Node::Literal(std::mem::take(&mut literal)));
+                    }
+                    let node = if let Some(name) = tag.strip_prefix('?') {
+                        stack.push((Some((parse_field(name)?, false)), Vec::new()));
+                        continue;
+                    } else if let Some(name) = tag.strip_prefix('!') {
+                        stack.push((Some((parse_field(name)?, true)), Vec::new()));
+                        continue;
+                    } else if let Some(name) = tag.strip_prefix('/') {
+                        let Some((Some((field, negate)), body)) =
+                            (stack.len() > 1).then(|| stack.pop()).flatten()
+                        else {
+                            return Err(format!("'{{{tag}}}' closes no section"));
+                        };
+                        if !name.trim().is_empty() && parse_field(name)? != field {
+                            return Err(format!("'{{{tag}}}' does not match the open section"));
+                        }
+                        Node::Section {
+                            field,
+                            negate,
+                            body,
+                        }
+                    } else {
+                        let mut parts = split_filters(&tag).into_iter();
+                        let field = parse_field(&parts.next().unwrap_or_default())?;
+                        let filters = parts
+                            .map(|f| parse_filter(&f))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        Node::Field(field, filters)
+                    };
+                    stack.last_mut().expect("template root").1.push(node);
+                }
+                _ => literal.push(c),
+            }
+        }
+
+        if stack.len() > 1 {
+            return Err("section is never closed with '{/}'".to_string());
+        }
+        let mut nodes = stack.pop().expect("template root").1;
+        if !literal.is_empty() {
+            nodes.push(Node::Literal(literal));
+        }
+        Ok(Self { nodes })
+    }
+
+    pub fn render(&self, task: &Task, ctx: &RenderContext) -> String {
+        let mut out = String::new();
+        render_nodes(&self.nodes, task, ctx, &mut out);
+        out
+    }
+}
+
+/// Read up to the closing `}`, allowing braces inside filter arguments like `default({none})`.
+fn read_tag(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String, String> {
+    let mut tag = String::new();
+    let mut depth = 0usize;
+    for c in chars.by_ref() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '}' if depth == 0 => return Ok(tag.trim().to_string()),
+            _ => {}
+        }
+        tag.push(c);
+    }
+    Err("unclosed '{'".to_string())
+}
+
+/// Split `field:filter(a:b):filter` on the colons outside parentheses.
+fn split_filters(tag: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut depth = 0usize;
+    for c in tag.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => {
+                parts.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        parts.last_mut().expect("non-empty").push(c);
+    }
+    parts
+}
+
+fn parse_field(name: &str) -> Result<Field, String> {
+    let field = match name.trim().to_lowercase().as_str() {
+        "id" => Field::Id,
+        "uid" => Field::Uid,
+        "title" => Field::Title,
+        "content" | "note" => Field::Content,
+        "tags" => Field::Tags,
+        "due" => Field::Due,
+        "priority" => Field::Priority,
+        "repeat" => Field::Repeat,
+        "branch" => Field::Branch,
+        "status" => Field::Status,
+        "done" => Field::Done,
+        "archived" => Field::Archived,
+        "created" => Field::Created,
+        "updated" => Field::Updated,
+        "completed" => Field::Completed,
+        "urgency" => Field::Urgency,
+        other => {
+            return Err(format!(
+                "unknown field '{other}' (expected {})",
+                FIELD_NAMES.join(", ")
+            ));
+        }
+    };
+    Ok(field)
+}
+
+fn parse_filter(spec: &str) -> Result<Filter, String> {
+    let spec = spec.trim();
+    let (name, arg) = match spec.split_once('(') {
+        Some((name, rest)) => {
+            let arg = rest
+                .strip_suffix(')')
+                .ok_or_else(|| format!("filter '{spec}' is missing ')'"))?;
+            (name.trim(), Some(arg))
+        }
+        None => (spec, None),
+    };
+    let width = |arg: Option<&str>| -> Result<usize, String> {
+        arg.and_then(|a| a.trim().parse().ok())
+            .ok_or_else(|| format!("filter '{name}' needs a width, e.g. {name}(20)"))
+    };
+    let filter = match name.to_lowercase().as_str() {
+        "relative" => Filter::Relative,
+        "date" => {
+            let format = arg.unwrap_or("%Y-%m-%d");
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(format!("invalid date format '{format}'"));
+            }
+            Filter::Date(format.to_string())
+        }
+        "join" => Filter::Join(arg.unwrap_or(",").to_string()),
+        "trunc" => Filter::Trunc(width(arg)?),
+        "pad" => Filter::Pad(width(arg)?),
+        "lpad" => Filter::LeftPad(width(arg)?),
+        "upper" => Filter::Upper,
+        "lower" => Filter::Lower,
+        "default" => Filter::Default(arg.unwrap_or("").to_string()),
+        _ => {
+            return Err(format!(
+                "unknown filter '{name}' (expected relative, date(fmt), join(sep), trunc(n), \
+                 pad(n), lpad(n), upper, lower or default(text))"
+            ));
+        }
+    };
+    Ok(filter)
+}
+
+fn render_nodes(nodes: &[Node], task: &Task, ctx: &RenderContext, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Literal(text) => out.push_str(text),
+            Node::Field(field, filters) => {
+                let value = filters.iter().fold(field_value(*field, task, ctx), |v, f| {
+                    apply_filter(v, f, task, ctx)
+                });
+                out.push_str(&into_text(value));
+            }
+            Node::Section {
+                field,
+                negate,
+                body,
+            } => {
+                let present = !into_text(field_value(*field, task, ctx)).is_empty();
+                if present != *negate {
+                    render_nodes(body, task, ctx, out);
+                }
+            }
+        }
+    }
+}
+
+fn field_value(field: Field, task: &Task, ctx: &RenderContext) -> Value {
+    let text = |s: &str| Value::Text(s.to_string());
+    let flag = |on: bool, name: &str| Value::Text(if on { name } else { "" }.to_string());
+    let time = |s: Option<&str>| match s {
+        Some(s) => parse_timestamp(s).map_or_else(|| text(s), Value::Time),
+        None => text(""),
+    };
+    match field {
+        Field::Id => Value::Text(task.id.to_string()),
+        Field::Uid => text(task.uid.as_deref().unwrap_or("")),
+        Field::Title => text(&task.title),
+        Field::Content => text(task.content.as_deref().unwrap_or("")),
+        Field::Tags => Value::List(task.tags.clone()),
+        Field::Due => task.due.map_or_else(|| text(""), Value::Date),
+        Field::Priority => Value::Text(
+            task.priority
+                .map(|p| format!("{p:?}").to_lowercase())
+                .unwrap_or_default(),
+        ),
+        Field::Repeat => Value::Text(
+            task.repeat
+                .map(|r| format!("{r:?}").to_lowercase())
+                .unwrap_or_default(),
+        ),
+        Field::Branch => text(&task.branch),
+        Field::Status => text(if task.done { "done" } else { "todo" }),
+        Field::Done => flag(task.done, "done"),
+        Field::Archived => flag(task.archived, "archived"),
+        Field::Created => time(Some(&task.created_at)),
+        Field::Updated => time(task.updated_at.as_deref()),
+        Field::Completed => time(task.completed_at.as_deref()),
+        Field::Urgency if task.done => text(""),
+        Field::Urgency => Value::Text(format!("{:.1}", urgency(task, ctx.urgency, ctx.today))),
+    }
+}
+
+/// `relative` words dates like `--date-style relative`: the due date of an open task
+/// reads as overdue, timestamps (always in the past) as `2d ago`.
+fn apply_filter(value: Value, filter: &Filter, task: &Task, ctx: &RenderContext) -> Value {
+    let relative =
+        |date, done| format_due_date(date, done, DateStyle::Relative, ISO_DATE_FORMAT, ctx.today);
+    match (filter, value) {
+        (Filter::Relative, Value::Date(d)) => Value::Text(relative(d, task.done)),
+        (Filter::Relative, Value::Time(t)) => Value::Text(relative(t.date_naive(), true)),
+        // A format with time fields can't render a plain date; fall back to ISO.
+        (Filter::Date(format), Value::Date(d)) => Value::Text(format_date(d, format)),
+        (Filter::Date(format), Value::Time(t)) => Value::Text(t.format(format).to_string()),
+        (Filter::Join(sep), Value::List(items)) => Value::Text(items.join(sep)),
+        (Filter::Relative | Filter::Date(_) | Filter::Join(_), value) => value,
+        (Filter::Trunc(width), value) => Value::Text(truncate_to_width(&into_text(value), *width)),
+        (Filter::Pad(width), value) => Value::Text(pad_right(&into_text(value), *width)),
+        (Filter::LeftPad(width), value) => {
+            let text = into_text(value);
+            let fill = width.saturating_sub(display_width(&text));
+            Value::Text(format!("{}{text}", " ".repeat(fill)))
+        }
+        (Filter::Upper, value) => Value::Text(into_text(value).to_uppercase()),
+        (Filter::Lower, value) => Value::Text(into_text(value).to_lowercase()),
+        (Filter::Default(fallback), value) => {
+            let text = into_text(value);
+            Value::Text(if text.is_empty() {
+                fallback.clone()
+            } else {
+                text
+            })
+        }
+    }
+}
+
+fn into_text(value: Value) -> String {
+    match value {
+        Value::Text(text) => text,
+        Value::Date(d) => d.to_string(),
+        Value::Time(t) => t.to_rfc3339(),
+        Value::List(items) => items.join(","),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        Task {
+            id: 7,
+            uid: None,
+            title: "Renew passport".to_string(),
+            content: None,
+            tags: Vec::new(),
+            due: NaiveDate::from_ymd_opt(2026, 10, 24),
+            priority: None,
+            repeat: None,
+            branch: "main".to_string(),
+            archived: false,
+            done: false,
+            created_at: "2026-10-18T09:30:00+02:00".to_string(),
+            updated_at: None,
+            completed_at: None,
+        }
+    }
+
+    fn render(source: &str) -> String {
+        let urgency = UrgencyCoefficients::default();
+        let ctx = RenderContext {
+            today: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+            urgency: &urgency,
+        };
+        Template::parse(source).unwrap().render(&task(), &ctx)
+    }
+
+    #[test]
+    fn formats_dates_and_timestamps() {
+        assert_eq!(render("{due:date(%d %b)}"), "24 Oct");
+        assert_eq!(render("{created:date(%H:%M)}"), "09:30");
+    }
+
+    #[test]
+    fn falls_back_to_iso_for_a_date_with_time_fields() {
+        assert_eq!(render("{due:date(%H:%M)}"), "2026-10-24");
+        assert_eq!(render("{id} {due:date(%Y %Z)}"), "7 2026-10-24");
+    }
+}
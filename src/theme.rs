@@ -0,0 +1,430 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+use owo_colors::{AnsiColors, DynColors, OwoColorize, Style};
+use ratatui::style::{Color as TuiColor, Modifier, Style as TuiStyle};
+
+use crate::display::pad_right;
+use crate::model::{ThemeConfig, ThemeElement, ThemePreset};
+
+thread_local! {
+    static ACTIVE: RefCell<Theme> = RefCell::new(Theme::from_config(&ThemeConfig::default()));
+}
+
+/// Resolved styles for every element plus the user's tag and branch colors.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    styles: HashMap<ThemeElement, Style>,
+    /// The same element styles for the terminal UI.
+    tui: HashMap<ThemeElement, TuiStyle>,
+    tags: HashMap<String, Style>,
+    branches: HashMap<String, Style>,
+}
+
+impl Theme {
+    /// Build a theme from config. Specs that no longer parse fall back to the preset.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let specs: Vec<(ThemeElement, StyleSpec)> = ThemeElement::value_variants()
+            .iter()
+            .map(|el| {
+                let spec = config
+                    .styles
+                    .get(el)
+                    .and_then(|spec| parse_spec(spec).ok())
+                    .unwrap_or_else(|| {
+                        parse_spec(preset_spec(config.preset, *el)).unwrap_or_default()
+                    });
+                (*el, spec)
+            })
+            .collect();
+        let styles = specs.iter().map(|(el, spec)| (*el, spec.owo())).collect();
+        let tui = specs.iter().map(|(el, spec)| (*el, spec.tui())).collect();
+        let parse_map = |map: &std::collections::BTreeMap<String, String>| {
+            map.iter()
+                .filter_map(|(name, spec)| Some((name.to_lowercase(), parse_style(spec).ok()?)))
+                .collect()
+        };
+        Self {
+            styles,
+            tui,
+            tags: parse_map(&config.tag_colors),
+            branches: parse_map(&config.branch_colors),
+        }
+    }
+}
+
+/// Make `config` the theme used by `paint` and friends on this thread.
+pub fn install(config: &ThemeConfig) {
+    let theme = Theme::from_config(config);
+    ACTIVE.with(|active| *active.borrow_mut() = theme);
+}
+
+/// Style `text` as `element` of the active theme.
+pub fn paint(element: ThemeElement, text: &str) -> String {
+    ACTIVE.with(|active| {
+        let style = active
+            .borrow()
+            .styles
+            .get(&element)
+            .copied()
+            .unwrap_or_default();
+        text.style(style).to_string()
+    })
+}
+
+/// `element` of the active theme as a terminal UI style.
+pub fn tui_style(element: ThemeElement) -> TuiStyle {
+    ACTIVE.with(|active| {
+        active
+            .borrow()
+            .tui
+            .get(&element)
+            .copied()
+            .unwrap_or_default()
+    })
+}
+
+/// Style `text` with the color assigned to `tag`, or as `fallback` (if any) when it has none.
+pub fn paint_tag(tag: &str, text: &str, fallback: Option<ThemeElement>) -> String {
+    let style = ACTIVE.with(|active| active.borrow().tags.get(&tag.to_lowercase()).copied());
+    paint_named(style, text, fallback)
+}
+
+/// Style `text` with the color assigned to `branch`, or as `fallback` (if any) when it has none.
+pub fn paint_branch(branch: &str, text: &str, fallback: Option<ThemeElement>) -> String {
+    let style = ACTIVE.with(|active| {
+        active
+            .borrow()
+            .branches
+            .get(&branch.to_lowercase())
+            .copied()
+    });
+    paint_named(style, text, fallback)
+}
+
+/// Style a list of tags joined by `sep`, coloring each tag on its own.
+/// `text` may be a truncated rendering of `tags.join(sep)`.
+pub fn paint_tag_list(text: &str, sep: &str, fallback: Option<ThemeElement>) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let sep_painted = match fallback {
+        Some(element) => paint(element, sep),
+        None => sep.to_string(),
+    };
+    text.split(sep)
+        .map(|tag| paint_tag(tag, tag, fallback))
+        .collect::<Vec<_>>()
+        .join(&sep_painted)
+}
+
+fn paint_named(style: Option<Style>, text: &str, fallback: Option<ThemeElement>) -> String {
+    match (style, fallback) {
+        (Some(style), _) => text.style(style).to_string(),
+        (None, Some(element)) => paint(element, text),
+        (None, None) => text.to_string(),
+    }
+}
+
+/// Show every element in its current style, then the tag and branch colors.
+pub fn print_theme(config: &ThemeConfig, color: bool) {
+    println!("theme: {}", value_name(config.preset));
+    let sample = |name: &str, style: String, custom: bool| {
+        let custom = if custom { "  (custom)" } else { "" };
+        println!("  {name}  {style}{custom}");
+    };
+    for element in ThemeElement::value_variants() {
+        let name = pad_right(&value_name(*element), 16);
+        let name = if color { paint(*element, &name) } else { name };
+        let (spec, custom) = match config.styles.get(element) {
+            Some(spec) => (spec.clone(), true),
+            None => (preset_spec(config.preset, *element).to_string(), false),
+        };
+        sample(&name, spec, custom);
+    }
+    for (label, map, is_tag) in [
+        ("tag colors", &config.tag_colors, true),
+        ("branch colors", &config.branch_colors, false),
+    ] {
+        if map.is_empty() {
+            continue;
+        }
+        println!("{label}:");
+        for (name, spec) in map {
+            let padded = pad_right(name, 16);
+            let padded = match (color, is_tag) {
+                (false, _) => padded,
+                (true, true) => paint_tag(name, &padded, None),
+                (true, false) => paint_branch(name, &padded, None),
+            };
+            sample(&padded, spec.clone(), false);
+        }
+    }
+    if !color {
+        println!("(color is off: --no-color, NO_COLOR, `config --color false` or not a terminal)");
+    }
+}
+
+/// The name clap uses for a value, e.g. `high-contrast`.
+pub fn value_name<T: ValueEnum>(value: T) -> String {
+    value
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// The spec a preset uses for `element`.
+pub fn preset_spec(preset: ThemePreset, element: ThemeElement) -> &'static str {
+    use ThemeElement::*;
+    match preset {
+        ThemePreset::Dark => match element {
+            Header => "bright-blue bold",
+            Separator | Muted | Done | Tag | Branch => "dimmed",
+            Title => "bold",
+            StatusOpen | DueToday | PriorityMedium => "yellow bold",
+            StatusDone | PriorityLow => "green bold",
+            Overdue | PriorityHigh => "red bold",
+            DueLater => "cyan",
+            Projection => "dimmed italic",
+            Today => "yellow bold reversed",
+            Greeting => "bright-yellow bold",
+            Message => "bright-magenta bold",
+        },
+        ThemePreset::Light => match element {
+            Header => "blue bold",
+            Separator | Muted | Done | Tag | Branch => "dimmed",
+            Title => "bold",
+            StatusOpen => "blue bold",
+            StatusDone | PriorityLow => "green bold",
+            Overdue | PriorityHigh => "red bold",
+            DueToday | PriorityMedium => "magenta bold",
+            DueLater => "blue",
+            Projection => "dimmed italic",
+            Today => "blue bold reversed",
+            Greeting => "blue bold",
+            Message => "magenta bold",
+        },
+        ThemePreset::HighContrast => match element {
+            Header => "bold underline",
+            Separator | Muted => "none",
+            Title => "bold",
+            Done => "italic",
+            StatusOpen => "bright-yellow bold",
+            StatusDone => "bright-green bold",
+            Overdue | PriorityHigh => "bright-white on red bold",
+            DueToday | PriorityMedium => "black on bright-yellow",
+            DueLater => "bright-cyan bold",
+            PriorityLow => "black on bright-green",
+            Tag => "bright-magenta bold",
+            Branch => "bright-blue bold",
+            Projection => "italic underline",
+            Today => "bold reversed",
+            Greeting => "bright-yellow bold",
+            Message => "bright-magenta bold",
+        },
+        ThemePreset::Monochrome => match element {
+            Header => "bold underline",
+            Separator | DueLater | PriorityMedium | Tag | Branch => "none",
+            Muted | Done | StatusDone | PriorityLow => "dimmed",
+            Title | StatusOpen | DueToday | PriorityHigh => "bold",
+            Overdue => "bold underline",
+            Projection => "italic",
+            Today => "reversed",
+            Greeting | Message => "bold",
+        },
+    }
+}
+
+/// Parse a style spec: effects (`bold`, `dimmed`, `italic`, `underline`, `reversed`,
+/// `strikethrough`), a foreground color and `on <color>` for the background.
+/// Colors are the eight ANSI names, their `bright-` variants or `#rrggbb`; `none` is plain.
+pub fn parse_style(spec: &str) -> Result<Style, String> {
+    parse_spec(spec).map(|spec| spec.owo())
+}
+
+/// A parsed style spec, before it becomes a text style or a terminal UI style.
+#[derive(Debug, Clone, Copy, Default)]
+struct StyleSpec {
+    effects: Modifier,
+    fg: Option<DynColors>,
+    bg: Option<DynColors>,
+}
+
+impl StyleSpec {
+    fn owo(&self) -> Style {
+        let mut style = Style::new();
+        for (effect, apply) in [
+            (Modifier::BOLD, Style::bold as fn(Style) -> Style),
+            (Modifier::DIM, Style::dimmed),
+            (Modifier::ITALIC, Style::italic),
+            (Modifier::UNDERLINED, Style::underline),
+            (Modifier::REVERSED, Style::reversed),
+            (Modifier::CROSSED_OUT, Style::strikethrough),
+        ] {
+            if self.effects.contains(effect) {
+                style = apply(style);
+            }
+        }
+        if let Some(fg) = self.fg {
+            style = style.color(fg);
+        }
+        if let Some(bg) = self.bg {
+            style = style.on_color(bg);
+        }
+        style
+    }
+
+    fn tui(&self) -> TuiStyle {
+        let mut style = TuiStyle::default().add_modifier(self.effects);
+        if let Some(fg) = self.fg.and_then(tui_color) {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = self.bg.and_then(tui_color) {
+            style = style.bg(bg);
+        }
+        style
+    }
+}
+
+fn tui_color(color: DynColors) -> Option<TuiColor> {
+    let color = match color {
+        DynColors::Ansi(ansi) => match ansi {
+            AnsiColors::Black => TuiColor::Black,
+            AnsiColors::Red => TuiColor::Red,
+            AnsiColors::Green => TuiColor::Green,
+            AnsiColors::Yellow => TuiColor::Yellow,
+            AnsiColors::Blue => TuiColor::Blue,
+            AnsiColors::Magenta => TuiColor::Magenta,
+            AnsiColors::Cyan => TuiColor::Cyan,
+            AnsiColors::White => TuiColor::Gray,
+            AnsiColors::Default => TuiColor::Reset,
+            AnsiColors::BrightBlack => TuiColor::DarkGray,
+            AnsiColors::BrightRed => TuiColor::LightRed,
+            AnsiColors::BrightGreen => TuiColor::LightGreen,
+            AnsiColors::BrightYellow => TuiColor::LightYellow,
+            AnsiColors::BrightBlue => TuiColor::LightBlue,
+            AnsiColors::BrightMagenta => TuiColor::LightMagenta,
+            AnsiColors::BrightCyan => TuiColor::LightCyan,
+            AnsiColors::BrightWhite => TuiColor::White,
+        },
+        DynColors::Xterm(xterm) => TuiColor::Indexed(xterm.into()),
+        DynColors::Rgb(r, g, b) => TuiColor::Rgb(r, g, b),
+        DynColors::Css(_) => return None,
+    };
+    Some(color)
+}
+
+fn parse_spec(spec: &str) -> Result<StyleSpec, String> {
+    let words: Vec<String> = spec
+        .split(|c: char| c.is_whitespace() || c == '+' || c == ',')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase().replace('_', "-"))
+        .collect();
+    if words.is_empty() {
+        return Err("empty style (use 'none' for plain text)".to_string());
+    }
+
+    let mut style = StyleSpec::default();
+    let mut background = false;
+    let mut iter = words.iter();
+    while let Some(word) = iter.next() {
+        let word = word.as_str();
+        match word {
+            "none" | "plain" => {}
+            "bold" => style.effects |= Modifier::BOLD,
+            "dim" | "dimmed" => style.effects |= Modifier::DIM,
+            "italic" => style.effects |= Modifier::ITALIC,
+            "underline" | "underlined" => style.effects |= Modifier::UNDERLINED,
+            "reverse" | "reversed" => style.effects |= Modifier::REVERSED,
+            "strike" | "strikethrough" => style.effects |= Modifier::CROSSED_OUT,
+            "on" => {
+                background = true;
+                continue;
+            }
+            _ => {
+                let (word, on) = match word.strip_prefix("on-") {
+                    Some(rest) => (rest, true),
+                    None => (word, background),
+                };
+                // Allow `bright red` as two words.
+                let name = if word == "bright" {
+                    match iter.next() {
+                        Some(next) => format!("bright-{next}"),
+                        None => return Err("expected a color after 'bright'".to_string()),
+                    }
+                } else {
+                    word.to_string()
+                };
+                let color = Some(parse_color(&name)?);
+                if on {
+                    style.bg = color;
+                } else {
+                    style.fg = color;
+                }
+                background = false;
+            }
+        }
+        if background {
+            return Err("expected a color after 'on'".to_string());
+        }
+    }
+    if background {
+        return Err("expected a color after 'on'".to_string());
+    }
+    Ok(style)
+}
+
+fn parse_color(name: &str) -> Result<DynColors, String> {
+    let normalized = match name.strip_prefix("bright-") {
+        Some(base) => format!("bright {base}"),
+        None => name.to_string(),
+    };
+    normalized.parse::<DynColors>().map_err(|_| {
+        format!(
+            "unknown color or effect '{name}' (expected a color like red, bright-blue or #ff8800, \
+             or bold, dimmed, italic, underline, reversed, strikethrough)"
+        )
+    })
+}
+
+/// Parse `<element>=<style>` for `config --theme-style`.
+pub fn parse_element_style(value: &str) -> Result<(ThemeElement, String), String> {
+    let (name, spec) = value
+        .split_once('=')
+        .ok_or_else(|| "expected <element>=<style>, e.g. overdue=bold magenta".to_string())?;
+    let element = ThemeElement::from_str(name.trim(), true).map_err(|_| {
+        let names: Vec<String> = ThemeElement::value_variants()
+            .iter()
+            .map(|el| value_name(*el))
+            .collect();
+        format!(
+            "unknown element '{}' (expected {})",
+            name.trim(),
+            names.join(", ")
+        )
+    })?;
+    Ok((element, validate_spec(spec)?))
+}
+
+/// Parse `<name>=<style>` for `config --tag-color` and `--branch-color`.
+pub fn parse_named_style(value: &str) -> Result<(String, String), String> {
+    let (name, spec) = value
+        .split_once('=')
+        .ok_or_else(|| "expected <name>=<style>, e.g. work=cyan".to_string())?;
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err("name must not be empty".to_string());
+    }
+    Ok((name, validate_spec(spec)?))
+}
+
+/// `default` clears an override and is passed through as an empty spec.
+fn validate_spec(spec: &str) -> Result<String, String> {
+    let spec = spec.trim();
+    if spec.eq_ignore_ascii_case("default") {
+        return Ok(String::new());
+    }
+    parse_style(spec)?;
+    Ok(spec.to_string())
+}
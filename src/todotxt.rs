@@ -0,0 +1,396 @@
+use std::fmt::Write;
+
+use chrono::{Local, NaiveDate, TimeZone};
+
+use crate::app::TaskParts;
+use crate::branch::is_default_branch;
+use crate::model::{ISO_DATE_FORMAT, Priority, Repeat, Task};
+use crate::util::parse_timestamp;
+
+/// Keys `parse_line` reads as task fields rather than title words.
+const KEYS: [&str; 9] = [
+    "due",
+    "rec",
+    "pri",
+    "uid",
+    "archived",
+    "created",
+    "updated",
+    "completed",
+    "note",
+];
+
+/// One todo.txt line per task.
+pub fn write_tasks(view: &[&Task]) -> String {
+    view.iter().map(|task| format_line(task) + "\n").collect()
+}
+
+/// `x <completed> <created> title +branch @tag due:… rec:… uid:… note:…` for done
+/// tasks, `(A) <created> title …` for open ones. Full timestamps and the content
+/// follow as escaped pairs, so nothing is lost on the way back in.
+pub fn format_line(task: &Task) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let created = parse_timestamp(&task.created_at).map(|dt| dt.date_naive());
+    let priority = task.priority.map(|p| match p {
+        Priority::High => 'A',
+        Priority::Medium => 'B',
+        Priority::Low => 'C',
+    });
+
+    if task.done {
+        words.push("x".to_string());
+        // The spec needs a completion date before a creation date.
+        let completed = task
+            .completed_at
+            .as_deref()
+            .or(task.updated_at.as_deref())
+            .and_then(parse_timestamp)
+            .map(|dt| dt.date_naive())
+            .or(created);
+        if let Some(date) = completed {
+            words.push(date.format(ISO_DATE_FORMAT).to_string());
+        }
+    } else if let Some(p) = priority {
+        words.push(format!("({p})"));
+    }
+    if let Some(date) = created {
+        words.push(date.format(ISO_DATE_FORMAT).to_string());
+    }
+    words.push(escape_title(&task.title));
+    if !is_default_branch(&task.branch) {
+        words.push(format!("+{}", word(&task.branch)));
+    }
+    words.extend(task.tags.iter().map(|tag| format!("@{}", word(tag))));
+    if let Some(due) = task.due {
+        words.push(format!("due:{}", due.format(ISO_DATE_FORMAT)));
+    }
+    if let Some(repeat) = task.repeat {
+        let unit = match repeat {
+            Repeat::Daily => "1d",
+            Repeat::Weekly => "1w",
+            Repeat::Monthly => "1m",
+        };
+        words.push(format!("rec:{unit}"));
+    }
+    // Completed tasks lose their `(A)` prefix, so keep the priority as a pair.
+    if task.done
+        && let Some(p) = priority
+    {
+        words.push(format!("pri:{p}"));
+    }
+    if let Some(uid) = task.uid.as_deref() {
+        words.push(format!("uid:{uid}"));
+    }
+    if task.archived {
+        words.push("archived:true".to_string());
+    }
+    let stamps = [
+        ("created", Some(task.created_at.as_str())),
+        ("updated", task.updated_at.as_deref()),
+        ("completed", task.completed_at.as_deref()),
+        ("note", task.content.as_deref()),
+    ];
+    for (key, value) in stamps {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            words.push(format!("{key}:{}", escape_value(value)));
+        }
+    }
+    words.join(" ")
+}
+
+/// Projects and contexts end at whitespace.
+fn word(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+/// Put a `\` before title words `parse_line` would read as metadata, and before
+/// words that already start with one.
+fn escape_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| {
+            if looks_like_metadata(word, i == 0) {
+                format!("\\{word}")
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn looks_like_metadata(word: &str, first: bool) -> bool {
+    word.starts_with('\\')
+        || (word.len() > 1 && word.starts_with(['+', '@', '#']))
+        || word
+            .split_once(':')
+            .is_some_and(|(key, _)| KEYS.contains(&key))
+        || (first && (word == "x" || parse_priority(word).is_some() || parse_date(word).is_some()))
+}
+
+/// Pair values end at whitespace, so whitespace and `%` are written as `%20`, `%0A`, `%25`….
+fn escape_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '%' || c.is_whitespace() {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                let _ = write!(out, "%{byte:02X}");
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn unescape_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = value
+            .get(i + 1..i + 3)
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Every non-blank line of `text` as task parts.
+pub(crate) fn parse_tasks(text: &str) -> Vec<TaskParts> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_line)
+        .collect()
+}
+
+/// One todo.txt line. Unknown `key:value` pairs stay in the title where they were, and
+/// a word starting with `\` is a title word even if it looks like metadata.
+pub(crate) fn parse_line(line: &str) -> TaskParts {
+    let mut parts = TaskParts::default();
+    let mut words = line.split_whitespace().peekable();
+
+    if words.peek() == Some(&"x") {
+        words.next();
+        parts.done = Some(true);
+        if let Some(date) = words.peek().and_then(|w| parse_date(w)) {
+            words.next();
+            parts.completed_at = Some(local_midnight(date));
+        }
+    }
+    if let Some(p) = words.peek().and_then(|w| parse_priority(w)) {
+        words.next();
+        parts.priority = Some(p);
+    }
+    if let Some(date) = words.peek().and_then(|w| parse_date(w)) {
+        words.next();
+        parts.created_at = Some(local_midnight(date));
+    }
+
+    let mut title: Vec<&str> = Vec::new();
+    for word in words {
+        if let Some(literal) = word.strip_prefix('\\') {
+            title.push(literal);
+            continue;
+        }
+        if let Some(project) = word.strip_prefix('+').filter(|p| !p.is_empty()) {
+            // The first project is the branch; any others become tags.
+            if parts.branch.is_none() {
+                parts.branch = Some(project.to_string());
+            } else {
+                parts.tags.push(project.to_string());
+            }
+            continue;
+        }
+        if let Some(tag) = word
+            .strip_prefix('@')
+            .or_else(|| word.strip_prefix('#'))
+            .filter(|t| !t.is_empty())
+        {
+            parts.tags.push(tag.to_string());
+            continue;
+        }
+        if let Some((key, value)) = word.split_once(':')
+            && apply_pair(&mut parts, key, value)
+        {
+            continue;
+        }
+        title.push(word);
+    }
+    parts.title = title.join(" ");
+    parts
+}
+
+/// Apply a known `key:value` pair; false leaves it in the title.
+fn apply_pair(parts: &mut TaskParts, key: &str, value: &str) -> bool {
+    match key {
+        "due" if parse_date(value).is_some() => parts.due = Some(value.to_string()),
+        "rec" => match parse_rec(value) {
+            Some(repeat) => parts.repeat = Some(repeat),
+            None => return false,
+        },
+        "pri" => match parse_priority(&format!("({value})")) {
+            Some(p) => parts.priority = Some(p),
+            None => return false,
+        },
+        "uid" if !value.is_empty() => parts.uid = Some(value.to_string()),
+        "archived" if value == "true" => parts.archived = Some(true),
+        "created" | "updated" | "completed" => {
+            let value = unescape_value(value);
+            if parse_timestamp(&value).is_none() {
+                return false;
+            }
+            let field = match key {
+                "created" => &mut parts.created_at,
+                "updated" => &mut parts.updated_at,
+                _ => &mut parts.completed_at,
+            };
+            *field = Some(value);
+        }
+        "note" => parts.content = Some(unescape_value(value)).filter(|c| !c.is_empty()),
+        _ => return false,
+    }
+    true
+}
+
+/// `(A)` is high, `(B)` medium and `(C)` through `(Z)` low.
+fn parse_priority(word: &str) -> Option<Priority> {
+    let letter = word.strip_prefix('(')?.strip_suffix(')')?;
+    match letter {
+        "A" => Some(Priority::High),
+        "B" => Some(Priority::Medium),
+        l if l.len() == 1 && l.chars().all(|c| c.is_ascii_uppercase()) => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// `rec:1d`, `rec:w`, `rec:+1m`…; longer intervals have no `Repeat`.
+fn parse_rec(value: &str) -> Option<Repeat> {
+    let value = value.strip_prefix('+').unwrap_or(value);
+    let unit = value.strip_prefix('1').unwrap_or(value);
+    match unit {
+        "d" => Some(Repeat::Daily),
+        "w" => Some(Repeat::Weekly),
+        "m" => Some(Repeat::Monthly),
+        _ => None,
+    }
+}
+
+fn parse_date(word: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(word, ISO_DATE_FORMAT).ok()
+}
+
+fn local_midnight(date: NaiveDate) -> String {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| date.format(ISO_DATE_FORMAT).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(title: &str) -> Task {
+        Task {
+            id: 1,
+            uid: Some("7f0c7a52-5b8e-4c57-9c1a-2f3c1e0d9a11".to_string()),
+            title: title.to_string(),
+            content: None,
+            tags: Vec::new(),
+            due: None,
+            priority: None,
+            repeat: None,
+            branch: "personal".to_string(),
+            archived: false,
+            done: false,
+            created_at: "2026-10-18T09:30:00+02:00".to_string(),
+            updated_at: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_every_field() {
+        let task = Task {
+            content: Some("first line\nsecond: 100% sure".to_string()),
+            tags: vec!["home".to_string(), "errand".to_string()],
+            due: NaiveDate::from_ymd_opt(2026, 10, 24),
+            priority: Some(Priority::High),
+            repeat: Some(Repeat::Weekly),
+            branch: "work".to_string(),
+            archived: true,
+            done: true,
+            updated_at: Some("2026-10-19T08:00:00+02:00".to_string()),
+            completed_at: Some("2026-10-19T07:59:30+02:00".to_string()),
+            ..task("Water the plants")
+        };
+        let parts = parse_line(&format_line(&task));
+
+        assert_eq!(parts.title, task.title);
+        assert_eq!(parts.content, task.content);
+        assert_eq!(parts.tags, task.tags);
+        assert_eq!(parts.due.as_deref(), Some("2026-10-24"));
+        assert_eq!(parts.priority, Some(Priority::High));
+        assert!(matches!(parts.repeat, Some(Repeat::Weekly)));
+        assert_eq!(parts.branch.as_deref(), Some("work"));
+        assert_eq!(parts.done, Some(true));
+        assert_eq!(parts.archived, Some(true));
+        assert_eq!(parts.uid, task.uid);
+        assert_eq!(parts.created_at.as_deref(), Some(task.created_at.as_str()));
+        assert_eq!(parts.updated_at, task.updated_at);
+        assert_eq!(parts.completed_at, task.completed_at);
+    }
+
+    #[test]
+    fn keeps_title_words_that_look_like_metadata() {
+        for title in [
+            "x marks the spot",
+            "(A) is not a priority",
+            "2026-01-01 was a date",
+            "Email @bob about +1 votes and #42",
+            "Set due:friday and note:this",
+            r"Escape \this word",
+        ] {
+            let parts = parse_line(&format_line(&task(title)));
+            assert_eq!(parts.title, title);
+            assert!(parts.tags.is_empty(), "{title}");
+            assert_eq!(parts.due, None, "{title}");
+            assert_eq!(parts.content, None, "{title}");
+            assert_eq!(parts.done, None, "{title}");
+        }
+    }
+
+    #[test]
+    fn reads_plain_todo_txt_lines() {
+        let parts = parse_line("x 2026-10-19 2026-10-18 Call mom +family @phone due:2026-10-20");
+        assert_eq!(parts.title, "Call mom");
+        assert_eq!(parts.done, Some(true));
+        assert_eq!(parts.branch.as_deref(), Some("family"));
+        assert_eq!(parts.tags, vec!["phone".to_string()]);
+        assert_eq!(parts.due.as_deref(), Some("2026-10-20"));
+        assert!(
+            parts
+                .created_at
+                .is_some_and(|c| c.starts_with("2026-10-18T00:00:00"))
+        );
+    }
+
+    #[test]
+    fn escaped_values_round_trip() {
+        let value = "a b\tc\n100%";
+        assert_eq!(unescape_value(&escape_value(value)), value);
+        assert!(!escape_value(value).contains(char::is_whitespace));
+    }
+}
@@ -0,0 +1,819 @@
+use std::io::{self, Stdout};
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use chrono::Local;
+use ratatui::Frame;
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::cursor::Show;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::symbols::border;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{
+    Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+};
+
+use crate::app::{create_task, mark_done_with_repeat, next_task_id, task_matches};
+use crate::branch::{collect_branches, normalize_branch, order_branches};
+use crate::display::{
+    CellContext, build_meta_line, column_header, column_text, effective_columns, table_columns,
+    task_view_lines,
+};
+use crate::edit::edit_in_editor;
+use crate::error::TodoError;
+use crate::model::{
+    AppState, ColumnAlign, DateView, IdScope, ListColumn, ListViewStyle, Priority, Task,
+    ThemeElement,
+};
+use crate::output::{Glyph, status_glyph};
+use crate::quick::{QuickAdd, parse_quick_add};
+use crate::sort::sort_indices;
+use crate::storage::{load_tasks, save_tasks};
+use crate::theme::tui_style;
+use crate::util::parse_due;
+
+type Backend = CrosstermBackend<Stdout>;
+
+const TICK: Duration = Duration::from_millis(500);
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Focus {
+    Branches,
+    Tasks,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Prompt {
+    Search,
+    Add,
+    Move,
+    Due,
+    ConfirmDelete,
+}
+
+struct TuiApp<'a> {
+    state: &'a mut AppState,
+    tasks: &'a mut Vec<Task>,
+    path: &'a PathBuf,
+    branches: Vec<String>,
+    branch: String,
+    /// Indices into `tasks` for the current branch/filter, in display order.
+    visible: Vec<usize>,
+    selected: usize,
+    focus: Focus,
+    show_done: bool,
+    search: String,
+    prompt: Option<Prompt>,
+    input: String,
+    message: Option<String>,
+    last_modified: Option<SystemTime>,
+    color: bool,
+}
+
+/// Full-screen interface: branch sidebar, task list and detail pane.
+pub fn run_tui(
+    state: &mut AppState,
+    tasks: &mut Vec<Task>,
+    path: &PathBuf,
+    color: bool,
+) -> Result<(), TodoError> {
+    let mut terminal = start_terminal()
+        .map_err(|e| TodoError::Failed(format!("cannot start the terminal UI: {e}")))?;
+    // A panic must not leave the shell in raw mode on the alternate screen.
+    let previous: Arc<dyn Fn(&PanicHookInfo<'_>) + Send + Sync> = Arc::from(panic::take_hook());
+    let hook = Arc::clone(&previous);
+    panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        hook(info);
+    }));
+
+    let branch = state.current_branch.clone();
+    let mut app = TuiApp {
+        state,
+        tasks,
+        path,
+        branches: Vec::new(),
+        branch,
+        visible: Vec::new(),
+        selected: 0,
+        focus: Focus::Tasks,
+        show_done: false,
+        search: String::new(),
+        prompt: None,
+        input: String::new(),
+        message: None,
+        last_modified: modified_time(path),
+        color,
+    };
+    app.refresh();
+
+    let result = app.run(&mut terminal);
+    let _ = stop_terminal(&mut terminal);
+    panic::set_hook(Box::new(move |info| previous(info)));
+    result.map_err(|e| TodoError::Failed(format!("terminal UI error: {e}")))
+}
+
+fn start_terminal() -> io::Result<Terminal<Backend>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    let terminal = execute!(stdout, EnterAlternateScreen)
+        .and_then(|()| Terminal::new(CrosstermBackend::new(stdout)));
+    if terminal.is_err() {
+        restore_terminal();
+    }
+    terminal
+}
+
+/// Leave raw mode and the alternate screen without a `Terminal`, e.g. from the panic hook.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+}
+
+fn stop_terminal(terminal: &mut Terminal<Backend>) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()
+}
+
+fn is_ctrl(key: KeyEvent, c: char) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char(c)
+}
+
+/// A Ctrl or Alt chord. Both together is AltGr on some terminals, which types a character.
+fn is_chord(modifiers: KeyModifiers) -> bool {
+    let chord = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+    !chord.is_empty() && chord != KeyModifiers::CONTROL | KeyModifiers::ALT
+}
+
+fn modified_time(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl TuiApp<'_> {
+    fn run(&mut self, terminal: &mut Terminal<Backend>) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()?
+                    && key.kind == KeyEventKind::Press
+                    && !self.handle_key(key, terminal)?
+                {
+                    return Ok(());
+                }
+            } else {
+                self.reload_if_changed();
+            }
+        }
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.visible.get(self.selected).copied()
+    }
+
+    /// Recompute branches and the visible task list, keeping the selection in range.
+    fn refresh(&mut self) {
+        let mut branches = collect_branches(self.tasks);
+        if !branches
+            .iter()
+            .any(|b| b.eq_ignore_ascii_case(&self.branch))
+        {
+            branches.push(self.branch.clone());
+        }
+        order_branches(&mut branches, &self.state.current_branch);
+        self.branches = branches;
+
+        let query = self.search.to_lowercase();
+        let mut visible: Vec<usize> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.branch.eq_ignore_ascii_case(&self.branch))
+            .filter(|(_, t)| !t.archived)
+            .filter(|(_, t)| self.show_done || !t.done)
+            .filter(|(_, t)| query.is_empty() || task_matches(t, &query))
+            .map(|(idx, _)| idx)
+            .collect();
+        sort_indices(
+            self.tasks,
+            &mut visible,
+            &self.state.config.default_sort,
+            self.state.config.default_desc,
+            &self.state.config.urgency,
+        );
+        self.visible = visible;
+        if self.selected >= self.visible.len() {
+            self.selected = self.visible.len().saturating_sub(1);
+        }
+    }
+
+    /// Save unless tasks.json changed on disk since it was last read; then the
+    /// other change wins and this one is dropped by reloading.
+    fn save(&mut self) {
+        if modified_time(self.path) != self.last_modified {
+            self.reload("tasks.json changed on disk; reloaded without saving, try again");
+            return;
+        }
+        if let Err(e) = save_tasks(self.path, self.tasks) {
+            self.message = Some(format!("Error: {e}"));
+        }
+        self.last_modified = modified_time(self.path);
+        self.refresh();
+    }
+
+    /// Reread tasks.json; errors go to the message line and keep the tasks in memory.
+    fn reload(&mut self, message: &str) {
+        self.last_modified = modified_time(self.path);
+        match load_tasks(self.path) {
+            Ok(tasks) => {
+                *self.tasks = tasks;
+                self.refresh();
+                self.message = Some(message.to_string());
+            }
+            Err(e) => self.message = Some(format!("Error: {e}")),
+        }
+    }
+
+    fn reload_if_changed(&mut self) {
+        if modified_time(self.path) != self.last_modified {
+            self.reload("Reloaded tasks.json (changed on disk)");
+        }
+    }
+
+    /// Handle a key press. Returns false when the UI should close.
+    fn handle_key(&mut self, key: KeyEvent, terminal: &mut Terminal<Backend>) -> io::Result<bool> {
+        if let Some(prompt) = self.prompt {
+            self.handle_prompt_key(prompt, key);
+            return Ok(true);
+        }
+        self.message = None;
+
+        if is_ctrl(key, 'c') {
+            return Ok(false);
+        }
+        // Raw mode reports Ctrl-X as a plain `x` plus modifiers; don't run the letter's action.
+        if is_chord(key.modifiers) {
+            return Ok(true);
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.focus = match self.focus {
+                    Focus::Branches => Focus::Tasks,
+                    Focus::Tasks => Focus::Branches,
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Left | KeyCode::Char('h') => self.focus = Focus::Branches,
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter => self.focus = Focus::Tasks,
+            KeyCode::Char('/') => self.open_prompt(Prompt::Search, self.search.clone()),
+            KeyCode::Char('a') => self.open_prompt(Prompt::Add, String::new()),
+            KeyCode::Char('A') => {
+                self.show_done = !self.show_done;
+                self.refresh();
+            }
+            KeyCode::Char('r') => self.reload("Reloaded"),
+            KeyCode::Char('x') | KeyCode::Char(' ') => self.toggle_done(),
+            KeyCode::Char('d') if self.selected_index().is_some() => {
+                self.open_prompt(Prompt::ConfirmDelete, String::new());
+            }
+            KeyCode::Char('m') if self.selected_index().is_some() => {
+                self.open_prompt(Prompt::Move, String::new());
+            }
+            KeyCode::Char('D') if self.selected_index().is_some() => {
+                let current = self
+                    .selected_index()
+                    .and_then(|idx| self.tasks[idx].due)
+                    .map(|d| d.to_string())
+                    .unwrap_or_default();
+                self.open_prompt(Prompt::Due, current);
+            }
+            KeyCode::Char('p') => self.cycle_priority(),
+            KeyCode::Char('>') | KeyCode::Char('+') => self.shift_due(1),
+            KeyCode::Char('<') | KeyCode::Char('-') => self.shift_due(-1),
+            KeyCode::Char('e') => self.edit_selected(terminal)?,
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        match self.focus {
+            Focus::Tasks => {
+                if self.visible.is_empty() {
+                    return;
+                }
+                let max = self.visible.len() as isize - 1;
+                self.selected = (self.selected as isize + delta).clamp(0, max) as usize;
+            }
+            Focus::Branches => {
+                if self.branches.is_empty() {
+                    return;
+                }
+                let pos = self
+                    .branches
+                    .iter()
+                    .position(|b| b.eq_ignore_ascii_case(&self.branch))
+                    .unwrap_or(0) as isize;
+                let max = self.branches.len() as isize - 1;
+                let next = (pos + delta).clamp(0, max) as usize;
+                self.branch = self.branches[next].clone();
+                self.selected = 0;
+                self.refresh();
+            }
+        }
+    }
+
+    fn open_prompt(&mut self, prompt: Prompt, initial: String) {
+        self.prompt = Some(prompt);
+        self.input = initial;
+    }
+
+    fn handle_prompt_key(&mut self, prompt: Prompt, key: KeyEvent) {
+        if is_ctrl(key, 'c') || (is_ctrl(key, 'd') && self.input.is_empty()) {
+            self.cancel_prompt(prompt);
+            return;
+        }
+        if is_chord(key.modifiers) {
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => self.cancel_prompt(prompt),
+            KeyCode::Enter => {
+                self.prompt = None;
+                let input = std::mem::take(&mut self.input);
+                self.submit_prompt(prompt, input.trim());
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                if prompt == Prompt::Search {
+                    self.search = self.input.clone();
+                    self.refresh();
+                }
+            }
+            KeyCode::Char(c) => {
+                if prompt == Prompt::ConfirmDelete {
+                    self.prompt = None;
+                    if c == 'y' || c == 'Y' {
+                        self.delete_selected();
+                    }
+                    return;
+                }
+                self.input.push(c);
+                if prompt == Prompt::Search {
+                    self.search = self.input.clone();
+                    self.selected = 0;
+                    self.refresh();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn cancel_prompt(&mut self, prompt: Prompt) {
+        if prompt == Prompt::Search {
+            self.search.clear();
+            self.refresh();
+        }
+        self.prompt = None;
+    }
+
+    fn submit_prompt(&mut self, prompt: Prompt, input: &str) {
+        match prompt {
+            Prompt::Search => {
+                self.search = input.to_string();
+                self.refresh();
+            }
+            Prompt::Add => {
+                if input.is_empty() {
+                    return;
+                }
+                let entry = if self.state.config.quick_add {
+                    match parse_quick_add(input, Local::now().date_naive()) {
+                        Ok(entry) => entry,
+                        Err(e) => {
+                            self.message = Some(format!("Invalid quick-add title: {e}"));
+                            return;
+                        }
+                    }
+                } else {
+                    QuickAdd {
+                        title: input.to_string(),
+                        ..QuickAdd::default()
+                    }
+                };
+                let entry = QuickAdd {
+                    branch: entry.branch.or_else(|| Some(self.branch.clone())),
+                    ..entry
+                };
+                let pos = create_task(self.tasks, self.state, entry, None);
+                self.message = Some(format!("Created task #{}", self.tasks[pos].id));
+                self.save();
+            }
+            Prompt::Move => {
+                let Some(idx) = self.selected_index() else {
+                    return;
+                };
+                let Some(branch) = normalize_branch(Some(input.to_string())) else {
+                    return;
+                };
+                let next_id = next_task_id(self.tasks, self.state.config.id_scope, Some(&branch));
+                let task = &mut self.tasks[idx];
+                task.branch = branch.clone();
+                if self.state.config.id_scope == IdScope::Branch {
+                    task.id = next_id;
+                }
+                task.touch();
+                self.message = Some(format!("Moved to '{branch}'"));
+                self.save();
+            }
+            Prompt::Due => {
+                let Some(idx) = self.selected_index() else {
+                    return;
+                };
+                if input.is_empty() {
+                    self.tasks[idx].due = None;
+                } else {
+                    match parse_due(input) {
+                        Ok(due) => self.tasks[idx].due = Some(due),
+                        Err(e) => {
+                            self.message = Some(format!("Invalid due date: {e}"));
+                            return;
+                        }
+                    }
+                }
+                self.tasks[idx].touch();
+                self.save();
+            }
+            Prompt::ConfirmDelete => {}
+        }
+    }
+
+    fn toggle_done(&mut self) {
+        let Some(idx) = self.selected_index() else {
+            return;
+        };
+        let branch = self.tasks[idx].branch.clone();
+        let mut next_id = next_task_id(self.tasks, self.state.config.id_scope, Some(&branch));
+        let use_uuid = self.state.config.use_uuid;
+        let task = &mut self.tasks[idx];
+        if task.done {
+            task.set_done(false);
+        } else if let Some(next_task) = mark_done_with_repeat(task, &mut next_id, use_uuid) {
+            self.tasks.push(next_task);
+        }
+        self.save();
+    }
+
+    fn delete_selected(&mut self) {
+        let Some(idx) = self.selected_index() else {
+            return;
+        };
+        let task = self.tasks.remove(idx);
+        self.message = Some(format!("Deleted #{}", task.id));
+        self.save();
+    }
+
+    fn cycle_priority(&mut self) {
+        let Some(idx) = self.selected_index() else {
+            return;
+        };
+        let task = &mut self.tasks[idx];
+        task.priority = match task.priority {
+            None => Some(Priority::Low),
+            Some(Priority::Low) => Some(Priority::Medium),
+            Some(Priority::Medium) => Some(Priority::High),
+            Some(Priority::High) => None,
+        };
+        task.touch();
+        self.save();
+    }
+
+    fn shift_due(&mut self, days: i64) {
+        let Some(idx) = self.selected_index() else {
+            return;
+        };
+        let task = &mut self.tasks[idx];
+        let base = task.due.unwrap_or_else(|| Local::now().date_naive());
+        task.due = Some(base + chrono::Duration::days(days));
+        task.touch();
+        self.save();
+    }
+
+    fn edit_selected(&mut self, terminal: &mut Terminal<Backend>) -> io::Result<()> {
+        let Some(idx) = self.selected_index() else {
+            return Ok(());
+        };
+        stop_terminal(terminal)?;
+        let result = edit_in_editor(self.tasks, &[idx], &self.state.config);
+        enable_raw_mode()?;
+        execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+        terminal.clear()?;
+
+        match result {
+            Ok(0) => self.message = Some("No changes".to_string()),
+            Ok(_) => {
+                self.message = Some(format!("Updated #{}", self.tasks[idx].id));
+                self.save();
+            }
+            Err(e) => self.message = Some(e),
+        }
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+        let [sidebar, list, detail] = Layout::horizontal([
+            Constraint::Length(22),
+            Constraint::Min(30),
+            Constraint::Percentage(30),
+        ])
+        .areas(main);
+
+        self.draw_branches(frame, sidebar);
+        self.draw_tasks(frame, list);
+        self.draw_detail(frame, detail);
+        self.draw_footer(frame, footer);
+    }
+
+    /// `element` of the theme, or plain when color is off.
+    fn style(&self, element: ThemeElement) -> Style {
+        if self.color {
+            tui_style(element)
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Dimmed text: the theme's muted style, or plain dim without color.
+    fn muted(&self) -> Style {
+        if self.color {
+            tui_style(ThemeElement::Muted)
+        } else {
+            Style::default().add_modifier(Modifier::DIM)
+        }
+    }
+
+    fn focused_block(&self, title: String, focus: Focus) -> Block<'static> {
+        let style = if self.focus == focus {
+            self.style(ThemeElement::Header)
+        } else {
+            self.muted()
+        };
+        bordered_block().border_style(style).title(title)
+    }
+
+    fn draw_branches(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .branches
+            .iter()
+            .map(|branch| {
+                let open = self
+                    .tasks
+                    .iter()
+                    .filter(|t| t.branch.eq_ignore_ascii_case(branch))
+                    .filter(|t| !t.done && !t.archived)
+                    .count();
+                let mark = if branch.eq_ignore_ascii_case(&self.state.current_branch) {
+                    "*"
+                } else {
+                    " "
+                };
+                ListItem::new(format!("{mark} {branch} ({open})"))
+            })
+            .collect();
+        let selected = self
+            .branches
+            .iter()
+            .position(|b| b.eq_ignore_ascii_case(&self.branch));
+        let list = List::new(items)
+            .block(self.focused_block(" Branches ".to_string(), Focus::Branches))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(selected);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_tasks(&self, frame: &mut Frame, area: Rect) {
+        let mut title = format!(" {} ({}) ", self.branch, self.visible.len());
+        if !self.search.is_empty() {
+            title = format!(
+                " {} {} /{} ({}) ",
+                self.branch,
+                Glyph::Separator.as_str(),
+                self.search,
+                self.visible.len()
+            );
+        }
+        let block = self.focused_block(title, Focus::Tasks);
+        let ctx = CellContext::new(self.state, self.state.profile.list_view.into());
+        let cols = effective_columns(self.state);
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+
+        if matches!(self.state.profile.list_view, ListViewStyle::Table) {
+            let table_cols = table_columns(self.state);
+            let mut header = vec![Cell::from(" "), Cell::from("ID"), Cell::from("Title")];
+            header.extend(
+                table_cols
+                    .iter()
+                    .map(|c| aligned_cell(column_header(c.kind).to_string(), c.align)),
+            );
+            let mut widths = vec![
+                Constraint::Length(1),
+                Constraint::Length(4),
+                Constraint::Min(12),
+            ];
+            widths.extend(
+                table_cols
+                    .iter()
+                    .map(|c| Constraint::Length(c.width as u16)),
+            );
+
+            let rows: Vec<Row> = self
+                .visible
+                .iter()
+                .map(|idx| {
+                    let task = &self.tasks[*idx];
+                    let mut cells = vec![
+                        Cell::from(status_glyph(task.done)).style(self.status_style(task)),
+                        Cell::from(format!("#{}", task.id)),
+                        Cell::from(task.title.clone()),
+                    ];
+                    cells.extend(table_cols.iter().map(|c| {
+                        aligned_cell(column_text(c.kind, task, &ctx), c.align)
+                            .style(self.cell_style(c.kind, task, ctx.today))
+                    }));
+                    Row::new(cells).style(self.task_style(task))
+                })
+                .collect();
+
+            let table = Table::new(rows, widths)
+                .header(Row::new(header).style(self.style(ThemeElement::Header)))
+                .block(block)
+                .row_highlight_style(highlight);
+            let mut state = TableState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(table, area, &mut state);
+            return;
+        }
+
+        let two_line = !matches!(self.state.profile.list_view, ListViewStyle::Compact);
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|idx| {
+                let task = &self.tasks[*idx];
+                let head = Line::from(vec![
+                    Span::styled(status_glyph(task.done), self.status_style(task)),
+                    Span::raw(format!(" #{:>3}  {}", task.id, task.title)),
+                ]);
+                let meta = build_meta_line(task, &cols, &ctx);
+                let muted = self.muted();
+                let text = if meta.is_empty() {
+                    Text::from(head)
+                } else if two_line {
+                    Text::from(vec![
+                        head,
+                        Line::from(Span::styled(format!("      {meta}"), muted)),
+                    ])
+                } else {
+                    let mut spans = head.spans;
+                    spans.push(Span::styled(
+                        format!("  {}  {meta}", Glyph::Dash.as_str()),
+                        muted,
+                    ));
+                    Text::from(Line::from(spans))
+                };
+                ListItem::new(text).style(self.task_style(task))
+            })
+            .collect();
+        let list = List::new(items).block(block).highlight_style(highlight);
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_detail(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = match self.selected_index() {
+            Some(idx) => task_view_lines(
+                &self.tasks[idx],
+                &CellContext::new(self.state, DateView::Detail),
+                false,
+            )
+            .into_iter()
+            .map(Line::from)
+            .collect(),
+            None => vec![Line::from("No task selected")],
+        };
+        let detail = Paragraph::new(lines)
+            .block(bordered_block().title(" Task "))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(detail, area);
+    }
+
+    fn draw_footer(&self, frame: &mut Frame, area: Rect) {
+        let text = match self.prompt {
+            Some(Prompt::Search) => format!("/{}", self.input),
+            Some(Prompt::Add) => format!("New task: {}", self.input),
+            Some(Prompt::Move) => format!("Move to branch: {}", self.input),
+            Some(Prompt::Due) => format!(
+                "Due (today|tomorrow|YYYY-MM-DD, empty clears): {}",
+                self.input
+            ),
+            Some(Prompt::ConfirmDelete) => "Delete this task? (y/n)".to_string(),
+            None => self.message.clone().unwrap_or_else(|| {
+                [
+                    "q quit",
+                    "tab focus",
+                    "x done",
+                    "e edit",
+                    "d delete",
+                    "m move",
+                    "p priority",
+                    "+/- due",
+                    "D set due",
+                    "a add",
+                    "/ search",
+                    "A show done",
+                ]
+                .join(&format!(" {} ", Glyph::Separator.as_str()))
+            }),
+        };
+        frame.render_widget(Paragraph::new(text).style(self.muted()), area);
+    }
+
+    fn status_style(&self, task: &Task) -> Style {
+        if task.done {
+            self.style(ThemeElement::StatusDone)
+        } else {
+            self.style(ThemeElement::StatusOpen)
+        }
+    }
+
+    fn task_style(&self, task: &Task) -> Style {
+        if !task.done {
+            Style::default()
+        } else if self.color {
+            tui_style(ThemeElement::Done)
+        } else {
+            Style::default().add_modifier(Modifier::DIM)
+        }
+    }
+
+    fn cell_style(&self, c: ListColumn, task: &Task, today: chrono::NaiveDate) -> Style {
+        if task.done {
+            return Style::default();
+        }
+        match c {
+            ListColumn::Due => match task.due {
+                Some(due) if due < today => self.style(ThemeElement::Overdue),
+                Some(due) if due == today => self.style(ThemeElement::DueToday),
+                Some(_) => self.style(ThemeElement::DueLater),
+                None => Style::default(),
+            },
+            ListColumn::Priority => match task.priority {
+                Some(Priority::High) => self.style(ThemeElement::PriorityHigh),
+                Some(Priority::Medium) => self.style(ThemeElement::PriorityMedium),
+                Some(Priority::Low) => self.style(ThemeElement::PriorityLow),
+                None => Style::default(),
+            },
+            _ => self.muted(),
+        }
+    }
+}
+
+/// A bordered block drawn with the output glyphs, so `--ascii` gets `+`, `-` and `|`.
+fn bordered_block() -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_set(border::Set {
+            top_left: Glyph::TopLeft.as_str(),
+            top_right: Glyph::TopRight.as_str(),
+            bottom_left: Glyph::BottomLeft.as_str(),
+            bottom_right: Glyph::BottomRight.as_str(),
+            vertical_left: Glyph::Vertical.as_str(),
+            vertical_right: Glyph::Vertical.as_str(),
+            horizontal_top: Glyph::Rule.as_str(),
+            horizontal_bottom: Glyph::Rule.as_str(),
+        })
+}
+
+fn aligned_cell(text: String, align: ColumnAlign) -> Cell<'static> {
+    let alignment = match align {
+        ColumnAlign::Left => Alignment::Left,
+        ColumnAlign::Right => Alignment::Right,
+        ColumnAlign::Center => Alignment::Center,
+    };
+    Cell::from(Text::from(text).alignment(alignment))
+}
@@ -0,0 +1,338 @@
+use chrono::NaiveDate;
+
+use crate::model::{Priority, Task, UrgencyCoefficients};
+use crate::util::parse_timestamp;
+
+/// Tag that marks a task as blocked for the urgency score.
+pub const BLOCKED_TAG: &str = "blocked";
+
+/// Coefficient names accepted by `config --urgency <name>=<weight>`; `tag.<name>` sets a per-tag weight.
+pub const COEFFICIENT_NAMES: [&str; 9] = [
+    "due",
+    "overdue",
+    "priority-high",
+    "priority-medium",
+    "priority-low",
+    "age",
+    "tags",
+    "blocked",
+    "repeat",
+];
+
+/// Taskwarrior-style urgency: a weighted sum of factors in 0.0..=1.0.
+///
+/// - due: 0.2 two weeks out or more, rising linearly to 1.0 at a week overdue
+/// - overdue: days overdue, saturating at 30
+/// - priority: the coefficient for the task's priority
+/// - age: days since creation, saturating at a year
+/// - tags: 0.8, 0.9 or 1.0 for one, two or three and more tags, plus each tag's own weight
+/// - blocked: tagged `blocked`
+/// - repeat: the task repeats
+///
+/// Completed tasks score 0.
+pub fn urgency(task: &Task, c: &UrgencyCoefficients, today: NaiveDate) -> f64 {
+    if task.done {
+        return 0.0;
+    }
+
+    let mut score = 0.0;
+    if let Some(due) = task.due {
+        let overdue_days = (today - due).num_days() as f64;
+        let due_factor = if overdue_days >= 7.0 {
+            1.0
+        } else if overdue_days >= -14.0 {
+            (overdue_days + 14.0) * 0.8 / 21.0 + 0.2
+        } else {
+            0.2
+        };
+        score += c.due * due_factor;
+        if overdue_days > 0.0 {
+            score += c.overdue * (overdue_days / 30.0).min(1.0);
+        }
+    }
+
+    score += match task.priority {
+        Some(Priority::High) => c.priority_high,
+        Some(Priority::Medium) => c.priority_medium,
+        Some(Priority::Low) => c.priority_low,
+        None => 0.0,
+    };
+
+    if let Some(created) = parse_timestamp(&task.created_at) {
+        let age_days = (today - created.date_naive()).num_days().max(0) as f64;
+        score += c.age * (age_days / 365.0).min(1.0);
+    }
+
+    let tag_factor = match task.tags.len() {
+        0 => 0.0,
+        1 => 0.8,
+        2 => 0.9,
+        _ => 1.0,
+    };
+    score += c.tags * tag_factor;
+    for tag in &task.tags {
+        score += c.tag_weights.get(tag).copied().unwrap_or(0.0);
+    }
+
+    if task.tags.iter().any(|t| t == BLOCKED_TAG) {
+        score += c.blocked;
+    }
+    if task.repeat.is_some() {
+        score += c.repeat;
+    }
+    score
+}
+
+/// Parse `<name>=<weight>` for `config --urgency`.
+pub fn parse_urgency_setting(value: &str) -> Result<(String, f64), String> {
+    let Some((name, weight)) = value.split_once('=') else {
+        return Err("expected <name>=<weight>, e.g. due=12 or tag.work=3".to_string());
+    };
+    let name = name.trim().to_lowercase().replace('_', "-");
+    let weight: f64 = weight
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a number", weight.trim()))?;
+    if !weight.is_finite() {
+        return Err("weight must be a finite number".to_string());
+    }
+
+    let known = COEFFICIENT_NAMES.contains(&name.as_str());
+    let tag = name
+        .strip_prefix("tag.")
+        .is_some_and(|t| !t.trim().is_empty());
+    if !known && !tag {
+        return Err(format!(
+            "unknown coefficient '{name}' (expected {} or tag.<name>)",
+            COEFFICIENT_NAMES.join(", ")
+        ));
+    }
+    Ok((name, weight))
+}
+
+/// Apply a setting returned by `parse_urgency_setting`. A tag weight of 0 removes it.
+pub fn set_coefficient(c: &mut UrgencyCoefficients, name: &str, weight: f64) {
+    match name {
+        "due" => c.due = weight,
+        "overdue" => c.overdue = weight,
+        "priority-high" => c.priority_high = weight,
+        "priority-medium" => c.priority_medium = weight,
+        "priority-low" => c.priority_low = weight,
+        "age" => c.age = weight,
+        "tags" => c.tags = weight,
+        "blocked" => c.blocked = weight,
+        "repeat" => c.repeat = weight,
+        _ => {
+            if let Some(tag) = name.strip_prefix("tag.") {
+                let tag = tag.trim().to_lowercase();
+                if weight == 0.0 {
+                    c.tag_weights.remove(&tag);
+                } else {
+                    c.tag_weights.insert(tag, weight);
+                }
+            }
+        }
+    }
+}
+
+/// `name=weight` pairs in `COEFFICIENT_NAMES` order, followed by the tag weights.
+pub fn describe_coefficients(c: &UrgencyCoefficients) -> Vec<String> {
+    let values = [
+        c.due,
+        c.overdue,
+        c.priority_high,
+        c.priority_medium,
+        c.priority_low,
+        c.age,
+        c.tags,
+        c.blocked,
+        c.repeat,
+    ];
+    let mut out: Vec<String> = COEFFICIENT_NAMES
+        .iter()
+        .zip(values)
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    out.extend(
+        c.tag_weights
+            .iter()
+            .map(|(tag, value)| format!("tag.{tag}={value}")),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    fn task() -> Task {
+        Task {
+            id: 1,
+            uid: None,
+            title: "Task".to_string(),
+            content: None,
+            tags: Vec::new(),
+            due: None,
+            priority: None,
+            repeat: None,
+            branch: "main".to_string(),
+            archived: false,
+            done: false,
+            created_at: "2026-10-18T09:00:00+00:00".to_string(),
+            updated_at: None,
+            completed_at: None,
+        }
+    }
+
+    fn score(task: &Task) -> f64 {
+        urgency(task, &UrgencyCoefficients::default(), today())
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn due_in(days: i64) -> Option<NaiveDate> {
+        Some(today() + chrono::Duration::days(days))
+    }
+
+    #[test]
+    fn weighs_the_due_date() {
+        assert_close(score(&task()), 0.0);
+        assert_close(
+            score(&Task {
+                due: due_in(30),
+                ..task()
+            }),
+            12.0 * 0.2,
+        );
+        assert_close(
+            score(&Task {
+                due: due_in(14),
+                ..task()
+            }),
+            12.0 * 0.2,
+        );
+        assert_close(
+            score(&Task {
+                due: due_in(0),
+                ..task()
+            }),
+            12.0 * (14.0 * 0.8 / 21.0 + 0.2),
+        );
+        // A week overdue: the full due weight plus 7/30 of the overdue weight.
+        assert_close(
+            score(&Task {
+                due: due_in(-7),
+                ..task()
+            }),
+            12.0 + 3.0 * 7.0 / 30.0,
+        );
+        assert_close(
+            score(&Task {
+                due: due_in(-60),
+                ..task()
+            }),
+            12.0 + 3.0,
+        );
+    }
+
+    #[test]
+    fn adds_priority_age_tags_and_repeat() {
+        let high = Task {
+            priority: Some(Priority::High),
+            ..task()
+        };
+        assert_close(score(&high), 6.0);
+
+        let old = Task {
+            created_at: "2024-10-18T09:00:00+00:00".to_string(),
+            ..task()
+        };
+        assert_close(score(&old), 2.0);
+
+        let tagged = Task {
+            tags: vec!["home".to_string(), "errand".to_string()],
+            ..task()
+        };
+        assert_close(score(&tagged), 0.9);
+
+        let blocked = Task {
+            tags: vec![BLOCKED_TAG.to_string()],
+            ..task()
+        };
+        assert_close(score(&blocked), 0.8 - 5.0);
+
+        let repeating = Task {
+            repeat: Some(crate::model::Repeat::Weekly),
+            ..task()
+        };
+        assert_close(score(&repeating), -1.0);
+    }
+
+    #[test]
+    fn completed_tasks_score_zero() {
+        let done = Task {
+            done: true,
+            due: due_in(-7),
+            priority: Some(Priority::High),
+            ..task()
+        };
+        assert_close(score(&done), 0.0);
+    }
+
+    #[test]
+    fn parses_and_applies_settings() {
+        let mut c = UrgencyCoefficients::default();
+        let (name, weight) = parse_urgency_setting(" Priority_High = 7.5 ").unwrap();
+        assert_eq!(name, "priority-high");
+        set_coefficient(&mut c, &name, weight);
+        assert_close(c.priority_high, 7.5);
+
+        let (name, weight) = parse_urgency_setting("tag.Work=3").unwrap();
+        set_coefficient(&mut c, &name, weight);
+        assert_eq!(c.tag_weights.get("work"), Some(&3.0));
+        let tagged = Task {
+            tags: vec!["work".to_string()],
+            ..task()
+        };
+        assert_close(urgency(&tagged, &c, today()), 0.8 + 3.0);
+
+        set_coefficient(&mut c, "tag.work", 0.0);
+        assert!(c.tag_weights.is_empty());
+
+        assert!(parse_urgency_setting("size=1").is_err());
+        assert!(parse_urgency_setting("tag.=1").is_err());
+        assert!(parse_urgency_setting("due").is_err());
+        assert!(parse_urgency_setting("due=abc").is_err());
+        assert!(parse_urgency_setting("due=inf").is_err());
+    }
+
+    #[test]
+    fn describes_coefficients_in_name_order() {
+        let mut c = UrgencyCoefficients::default();
+        c.tag_weights.insert("work".to_string(), 3.0);
+        assert_eq!(
+            describe_coefficients(&c),
+            vec![
+                "due=12",
+                "overdue=3",
+                "priority-high=6",
+                "priority-medium=3.9",
+                "priority-low=1.8",
+                "age=2",
+                "tags=1",
+                "blocked=-5",
+                "repeat=-1",
+                "tag.work=3",
+            ]
+        );
+    }
+}
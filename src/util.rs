@@ -1,7 +1,12 @@
-use chrono::{Local, Months, NaiveDate};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, Local, Months, NaiveDate, NaiveDateTime, TimeZone};
+use clap::ValueEnum;
+use std::fmt::Write as _;
 use std::io::{self, Write};
 
-use crate::model::{Priority, Repeat};
+use crate::model::{
+    ColumnAlign, DateStyle, DateView, ISO_DATE_FORMAT, ListColumn, Priority, Repeat,
+};
 
 pub fn parse_due(s: &str) -> Result<NaiveDate, String> {
     let s = s.trim().to_lowercase();
@@ -28,6 +33,213 @@ pub fn parse_due(s: &str) -> Result<NaiveDate, String> {
     Err("expected today|tomorrow|YYYY-MM-DD|DDMMYYYY".into())
 }
 
+/// Parse a look-ahead span like `30d`, `6w` or `3m` (a bare number means days) into the end date.
+pub fn parse_horizon(s: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let s = s.trim().to_lowercase();
+    let (count, unit) = match s.char_indices().last() {
+        Some((idx, c)) if c.is_ascii_alphabetic() => (&s[..idx], c),
+        _ => (s.as_str(), 'd'),
+    };
+    let count: u32 = count
+        .parse()
+        .map_err(|_| "expected a span like 30d, 6w or 3m".to_string())?;
+    let end = match unit {
+        'd' => today.checked_add_days(chrono::Days::new(count as u64)),
+        'w' => today.checked_add_days(chrono::Days::new(count as u64 * 7)),
+        'm' => today.checked_add_months(Months::new(count)),
+        _ => return Err("expected a span like 30d, 6w or 3m".to_string()),
+    };
+    end.ok_or_else(|| "date overflow".to_string())
+}
+
+/// Describe `date` relative to `today`: `today`, `tomorrow`, `in 3 days`, `2 weeks ago`, ...
+pub fn format_relative_date(date: NaiveDate, today: NaiveDate) -> String {
+    let days = (date - today).num_days();
+    match days {
+        0 => return "today".to_string(),
+        1 => return "tomorrow".to_string(),
+        -1 => return "yesterday".to_string(),
+        _ => {}
+    }
+    let n = days.unsigned_abs();
+    let (count, unit) = if n < 14 {
+        (n, "day")
+    } else if n < 60 {
+        (n / 7, "week")
+    } else if n < 365 {
+        (n / 30, "month")
+    } else {
+        (n / 365, "year")
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    if days > 0 {
+        format!("in {count} {unit}{plural}")
+    } else {
+        format!("{count} {unit}{plural} ago")
+    }
+}
+
+/// Write `due` the way `style` asks. Open tasks past their date read as overdue.
+pub fn format_due_date(
+    due: NaiveDate,
+    done: bool,
+    style: DateStyle,
+    format: &str,
+    today: NaiveDate,
+) -> String {
+    let days = (due - today).num_days();
+    match (style, days) {
+        (DateStyle::Absolute, _) => format_date(due, format),
+        (_, 0) => "today".to_string(),
+        (_, 1) => "tomorrow".to_string(),
+        (_, ..0) if !done => format!("{} overdue", format_short_span(days)),
+        (DateStyle::Human, -1) => "yesterday".to_string(),
+        (DateStyle::Human, 2..=6) => due.format("%a").to_string(),
+        (DateStyle::Human, _) => format_date(due, format),
+        (DateStyle::Relative, ..0) => format!("{} ago", format_short_span(days)),
+        (DateStyle::Relative, _) => format!("in {}", format_short_span(days)),
+    }
+}
+
+/// Parse `<view>=<style>` for `settings --date-style-for`; `default` drops the view's own style.
+pub fn parse_view_date_style(value: &str) -> Result<(DateView, Option<DateStyle>), String> {
+    let (view, style) = value
+        .split_once('=')
+        .ok_or_else(|| "expected <view>=<style>, e.g. cards=relative".to_string())?;
+    let view = DateView::from_str(view.trim(), true).map_err(|_| {
+        format!(
+            "unknown view '{}' (expected {})",
+            view.trim(),
+            value_names::<DateView>()
+        )
+    })?;
+    let style = style.trim();
+    if style.eq_ignore_ascii_case("default") {
+        return Ok((view, None));
+    }
+    let style = DateStyle::from_str(style, true).map_err(|_| {
+        format!(
+            "unknown date style '{style}' (expected {} or default)",
+            value_names::<DateStyle>()
+        )
+    })?;
+    Ok((view, Some(style)))
+}
+
+/// Parse `<column>=<width>` for `settings --column-width`; `default` drops the override.
+pub fn parse_column_width(value: &str) -> Result<(ListColumn, Option<usize>), String> {
+    parse_column_setting(value, "tags=24", |width| {
+        width
+            .parse::<usize>()
+            .ok()
+            .filter(|w| (1..=200).contains(w))
+            .ok_or_else(|| format!("width must be a number from 1 to 200, got '{width}'"))
+    })
+}
+
+/// Parse `<column>=<left|right|center>` for `settings --column-align`.
+pub fn parse_column_align(value: &str) -> Result<(ListColumn, Option<ColumnAlign>), String> {
+    parse_column_setting(value, "urgency=right", |align| {
+        ColumnAlign::from_str(align, true).map_err(|_| {
+            format!(
+                "unknown alignment '{align}' (expected {} or default)",
+                value_names::<ColumnAlign>()
+            )
+        })
+    })
+}
+
+fn parse_column_setting<T>(
+    value: &str,
+    example: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<(ListColumn, Option<T>), String> {
+    let (column, setting) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected <column>=<value>, e.g. {example}"))?;
+    let column = ListColumn::from_str(column.trim(), true).map_err(|_| {
+        format!(
+            "unknown column '{}' (expected {})",
+            column.trim(),
+            value_names::<ListColumn>()
+        )
+    })?;
+    let setting = setting.trim();
+    if setting.eq_ignore_ascii_case("default") {
+        return Ok((column, None));
+    }
+    Ok((column, Some(parse(setting)?)))
+}
+
+fn value_names<T: ValueEnum>() -> String {
+    T::value_variants()
+        .iter()
+        .filter_map(|v| v.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `3d`, `2w`, `4mo` or `1y` for a distance in days, with the same steps as `format_relative_date`.
+pub fn format_short_span(days: i64) -> String {
+    let n = days.unsigned_abs();
+    if n < 14 {
+        format!("{n}d")
+    } else if n < 60 {
+        format!("{}w", n / 7)
+    } else if n < 365 {
+        format!("{}mo", n / 30)
+    } else {
+        format!("{}y", n / 365)
+    }
+}
+
+/// Format `date` with a strftime `format`, falling back to ISO if the format can't be used.
+pub fn format_date(date: NaiveDate, format: &str) -> String {
+    let mut out = String::new();
+    if write!(out, "{}", date.format(format)).is_err() {
+        return date.format(ISO_DATE_FORMAT).to_string();
+    }
+    out
+}
+
+/// Check that `format` is a strftime format that works on plain dates.
+pub fn validate_date_format(format: &str) -> Result<(), String> {
+    if format.trim().is_empty() {
+        return Err("date format must not be empty".to_string());
+    }
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("invalid date format '{format}'"));
+    }
+    let mut out = String::new();
+    if write!(out, "{}", NaiveDate::MIN.format(format)).is_err() {
+        return Err(format!(
+            "date format '{format}' needs a time or zone; use date fields like %d, %m, %Y, %b, %a"
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a stored timestamp: RFC 3339, or a naive `YYYY-MM-DD[ HH:MM[:SS]]` in local time.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<FixedOffset>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt);
+    }
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.fixed_offset())
+}
+
 pub fn priority_rank(p: Option<Priority>) -> u8 {
     match p {
         Some(Priority::High) => 0,
@@ -61,6 +273,17 @@ pub fn normalize_tags(values: &[String]) -> Vec<String> {
     tags
 }
 
+/// Like `normalize_tags`, but keeps the given order (first occurrence wins).
+pub fn normalize_tags_ordered(values: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in values.iter().filter_map(|t| normalize_tag(t)) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
 pub fn advance_due(due: NaiveDate, repeat: Repeat) -> Option<NaiveDate> {
     match repeat {
         Repeat::Daily => due.checked_add_days(chrono::Days::new(1)),
@@ -69,6 +292,20 @@ pub fn advance_due(due: NaiveDate, repeat: Repeat) -> Option<NaiveDate> {
     }
 }
 
+/// Parse a CSV delimiter: a single ASCII character, or `tab`.
+pub fn parse_delimiter(value: &str) -> Result<u8, String> {
+    match value {
+        "tab" | "\\t" | "\t" => Ok(b'\t'),
+        _ => match value.as_bytes() {
+            [b'"'] | [b'\n'] | [b'\r'] => {
+                Err("the delimiter can't be a quote or line break".to_string())
+            }
+            [byte] if byte.is_ascii() => Ok(*byte),
+            _ => Err("expected a single ASCII character or `tab`".to_string()),
+        },
+    }
+}
+
 pub fn parse_bool_flag(value: &str) -> Option<bool> {
     match value.trim().to_lowercase().as_str() {
         "true" | "1" | "yes" | "y" => Some(true),